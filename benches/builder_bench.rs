@@ -1,7 +1,7 @@
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
-use halo_space::Flavor;
 use halo_space::modifiers::Builder;
 use halo_space::select::SelectBuilder;
+use halo_space::{Flavor, PreparedTemplate};
 
 fn bench_select_build(c: &mut Criterion) {
     c.bench_function("select_build_join_where_mysql", |b| {
@@ -19,5 +19,78 @@ fn bench_select_build(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_select_build);
+/// A base query of realistic size: many columns, joins and bound filters.
+fn base_query() -> SelectBuilder {
+    let mut sb = SelectBuilder::new();
+    sb.select(
+        (0..24)
+            .map(|i| format!("users.col_{i}"))
+            .collect::<Vec<_>>(),
+    );
+    sb.from(["users"]);
+    for i in 0..4 {
+        sb.join(format!("ext_{i}"), [format!("ext_{i}.user_id = users.id")]);
+    }
+    for i in 0..16 {
+        let cond = sb.equal(&format!("users.attr_{i}"), i as i64);
+        sb.where_([cond]);
+    }
+    sb.order_by(["users.id"]);
+    sb.limit(50);
+    sb
+}
+
+fn bench_clone_builder(c: &mut Criterion) {
+    let base = base_query();
+
+    c.bench_function("select_clone_builder", |b| {
+        b.iter(|| black_box(base.clone_builder()));
+    });
+
+    c.bench_function("select_clone_add_filter_build", |b| {
+        b.iter(|| {
+            let mut sb = base.clone_builder();
+            let cond = sb.greater_than("users.score", 100_i64);
+            sb.where_([cond]);
+            black_box(sb.build_with_flavor(Flavor::PostgreSQL, &[]));
+        });
+    });
+}
+
+fn bench_build_into(c: &mut Criterion) {
+    let base = base_query();
+
+    c.bench_function("select_build_with_flavor", |b| {
+        b.iter(|| black_box(base.build_with_flavor(Flavor::PostgreSQL, &[])));
+    });
+
+    c.bench_function("select_build_into_reused_buffers", |b| {
+        let mut sql = String::new();
+        let mut args = Vec::new();
+        b.iter(|| {
+            sql.clear();
+            args.clear();
+            base.build_into(Flavor::PostgreSQL, &mut sql, &mut args)
+                .unwrap();
+            black_box((&sql, &args));
+        });
+    });
+}
+
+fn bench_prepared_bind(c: &mut Criterion) {
+    let tpl = PreparedTemplate::new(&base_query(), Flavor::PostgreSQL).unwrap();
+    let values: Vec<i64> = (0..tpl.arity() as i64).collect();
+
+    c.bench_function("prepared_template_bind", |b| {
+        b.iter(|| black_box(tpl.bind(values.iter().copied()).unwrap()));
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_select_build,
+    bench_clone_builder,
+    bench_build_into,
+    bench_prepared_bind
+);
 criterion_main!(benches);
@@ -0,0 +1,303 @@
+//! AlterTableBuilder: build ALTER TABLE statements.
+
+use crate::args::{Args, BuildError, unwrap_or_panic};
+use crate::column_def::{ColumnDef, ColumnType, TableConstraint};
+use crate::flavor::Flavor;
+use crate::injection::{Injection, InjectionMarker};
+use crate::macros::{IntoStrings, collect_into_strings};
+use crate::modifiers::{Arg, Builder, escape, escape_all};
+use crate::shared::Shared;
+use crate::string_builder::StringBuilder;
+
+const AT_MARKER_INIT: InjectionMarker = 0;
+const AT_MARKER_AFTER_ALTER: InjectionMarker = 1;
+const AT_MARKER_AFTER_ACTIONS: InjectionMarker = 2;
+
+#[derive(Debug, Clone)]
+enum AlterAction {
+    AddColumn(ColumnDef),
+    DropColumn(String),
+    RenameColumn(String, String),
+    AlterType(String, ColumnType),
+    SetNotNull(String),
+    DropNotNull(String),
+    SetDefault(String, String),
+    DropDefault(String),
+    AddConstraint(TableConstraint),
+    DropConstraint(String),
+    AddIndex(String, Vec<String>),
+    DropIndex(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct AlterTableBuilder {
+    table: Option<String>,
+    actions: Vec<AlterAction>,
+
+    args: Shared<Args>,
+    injection: Injection,
+    marker: InjectionMarker,
+}
+
+impl Default for AlterTableBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AlterTableBuilder {
+    pub fn new() -> Self {
+        Self {
+            table: None,
+            actions: Vec::new(),
+            args: Shared::new(Args::default()),
+            injection: Injection::new(),
+            marker: AT_MARKER_INIT,
+        }
+    }
+
+    pub fn set_flavor(&mut self, flavor: Flavor) -> Flavor {
+        let mut a = self.args.borrow_mut();
+        let old = a.flavor;
+        a.flavor = flavor;
+        old
+    }
+
+    pub fn flavor(&self) -> Flavor {
+        self.args.borrow().flavor
+    }
+
+    pub fn alter_table(&mut self, table: &str) -> &mut Self {
+        self.table = Some(escape(table));
+        self.marker = AT_MARKER_AFTER_ALTER;
+        self
+    }
+
+    fn action(&mut self, action: AlterAction) -> &mut Self {
+        self.actions.push(action);
+        self.marker = AT_MARKER_AFTER_ACTIONS;
+        self
+    }
+
+    pub fn add_column(&mut self, col: ColumnDef) -> &mut Self {
+        self.action(AlterAction::AddColumn(col))
+    }
+
+    pub fn drop_column(&mut self, col: &str) -> &mut Self {
+        self.action(AlterAction::DropColumn(escape(col)))
+    }
+
+    pub fn rename_column(&mut self, from: &str, to: &str) -> &mut Self {
+        self.action(AlterAction::RenameColumn(escape(from), escape(to)))
+    }
+
+    /// Change a column's type. Note that MySQL's `MODIFY COLUMN` also resets nullability and
+    /// default.
+    pub fn alter_column_type(&mut self, col: &str, ty: ColumnType) -> &mut Self {
+        self.action(AlterAction::AlterType(escape(col), ty))
+    }
+
+    pub fn set_not_null(&mut self, col: &str) -> &mut Self {
+        self.action(AlterAction::SetNotNull(escape(col)))
+    }
+
+    pub fn drop_not_null(&mut self, col: &str) -> &mut Self {
+        self.action(AlterAction::DropNotNull(escape(col)))
+    }
+
+    /// Set a column default; `expr` is a SQL expression.
+    pub fn set_default(&mut self, col: &str, expr: impl Into<String>) -> &mut Self {
+        self.action(AlterAction::SetDefault(escape(col), expr.into()))
+    }
+
+    pub fn drop_default(&mut self, col: &str) -> &mut Self {
+        self.action(AlterAction::DropDefault(escape(col)))
+    }
+
+    pub fn add_constraint(&mut self, constraint: TableConstraint) -> &mut Self {
+        self.action(AlterAction::AddConstraint(constraint))
+    }
+
+    pub fn drop_constraint(&mut self, name: &str) -> &mut Self {
+        self.action(AlterAction::DropConstraint(escape(name)))
+    }
+
+    /// `ADD INDEX name (cols)` (MySQL, Doris).
+    pub fn add_index<T>(&mut self, name: &str, cols: T) -> &mut Self
+    where
+        T: IntoStrings,
+    {
+        self.action(AlterAction::AddIndex(
+            escape(name),
+            escape_all(collect_into_strings(cols)),
+        ))
+    }
+
+    /// `DROP INDEX name` (MySQL, Doris, ClickHouse).
+    pub fn drop_index(&mut self, name: &str) -> &mut Self {
+        self.action(AlterAction::DropIndex(escape(name)))
+    }
+
+    pub fn sql(&mut self, sql: impl Into<String>) -> &mut Self {
+        self.injection.sql(self.marker, sql);
+        self
+    }
+
+    pub fn num_action(&self) -> usize {
+        self.actions.len()
+    }
+}
+
+impl AlterTableBuilder {
+    fn build_format(&self, flavor: Flavor) -> Result<String, BuildError> {
+        let mut buf = StringBuilder::new();
+        write_injection(&mut buf, &self.injection, AT_MARKER_INIT);
+
+        if let Some(t) = &self.table {
+            buf.write_leading("ALTER TABLE ");
+            buf.write_str(t);
+        }
+        write_injection(&mut buf, &self.injection, AT_MARKER_AFTER_ALTER);
+
+        if self.actions.len() > 1
+            && matches!(flavor, Flavor::SQLite | Flavor::SQLServer | Flavor::Oracle)
+        {
+            return Err(BuildError::Unsupported {
+                flavor,
+                feature: "multiple ALTER TABLE actions",
+            });
+        }
+
+        let actions = self
+            .actions
+            .iter()
+            .map(|action| render_action(action, flavor))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !actions.is_empty() {
+            buf.write_leading(&actions.join(", "));
+            write_injection(&mut buf, &self.injection, AT_MARKER_AFTER_ACTIONS);
+        }
+
+        Ok(buf.into_string())
+    }
+}
+
+fn render_action(action: &AlterAction, flavor: Flavor) -> Result<String, BuildError> {
+    use Flavor::*;
+    let unsupported = |feature| BuildError::Unsupported { flavor, feature };
+    let col_type = |ty: &ColumnType| escape(&flavor.dialect().column_type(ty));
+
+    let s = match action {
+        AlterAction::AddColumn(def) => {
+            let def = def.render(flavor)?;
+            match flavor {
+                SQLServer | CQL => format!("ADD {def}"),
+                Oracle | Informix => format!("ADD ({def})"),
+                _ => format!("ADD COLUMN {def}"),
+            }
+        }
+        AlterAction::DropColumn(col) => match flavor {
+            CQL => format!("DROP {col}"),
+            _ => format!("DROP COLUMN {col}"),
+        },
+        AlterAction::RenameColumn(from, to) => match flavor {
+            SQLServer | Informix => return Err(unsupported("RENAME COLUMN")),
+            CQL => format!("RENAME {from} TO {to}"),
+            Doris => format!("RENAME COLUMN {from} {to}"),
+            _ => format!("RENAME COLUMN {from} TO {to}"),
+        },
+        AlterAction::AlterType(col, ty) => match flavor {
+            PostgreSQL => format!("ALTER COLUMN {col} TYPE {}", col_type(ty)),
+            MySQL | Doris | ClickHouse => format!("MODIFY COLUMN {col} {}", col_type(ty)),
+            SQLServer => format!("ALTER COLUMN {col} {}", col_type(ty)),
+            Oracle | Informix => format!("MODIFY ({col} {})", col_type(ty)),
+            SQLite | CQL => return Err(unsupported("ALTER COLUMN TYPE")),
+            _ => format!("ALTER COLUMN {col} SET DATA TYPE {}", col_type(ty)),
+        },
+        AlterAction::SetNotNull(col) | AlterAction::DropNotNull(col) => {
+            let set = matches!(action, AlterAction::SetNotNull(_));
+            match flavor {
+                Oracle => format!("MODIFY ({col} {})", if set { "NOT NULL" } else { "NULL" }),
+                MySQL | Doris | SQLServer | SQLite | CQL | ClickHouse | Informix => {
+                    return Err(unsupported("ALTER COLUMN nullability"));
+                }
+                _ => format!(
+                    "ALTER COLUMN {col} {} NOT NULL",
+                    if set { "SET" } else { "DROP" }
+                ),
+            }
+        }
+        AlterAction::SetDefault(col, expr) => match flavor {
+            Oracle => format!("MODIFY ({col} DEFAULT {expr})"),
+            ClickHouse => format!("MODIFY COLUMN {col} DEFAULT {expr}"),
+            Doris | SQLServer | SQLite | CQL | Informix => {
+                return Err(unsupported("ALTER COLUMN DEFAULT"));
+            }
+            _ => format!("ALTER COLUMN {col} SET DEFAULT {expr}"),
+        },
+        AlterAction::DropDefault(col) => match flavor {
+            Oracle => format!("MODIFY ({col} DEFAULT NULL)"),
+            ClickHouse => format!("MODIFY COLUMN {col} REMOVE DEFAULT"),
+            Doris | SQLServer | SQLite | CQL | Informix => {
+                return Err(unsupported("ALTER COLUMN DEFAULT"));
+            }
+            _ => format!("ALTER COLUMN {col} DROP DEFAULT"),
+        },
+        AlterAction::AddConstraint(c) => match flavor {
+            SQLite | CQL => return Err(unsupported("ADD CONSTRAINT")),
+            _ => format!("ADD {}", c.render(flavor)?),
+        },
+        AlterAction::DropConstraint(name) => match flavor {
+            SQLite | CQL => return Err(unsupported("DROP CONSTRAINT")),
+            _ => format!("DROP CONSTRAINT {name}"),
+        },
+        AlterAction::AddIndex(name, cols) => match flavor {
+            MySQL | Doris => format!("ADD INDEX {name} ({})", cols.join(", ")),
+            _ => return Err(unsupported("ALTER TABLE ADD INDEX")),
+        },
+        AlterAction::DropIndex(name) => match flavor {
+            MySQL | Doris | ClickHouse => format!("DROP INDEX {name}"),
+            _ => return Err(unsupported("ALTER TABLE DROP INDEX")),
+        },
+    };
+    Ok(s)
+}
+
+impl Builder for AlterTableBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        let format = unwrap_or_panic(self.build_format(flavor));
+        self.args
+            .snapshot()
+            .compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        let format = self.build_format(flavor)?;
+        self.args
+            .snapshot()
+            .try_compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn flavor(&self) -> Flavor {
+        self.flavor()
+    }
+}
+
+pub fn alter_table(table: impl Into<String>) -> AlterTableBuilder {
+    let mut builder = AlterTableBuilder::new();
+    builder.alter_table(&table.into());
+    builder
+}
+
+fn write_injection(buf: &mut StringBuilder, inj: &Injection, marker: InjectionMarker) {
+    let sqls = inj.at(marker);
+    if sqls.is_empty() {
+        return;
+    }
+    buf.write_leading("");
+    buf.write_str(&sqls.join(" "));
+}
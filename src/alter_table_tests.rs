@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use crate::alter_table::{AlterTableBuilder, alter_table};
+    use crate::column_def::{ColumnDef, ColumnType, TableConstraint};
+    use crate::modifiers::Builder;
+    use crate::{BuildError, Flavor};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn alter_table_actions_per_flavor() {
+        let mut atb = alter_table("users");
+        atb.add_column(
+            ColumnDef::new("age", ColumnType::Int)
+                .not_null()
+                .default("0"),
+        )
+        .alter_column_type("name", ColumnType::Varchar(128))
+        .set_default("active", "TRUE")
+        .drop_column("legacy");
+
+        assert_eq!(
+            atb.build_with_flavor(Flavor::PostgreSQL, &[]).0,
+            "ALTER TABLE users ADD COLUMN age INTEGER NOT NULL DEFAULT 0, ALTER COLUMN name TYPE VARCHAR(128), ALTER COLUMN active SET DEFAULT TRUE, DROP COLUMN legacy"
+        );
+        assert_eq!(
+            atb.build_with_flavor(Flavor::MySQL, &[]).0,
+            "ALTER TABLE users ADD COLUMN age INT NOT NULL DEFAULT 0, MODIFY COLUMN name VARCHAR(128), ALTER COLUMN active SET DEFAULT TRUE, DROP COLUMN legacy"
+        );
+        assert_eq!(
+            atb.try_build_with_flavor(Flavor::SQLite, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::SQLite,
+                feature: "multiple ALTER TABLE actions",
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ALTER COLUMN nullability is not supported by MySQL")]
+    fn alter_table_unsupported_action_panics_on_build() {
+        let mut atb = alter_table("users");
+        atb.set_not_null("email");
+        atb.build_with_flavor(Flavor::MySQL, &[]);
+    }
+
+    #[test]
+    fn alter_table_single_action_flavors() {
+        let mut atb = AlterTableBuilder::new();
+        atb.alter_table("users").rename_column("name", "full_name");
+        assert_eq!(
+            atb.build_with_flavor(Flavor::SQLite, &[]).0,
+            "ALTER TABLE users RENAME COLUMN name TO full_name"
+        );
+        assert_eq!(
+            atb.try_build_with_flavor(Flavor::SQLServer, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::SQLServer,
+                feature: "RENAME COLUMN",
+            })
+        );
+
+        let mut atb = alter_table("users");
+        atb.set_not_null("email");
+        assert_eq!(
+            atb.build_with_flavor(Flavor::Oracle, &[]).0,
+            "ALTER TABLE users MODIFY (email NOT NULL)"
+        );
+        assert_eq!(
+            atb.build_with_flavor(Flavor::PostgreSQL, &[]).0,
+            "ALTER TABLE users ALTER COLUMN email SET NOT NULL"
+        );
+        assert_eq!(
+            atb.try_build_with_flavor(Flavor::MySQL, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::MySQL,
+                feature: "ALTER COLUMN nullability",
+            })
+        );
+
+        let mut atb = alter_table("users");
+        atb.add_column(ColumnDef::new("bio", ColumnType::Text));
+        assert_eq!(
+            atb.build_with_flavor(Flavor::SQLServer, &[]).0,
+            "ALTER TABLE users ADD bio NVARCHAR(MAX)"
+        );
+        assert_eq!(
+            atb.build_with_flavor(Flavor::Oracle, &[]).0,
+            "ALTER TABLE users ADD (bio CLOB)"
+        );
+    }
+
+    #[test]
+    fn alter_table_constraints_and_indexes() {
+        let mut atb = alter_table("orders");
+        atb.sql("/* migrate */")
+            .add_constraint(
+                TableConstraint::foreign_key(["user_id"], "users", ["id"]).name("fk_user"),
+            )
+            .drop_constraint("chk_total")
+            .add_index("idx_created", ["created_at"])
+            .drop_index("idx_old");
+        assert_eq!(atb.num_action(), 4);
+        assert_eq!(
+            atb.build_with_flavor(Flavor::MySQL, &[]).0,
+            "ALTER TABLE orders /* migrate */ ADD CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id), DROP CONSTRAINT chk_total, ADD INDEX idx_created (created_at), DROP INDEX idx_old"
+        );
+        assert_eq!(
+            atb.try_build_with_flavor(Flavor::PostgreSQL, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::PostgreSQL,
+                feature: "ALTER TABLE ADD INDEX",
+            })
+        );
+    }
+}
@@ -3,25 +3,61 @@
 use crate::flavor::Flavor;
 use crate::flavor::default_flavor;
 use crate::modifiers::{Arg, Raw, SqlNamedArg};
-use crate::string_builder::StringBuilder;
+use crate::shared::CowArc;
+use crate::valuer::ValuerError;
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::ops::Range;
 
 #[derive(Debug, thiserror::Error, PartialEq, Eq)]
 pub enum CompileError {
     #[error("builder invalid arg reference ${0}")]
     InvalidArgRef(isize),
+    #[error("builder unterminated named arg ${{{0}")]
+    UnterminatedNamedArg(String),
+    #[error("builder unknown named arg ${{{0}}}")]
+    UnknownNamedArg(String),
 }
 
-/// Args store SQL-related arguments and index mappings.
+/// BuildError: returned by `try_build` / `try_compile` instead of emitting broken SQL.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum BuildError {
+    #[error(transparent)]
+    Compile(#[from] CompileError),
+    #[error(transparent)]
+    Valuer(#[from] ValuerError),
+    #[error("{feature} is not supported by {flavor}")]
+    Unsupported {
+        flavor: Flavor,
+        feature: &'static str,
+    },
+    #[error(
+        "{statement} without WHERE would affect every row; call allow_full_table() to permit it"
+    )]
+    MissingWhere { statement: &'static str },
+    #[error("asc()/desc() applies to the last ORDER BY term, which already has its own direction")]
+    MixedOrderBy,
+}
+
+/// UnwrapOrPanic: the lenient-build policy for everything but malformed args.
+///
+/// `build()` renders bad `$n` / `${name}` references as `/* INVALID ARG */` markers, but a
+/// feature the flavor cannot express or a missing WHERE has no stand-in that keeps the statement's
+/// meaning, so `build()` panics with the error and `try_build` returns it.
+pub(crate) fn unwrap_or_panic<T>(result: Result<T, BuildError>) -> T {
+    result.unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Args store SQL-related arguments and index mappings; clones share storage until mutated.
 #[derive(Debug, Clone)]
 pub struct Args {
     /// Default flavor used by `compile`.
     pub flavor: Flavor,
 
     pub(crate) index_base: usize,
-    pub(crate) arg_values: Vec<Arg>,
-    pub(crate) named_args: HashMap<String, usize>,
-    pub(crate) sql_named_args: HashMap<String, usize>,
+    pub(crate) arg_values: CowArc<Vec<Arg>>,
+    pub(crate) named_args: CowArc<HashMap<String, usize>>,
+    pub(crate) sql_named_args: CowArc<HashMap<String, usize>>,
     pub(crate) only_named: bool,
 }
 
@@ -31,9 +67,9 @@ impl Default for Args {
         Self {
             flavor: default_flavor(),
             index_base: 0,
-            arg_values: Vec::new(),
-            named_args: HashMap::new(),
-            sql_named_args: HashMap::new(),
+            arg_values: CowArc::default(),
+            named_args: CowArc::default(),
+            sql_named_args: CowArc::default(),
             only_named: false,
         }
     }
@@ -125,117 +161,192 @@ impl Args {
         flavor: Flavor,
         initial_value: &[Arg],
     ) -> (String, Vec<Arg>) {
+        self.compile_internal(format, flavor, initial_value, false)
+            .expect("lenient compile never fails")
+    }
+
+    /// TryCompile: like `compile`, but return an error for malformed formats or args.
+    pub fn try_compile(
+        &self,
+        format: &str,
+        initial_value: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        self.try_compile_with_flavor(format, self.flavor, initial_value)
+    }
+
+    /// TryCompileWithFlavor: strict variant of `compile_with_flavor`.
+    ///
+    /// Out-of-range `$n`, unterminated or unknown `${name}` and failing `SqlValuer`s are reported
+    /// as `BuildError`; valuers are resolved eagerly so their values end up in the returned args.
+    pub fn try_compile_with_flavor(
+        &self,
+        format: &str,
+        flavor: Flavor,
+        initial_value: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        self.compile_internal(format, flavor, initial_value, true)
+    }
+
+    /// CompileInto: strict compile that appends to caller-owned buffers.
+    ///
+    /// `args` plays the role of `initial_value`: placeholders are numbered after the values it
+    /// already holds. Nested builders write straight into the same buffers, so no intermediate
+    /// `String` or `Vec<Arg>` is allocated per level. On error both buffers are truncated back to
+    /// their length on entry.
+    pub fn compile_into(
+        &self,
+        format: &str,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        self.compile_tokens(format, tokenize(format), flavor, sql, args, true)
+    }
+
+    /// CompileParsedInto: `compile_into` for a format parsed ahead of time with `ParsedFormat`.
+    pub fn compile_parsed_into(
+        &self,
+        parsed: &ParsedFormat,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        self.compile_parsed(parsed, flavor, sql, args, true)
+    }
+
+    pub(crate) fn compile_parsed(
+        &self,
+        parsed: &ParsedFormat,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+        strict: bool,
+    ) -> Result<(), BuildError> {
+        let tokens = parsed.tokens.iter().cloned();
+        self.compile_tokens(&parsed.format, tokens, flavor, sql, args, strict)
+    }
+
+    fn compile_internal(
+        &self,
+        format: &str,
+        flavor: Flavor,
+        initial_value: &[Arg],
+        strict: bool,
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        let mut sql = String::with_capacity(format.len());
+        let mut values = initial_value.to_vec();
+        self.compile_tokens(
+            format,
+            tokenize(format),
+            flavor,
+            &mut sql,
+            &mut values,
+            strict,
+        )?;
+        Ok((sql, values))
+    }
+
+    fn compile_tokens(
+        &self,
+        format: &str,
+        tokens: impl Iterator<Item = (Token, Range<usize>)>,
+        flavor: Flavor,
+        sql: &mut String,
+        values: &mut Vec<Arg>,
+        strict: bool,
+    ) -> Result<(), BuildError> {
+        rollback_on_err(sql, values, |sql, values| {
+            self.write_tokens(format, tokens, flavor, sql, values, strict)
+        })
+    }
+
+    fn write_tokens(
+        &self,
+        format: &str,
+        tokens: impl Iterator<Item = (Token, Range<usize>)>,
+        flavor: Flavor,
+        sql: &mut String,
+        values: &mut Vec<Arg>,
+        strict: bool,
+    ) -> Result<(), BuildError> {
         let mut offset = 0usize;
         let mut ctx = CompileContext {
-            buf: StringBuilder::new(),
+            buf: sql,
             flavor,
-            values: initial_value.to_vec(),
+            values,
             named_args: Vec::new(),
+            strict,
         };
 
-        let mut rest = format;
-        while let Some(pos) = rest.find('$') {
-            if pos > 0 {
-                ctx.buf.write_str(&rest[..pos]);
-            }
-            rest = &rest[pos + 1..];
-
-            if rest.is_empty() {
-                ctx.buf.write_char('$');
-                break;
-            }
-
-            let b0 = rest.as_bytes()[0];
-            match b0 {
-                b'$' => {
-                    ctx.buf.write_char('$');
-                    rest = &rest[1..];
+        for (token, range) in tokens {
+            let text = &format[range];
+            match token {
+                Token::Text => ctx.buf.push_str(text),
+                // With `only_named`, `$n` and `$?` are not placeholders and stay as written.
+                Token::Index(_) | Token::Successive if self.only_named => {
+                    ctx.buf.push('$');
+                    ctx.buf.push_str(text);
                 }
-                b'{' => {
-                    rest = self.compile_named(&mut ctx, rest);
+                Token::Index(Some(pointer)) => {
+                    offset =
+                        self.compile_successive(&mut ctx, pointer.saturating_sub(self.index_base))?;
                 }
-                b'0'..=b'9' if !self.only_named => {
-                    let (r, off) = self.compile_digits(&mut ctx, rest, offset);
-                    rest = r;
-                    offset = off;
+                // `$n` with more digits than fit in a usize; reported as `isize::MAX`.
+                Token::Index(None) => {
+                    if strict {
+                        return Err(CompileError::InvalidArgRef(isize::MAX).into());
+                    }
+                    ctx.buf.push_str("/* INVALID ARG $");
+                    ctx.buf.push_str(text);
+                    ctx.buf.push_str(" */");
                 }
-                b'?' if !self.only_named => {
-                    let (r, off) = self.compile_successive(&mut ctx, &rest[1..], offset);
-                    rest = r;
-                    offset = off;
+                Token::Successive => offset = self.compile_successive(&mut ctx, offset)?,
+                Token::Named => {
+                    if let Some(&p) = self.named_args.get(text) {
+                        let pos = ctx.values.len();
+                        self.compile_successive(&mut ctx, p - self.index_base)?;
+                        if ctx.values.len() == pos + 1 {
+                            record_slot_name(pos, text);
+                        }
+                    } else if strict {
+                        return Err(CompileError::UnknownNamedArg(text.to_string()).into());
+                    }
                 }
-                _ => {
-                    ctx.buf.write_char('$');
+                Token::Unterminated => {
+                    if strict {
+                        return Err(CompileError::UnterminatedNamedArg(text.to_string()).into());
+                    }
                 }
             }
         }
 
-        if !rest.is_empty() {
-            ctx.buf.write_str(rest);
-        }
-
-        let sql = ctx.buf.into_string();
-        let values = self.merge_sql_named_args(ctx.values, ctx.named_args);
-        (sql, values)
-    }
-
-    fn compile_named<'a>(&self, ctx: &mut CompileContext, format: &'a str) -> &'a str {
-        // format[0] == '{'
-        if let Some(end) = format.find('}') {
-            let name = &format[1..end];
-            let rest = &format[end + 1..];
-            if let Some(&p) = self.named_args.get(name) {
-                let (r, _off) = self.compile_successive(ctx, rest, p - self.index_base);
-                return r;
-            }
-            return rest;
-        }
-        // invalid
-        format
+        let named = std::mem::take(&mut ctx.named_args);
+        self.merge_sql_named_args(ctx.values, named);
+        Ok(())
     }
 
-    fn compile_digits<'a>(
+    fn compile_successive(
         &self,
         ctx: &mut CompileContext,
-        format: &'a str,
         offset: usize,
-    ) -> (&'a str, usize) {
-        let mut i = 0usize;
-        for b in format.as_bytes() {
-            if b.is_ascii_digit() {
-                i += 1;
-            } else {
-                break;
+    ) -> Result<usize, BuildError> {
+        let Some(arg) = self.arg_values.get(offset) else {
+            if ctx.strict {
+                let reference = (offset + self.index_base) as isize;
+                return Err(CompileError::InvalidArgRef(reference).into());
             }
-        }
-        let digits = &format[..i];
-        let rest = &format[i..];
-        if let Ok(pointer) = digits.parse::<usize>() {
-            return self.compile_successive(ctx, rest, pointer.saturating_sub(self.index_base));
-        }
-        (rest, offset)
-    }
-
-    fn compile_successive<'a>(
-        &self,
-        ctx: &mut CompileContext,
-        format: &'a str,
-        offset: usize,
-    ) -> (&'a str, usize) {
-        if offset >= self.arg_values.len() {
-            ctx.buf.write_str("/* INVALID ARG $");
-            ctx.buf.write_str(&offset.to_string());
-            ctx.buf.write_str(" */");
-            return (format, offset);
-        }
-        let arg = self.arg_values[offset].clone();
-        ctx.write_value(&arg);
-        (format, offset + 1)
+            ctx.buf.push_str("/* INVALID ARG $");
+            ctx.buf.push_str(&offset.to_string());
+            ctx.buf.push_str(" */");
+            return Ok(offset);
+        };
+        ctx.write_value(arg)?;
+        Ok(offset + 1)
     }
 
-    fn merge_sql_named_args(&self, mut values: Vec<Arg>, named: Vec<SqlNamedArg>) -> Vec<Arg> {
+    fn merge_sql_named_args(&self, values: &mut Vec<Arg>, named: Vec<SqlNamedArg>) {
         if self.sql_named_args.is_empty() && named.is_empty() {
-            return values;
+            return;
         }
 
         // Add named args encountered during parsing first, de-duplicated.
@@ -256,102 +367,233 @@ impl Args {
         for p in idxs {
             values.push(self.arg_values[p - self.index_base].clone());
         }
+    }
+}
+
+/// RollbackOnErr: run `f` on the buffers and truncate them back to their length on entry if it
+/// fails, so a failed `build_into` leaves the caller's buffers untouched.
+pub(crate) fn rollback_on_err(
+    sql: &mut String,
+    args: &mut Vec<Arg>,
+    f: impl FnOnce(&mut String, &mut Vec<Arg>) -> Result<(), BuildError>,
+) -> Result<(), BuildError> {
+    let (sql_len, args_len) = (sql.len(), args.len());
+    let r = f(sql, args);
+    if r.is_err() {
+        sql.truncate(sql_len);
+        args.truncate(args_len);
+    }
+    r
+}
+
+/// ParsedFormat: a `$`-format split into text and placeholder tokens once, so hot paths that
+/// compile the same format repeatedly skip the scan (see `Args::compile_parsed_into`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedFormat {
+    format: String,
+    tokens: Vec<(Token, Range<usize>)>,
+}
+
+impl ParsedFormat {
+    pub fn new(format: impl Into<String>) -> Self {
+        let format = format.into();
+        let tokens = tokenize(&format).collect();
+        Self { format, tokens }
+    }
+
+    /// The original format string.
+    pub fn as_str(&self) -> &str {
+        &self.format
+    }
+}
+
+/// One piece of a `$`-format; the paired range selects the text the token carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    /// Literal SQL, including the `$` of `$$` and of a `$` not followed by a placeholder.
+    Text,
+    /// `$n`; the range covers the digits, `None` when they overflow `usize`.
+    Index(Option<usize>),
+    /// `$?`; the range covers the `?`.
+    Successive,
+    /// `${name}`; the range covers `name`.
+    Named,
+    /// `${` without a closing brace; the range covers everything after `{`, which is then
+    /// scanned again as ordinary text.
+    Unterminated,
+}
+
+fn tokenize(format: &str) -> Tokens<'_> {
+    Tokens { format, pos: 0 }
+}
+
+struct Tokens<'a> {
+    format: &'a str,
+    pos: usize,
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = (Token, Range<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.pos;
+        let bytes = self.format.as_bytes();
+        if start >= bytes.len() {
+            return None;
+        }
+        if bytes[start] != b'$' {
+            let end = self.format[start..]
+                .find('$')
+                .map_or(bytes.len(), |i| start + i);
+            self.pos = end;
+            return Some((Token::Text, start..end));
+        }
+
+        let after = start + 1;
+        let item = match bytes.get(after) {
+            // A lone trailing `$` or `$$` both produce a single `$`.
+            None => (Token::Text, start..after),
+            Some(b'$') => {
+                self.pos = after + 1;
+                return Some((Token::Text, start..after));
+            }
+            Some(b'{') => match self.format[after..].find('}') {
+                Some(i) => {
+                    self.pos = after + i + 1;
+                    return Some((Token::Named, after + 1..after + i));
+                }
+                None => {
+                    self.pos = after;
+                    return Some((Token::Unterminated, after + 1..bytes.len()));
+                }
+            },
+            Some(b'0'..=b'9') => {
+                let end = bytes[after..]
+                    .iter()
+                    .position(|b| !b.is_ascii_digit())
+                    .map_or(bytes.len(), |i| after + i);
+                self.pos = end;
+                let digits = &self.format[after..end];
+                return Some((Token::Index(digits.parse().ok()), after..end));
+            }
+            Some(b'?') => {
+                self.pos = after + 1;
+                return Some((Token::Successive, after..after + 1));
+            }
+            Some(_) => (Token::Text, start..after),
+        };
+        self.pos = after;
+        Some(item)
+    }
+}
+
+thread_local! {
+    /// Arg positions filled by `${name}` while a `PreparedTemplate` is being built.
+    static SLOT_NAMES: RefCell<Option<Vec<(usize, String)>>> = const { RefCell::new(None) };
+}
+
+/// Run `f` and return the `(arg position, name)` pairs of every `${name}` that compiled to a
+/// single value; recording is off outside this scope.
+pub(crate) fn record_slot_names<R>(f: impl FnOnce() -> R) -> (R, Vec<(usize, String)>) {
+    let scope = SlotNamesScope(SLOT_NAMES.with(|s| s.replace(Some(Vec::new()))));
+    let r = f();
+    let names = SLOT_NAMES.with(|s| s.take()).unwrap_or_default();
+    drop(scope);
+    (r, names)
+}
+
+/// Restores the enclosing recording state on drop, so a panicking build does not leave
+/// recording switched on for the rest of the thread.
+struct SlotNamesScope(Option<Vec<(usize, String)>>);
 
-        values
+impl Drop for SlotNamesScope {
+    fn drop(&mut self) {
+        let outer = self.0.take();
+        SLOT_NAMES.with(|s| s.replace(outer));
     }
 }
 
+fn record_slot_name(pos: usize, name: &str) {
+    SLOT_NAMES.with(|s| {
+        if let Some(names) = s.borrow_mut().as_mut() {
+            names.push((pos, name.to_string()));
+        }
+    });
+}
+
 #[derive(Debug)]
-struct CompileContext {
-    buf: StringBuilder,
+struct CompileContext<'a> {
+    buf: &'a mut String,
     flavor: Flavor,
-    values: Vec<Arg>,
+    values: &'a mut Vec<Arg>,
     named_args: Vec<SqlNamedArg>,
+    strict: bool,
 }
 
-impl CompileContext {
-    fn write_value(&mut self, arg: &Arg) {
+impl CompileContext<'_> {
+    fn write_value(&mut self, arg: &Arg) -> Result<(), BuildError> {
         match arg {
             Arg::Builder(b) => {
-                let (sql, args) = b.build_with_flavor(self.flavor, &self.values);
-                self.buf.write_str(&sql);
+                if self.strict {
+                    b.build_into(self.flavor, self.buf, self.values)?;
+                } else {
+                    let (sql, args) = b.build_with_flavor(self.flavor, self.values);
+                    self.buf.push_str(&sql);
+                    *self.values = args;
+                }
 
-                let (values, named) = split_named_args(args);
-                self.values = values;
-                self.named_args.extend(named);
+                // Named args the nested builder appended are merged once by the outer compile.
+                let split = self
+                    .values
+                    .iter()
+                    .rposition(|a| !matches!(a, Arg::SqlNamed(_)))
+                    .map_or(0, |i| i + 1);
+                self.named_args
+                    .extend(self.values.drain(split..).map(|a| match a {
+                        Arg::SqlNamed(a) => a,
+                        _ => unreachable!("only SqlNamed args are drained"),
+                    }));
             }
             Arg::SqlNamed(SqlNamedArg { name, value }) => {
-                self.buf.write_char('@');
-                self.buf.write_str(name);
+                self.buf.push('@');
+                self.buf.push_str(name);
                 self.named_args.push(SqlNamedArg {
                     name: name.clone(),
                     value: value.clone(),
                 });
             }
-            Arg::Raw(Raw { expr }) => self.buf.write_str(expr),
+            Arg::Raw(Raw { expr }) => self.buf.push_str(expr),
             Arg::List { args, is_tuple } => {
                 if *is_tuple {
-                    self.buf.write_char('(');
+                    self.buf.push('(');
                 }
                 for (i, a) in args.iter().enumerate() {
                     if i > 0 {
-                        self.buf.write_str(", ");
+                        self.buf.push_str(", ");
                     }
-                    self.write_value(a);
+                    self.write_value(a)?;
                 }
                 if *is_tuple {
-                    self.buf.write_char(')');
+                    self.buf.push(')');
                 }
             }
             Arg::Named { .. } => {
                 // Named only takes effect when `${name}` is parsed; treat as a value here for predictability.
                 self.write_placeholder_and_push(arg.clone());
             }
+            Arg::Valuer(v) if self.strict => {
+                let value = v.value()?;
+                self.write_placeholder_and_push(Arg::Value(value));
+            }
             Arg::Valuer(_) => self.write_placeholder_and_push(arg.clone()),
             Arg::Value(_) => self.write_placeholder_and_push(arg.clone()),
         }
+        Ok(())
     }
 
     fn write_placeholder_and_push(&mut self, arg: Arg) {
-        match self.flavor {
-            Flavor::MySQL
-            | Flavor::SQLite
-            | Flavor::CQL
-            | Flavor::ClickHouse
-            | Flavor::Presto
-            | Flavor::Informix
-            | Flavor::Doris => {
-                self.buf.write_char('?');
-            }
-            Flavor::PostgreSQL => {
-                let idx = self.values.len() + 1;
-                self.buf.write_char('$');
-                self.buf.write_str(&idx.to_string());
-            }
-            Flavor::SQLServer => {
-                let idx = self.values.len() + 1;
-                self.buf.write_str(&format!("@p{idx}"));
-            }
-            Flavor::Oracle => {
-                let idx = self.values.len() + 1;
-                self.buf.write_char(':');
-                self.buf.write_str(&idx.to_string());
-            }
-        }
+        let idx = self.values.len() + 1;
+        self.flavor.dialect().write_placeholder(self.buf, idx);
         self.values.push(arg);
     }
 }
-
-fn split_named_args(mut values: Vec<Arg>) -> (Vec<Arg>, Vec<SqlNamedArg>) {
-    if values.is_empty() {
-        return (values, Vec::new());
-    }
-
-    let mut named = Vec::new();
-    while let Some(Arg::SqlNamed(a)) = values.last().cloned() {
-        values.pop();
-        named.push(a);
-    }
-    named.reverse();
-    (values, named)
-}
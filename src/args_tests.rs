@@ -1,9 +1,15 @@
 #[cfg(test)]
 mod tests {
-    use crate::args::Args;
+    use crate::args::{Args, BuildError, CompileError, ParsedFormat};
+    use crate::builder::build;
+    use crate::cond::Cond;
+    use crate::delete::DeleteBuilder;
     use crate::flavor::{Flavor, set_default_flavor_scoped};
-    use crate::modifiers::{Arg, SqlNamedArg, named, raw};
+    use crate::modifiers::{Arg, Builder, SqlNamedArg, named, raw};
+    use crate::select::SelectBuilder;
     use crate::value::SqlValue;
+    use crate::valuer::{SqlValuer, ValuerError};
+    use crate::where_clause::{WhereClause, WhereClauseBuilder};
     use pretty_assertions::assert_eq;
 
     fn to_postgresql(sql: &str) -> String {
@@ -174,4 +180,246 @@ mod tests {
             Some(&Arg::Value(SqlValue::I64(v1)))
         );
     }
+
+    #[derive(Debug, Clone)]
+    struct FixedValuer(Result<SqlValue, ValuerError>);
+
+    impl SqlValuer for FixedValuer {
+        fn value(&self) -> Result<SqlValue, ValuerError> {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn args_try_compile_reports_errors() {
+        let _g = set_default_flavor_scoped(Flavor::MySQL);
+        let mut a = Args::default();
+        a.add(123_i64);
+        a.add(named("name", "foo"));
+
+        assert_eq!(
+            a.try_compile("abc $0 ${name} def", &[]).map(|(sql, _)| sql),
+            Ok("abc ? ? def".to_string())
+        );
+        assert_eq!(
+            a.try_compile("abc $5 def", &[]),
+            Err(BuildError::Compile(CompileError::InvalidArgRef(5)))
+        );
+        assert_eq!(
+            a.try_compile("abc ${unknown} def", &[]),
+            Err(BuildError::Compile(CompileError::UnknownNamedArg(
+                "unknown".to_string()
+            )))
+        );
+        assert_eq!(
+            a.try_compile("abc ${name def", &[]),
+            Err(BuildError::Compile(CompileError::UnterminatedNamedArg(
+                "name def".to_string()
+            )))
+        );
+
+        assert_eq!(
+            a.try_compile("abc $99999999999999999999999 def", &[]),
+            Err(BuildError::Compile(CompileError::InvalidArgRef(isize::MAX)))
+        );
+
+        // The lenient variant keeps writing markers into the SQL.
+        let (sql, _) = a.compile("abc $5 def", &[]);
+        assert_eq!(sql, "abc /* INVALID ARG $5 */ def");
+        let (sql, _) = a.compile("abc $99999999999999999999999 def", &[]);
+        assert_eq!(sql, "abc /* INVALID ARG $99999999999999999999999 */ def");
+    }
+
+    #[test]
+    fn args_try_compile_resolves_valuers() {
+        let _g = set_default_flavor_scoped(Flavor::MySQL);
+        let mut a = Args::default();
+        let ok = a.add(Box::new(FixedValuer(Ok(SqlValue::I64(7)))) as Box<dyn SqlValuer>);
+        let (sql, args) = a.try_compile(&format!("a = {ok}"), &[]).unwrap();
+        assert_eq!(sql, "a = ?");
+        assert_eq!(args, vec![Arg::Value(SqlValue::I64(7))]);
+
+        let bad =
+            a.add(Box::new(FixedValuer(Err(ValuerError("boom".into())))) as Box<dyn SqlValuer>);
+        assert_eq!(
+            a.try_compile(&format!("a = {ok} AND b = {bad}"), &[]),
+            Err(BuildError::Valuer(ValuerError("boom".into())))
+        );
+    }
+
+    #[test]
+    fn try_build_propagates_errors_from_nested_builders() {
+        let _g = set_default_flavor_scoped(Flavor::PostgreSQL);
+        let mut inner = SelectBuilder::new();
+        inner.select(["id"]).from(["banned"]);
+        inner.where_(["reason = $9"]);
+
+        let mut sb = SelectBuilder::new();
+        sb.select(["*"]).from(["users"]);
+        sb.where_([
+            sb.in_("id", [Arg::Builder(Box::new(inner))]),
+            sb.equal("status", 1_i64),
+        ]);
+        assert_eq!(
+            sb.try_build(),
+            Err(BuildError::Compile(CompileError::InvalidArgRef(9)))
+        );
+
+        let mut ok_inner = SelectBuilder::new();
+        ok_inner.select(["id"]).from(["banned"]);
+        ok_inner.where_([ok_inner.gt("score", 3_i64)]);
+        let mut sb = SelectBuilder::new();
+        sb.select(["*"]).from(["users"]);
+        sb.where_([
+            sb.equal("status", 1_i64),
+            sb.in_("id", [Arg::Builder(Box::new(ok_inner))]),
+        ]);
+        let (sql, args) = sb.try_build().unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM users WHERE status = $1 AND id IN (SELECT id FROM banned WHERE score > $2)"
+        );
+        assert_eq!(args.len(), 2);
+    }
+
+    #[test]
+    fn parsed_format_compiles_like_format() {
+        let mut a = Args::default();
+        a.add(123_i64);
+        a.add(raw("raw"));
+        a.add(named("name", "foo"));
+        a.add(SqlNamedArg::new("at", 1_i64));
+
+        let formats = [
+            "abc $? $? $0 $? def",
+            "abc ${name} $$ $-1 $a $",
+            "abc $3 ${name} $1$0",
+            "abc $99999999999999999999999 def",
+            "abc $9 def",
+            "abc ${unknown} def",
+            "abc ${name def",
+        ];
+        for only_named in [false, true] {
+            a.only_named = only_named;
+            for format in formats {
+                let parsed = ParsedFormat::new(format);
+                assert_eq!(parsed.as_str(), format);
+
+                let mut sql = String::new();
+                let mut args = Vec::new();
+                let got = a
+                    .compile_parsed_into(&parsed, Flavor::PostgreSQL, &mut sql, &mut args)
+                    .map(|()| (sql, args));
+                assert_eq!(
+                    got,
+                    a.try_compile_with_flavor(format, Flavor::PostgreSQL, &[]),
+                    "{format}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn build_into_appends_to_caller_buffers() {
+        let mut inner = SelectBuilder::new();
+        inner.select(["id"]).from(["banned"]);
+        inner.where_([inner.gt("score", 3_i64)]);
+        let inner_named = build(
+            "SELECT id FROM vip WHERE since > $0",
+            [SqlNamedArg::new("since", 2020_i64)],
+        );
+
+        let mut sb = SelectBuilder::new();
+        sb.select(["*"]).from(["users"]);
+        sb.where_([
+            sb.equal("status", 1_i64),
+            sb.in_("id", [Arg::Builder(Box::new(inner))]),
+            sb.in_("id", [Arg::Builder(inner_named)]),
+        ]);
+
+        for flavor in [Flavor::MySQL, Flavor::PostgreSQL, Flavor::SQLServer] {
+            let prefix: Arg = "prefix".into();
+            let (expected_sql, expected_args) = sb
+                .try_build_with_flavor(flavor, std::slice::from_ref(&prefix))
+                .unwrap();
+
+            let mut sql = String::from("-- head\n");
+            let mut args = vec![prefix];
+            sb.build_into(flavor, &mut sql, &mut args).unwrap();
+            assert_eq!(sql, format!("-- head\n{expected_sql}"));
+            assert_eq!(args, expected_args);
+        }
+
+        let (sql, args) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT * FROM users WHERE status = $1 AND id IN (SELECT id FROM banned WHERE score > $2) AND id IN (SELECT id FROM vip WHERE since > @since)"
+        );
+        assert_eq!(
+            args,
+            vec![
+                1_i64.into(),
+                3_i64.into(),
+                Arg::SqlNamed(SqlNamedArg::new("since", 2020_i64)),
+            ]
+        );
+    }
+
+    #[test]
+    fn build_into_reuses_buffers_and_reports_errors() {
+        let mut sb = SelectBuilder::new();
+        sb.select(["id"]).from(["users"]);
+        sb.where_([sb.equal("id", 7_i64)]);
+
+        let mut sql = String::new();
+        let mut args = Vec::new();
+        for _ in 0..3 {
+            sql.clear();
+            args.clear();
+            sb.build_into(Flavor::MySQL, &mut sql, &mut args).unwrap();
+            assert_eq!(sql, "SELECT id FROM users WHERE id = ?");
+            assert_eq!(args, vec![7_i64.into()]);
+        }
+
+        let mut db = DeleteBuilder::new();
+        db.delete_from(["users"]);
+        assert_eq!(
+            db.build_into(Flavor::MySQL, &mut sql, &mut args),
+            Err(BuildError::MissingWhere {
+                statement: "DELETE"
+            })
+        );
+    }
+
+    #[test]
+    fn build_into_restores_buffers_on_nested_error() {
+        let bad = build("SELECT id FROM vip WHERE tier = ${tier}", Vec::<Arg>::new());
+        let mut sb = SelectBuilder::new();
+        sb.select(["*"]).from(["users"]);
+        sb.where_([sb.equal("status", 1_i64), sb.in_("id", [Arg::Builder(bad)])]);
+
+        let mut sql = String::from("-- head\n");
+        let mut args = vec![Arg::from("prefix")];
+        assert_eq!(
+            sb.build_into(Flavor::PostgreSQL, &mut sql, &mut args),
+            Err(CompileError::UnknownNamedArg("tier".to_string()).into())
+        );
+        assert_eq!(sql, "-- head\n");
+        assert_eq!(args, vec![Arg::from("prefix")]);
+
+        let (c1, c2) = (Cond::new(), Cond::new());
+        let wc = WhereClause::new();
+        {
+            let mut w = wc.borrow_mut();
+            w.add_where_expr(c1.clone().args, [c1.eq("id", 1_i64)]);
+            w.add_where_expr(c2.clone().args, ["tier = ${tier}"]);
+        }
+        let wb = WhereClauseBuilder::new(wc);
+        assert_eq!(
+            wb.build_into(Flavor::MySQL, &mut sql, &mut args),
+            Err(CompileError::UnknownNamedArg("tier".to_string()).into())
+        );
+        assert_eq!(sql, "-- head\n");
+        assert_eq!(args, vec![Arg::from("prefix")]);
+    }
 }
@@ -1,25 +1,61 @@
 //! Build / BuildNamed / Buildf: helpers for format-style SQL builders.
 
-use crate::args::Args;
+use crate::args::{Args, BuildError, ParsedFormat};
 use crate::flavor::Flavor;
 use crate::modifiers::{Arg, Builder, escape, named};
 
+/// CompiledBuilder: args plus a format parsed once at construction, so repeated builds skip
+/// the `$` scan.
 #[derive(Debug, Clone)]
 struct CompiledBuilder {
     args: Args,
-    format: String,
+    format: ParsedFormat,
 }
 
 impl CompiledBuilder {
     fn new(args: Args, format: String) -> Self {
-        Self { args, format }
+        Self {
+            args,
+            format: ParsedFormat::new(format),
+        }
+    }
+
+    fn compile(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+        strict: bool,
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        let mut sql = String::with_capacity(self.format.as_str().len());
+        let mut args = initial_arg.to_vec();
+        self.args
+            .compile_parsed(&self.format, flavor, &mut sql, &mut args, strict)?;
+        Ok((sql, args))
     }
 }
 
 impl Builder for CompiledBuilder {
     fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        self.compile(flavor, initial_arg, false)
+            .expect("lenient compile never fails")
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        self.compile(flavor, initial_arg, true)
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
         self.args
-            .compile_with_flavor(&self.format, flavor, initial_arg)
+            .compile_parsed_into(&self.format, flavor, sql, args)
     }
 
     fn flavor(&self) -> Flavor {
@@ -38,6 +74,27 @@ impl Builder for FlavoredBuilder {
         self.inner.build_with_flavor(flavor, initial_arg)
     }
 
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        self.inner.try_build_with_flavor(flavor, initial_arg)
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        self.inner.build_into(flavor, sql, args)
+    }
+
+    fn needs_subquery_in_compound(&self) -> bool {
+        self.inner.needs_subquery_in_compound()
+    }
+
     fn flavor(&self) -> Flavor {
         self.flavor
     }
@@ -0,0 +1,314 @@
+//! ColumnDef / TableConstraint: typed definitions for `CreateTableBuilder`, rendered per flavor.
+
+use crate::args::BuildError;
+use crate::flavor::Flavor;
+use crate::macros::{IntoStrings, collect_into_strings};
+use crate::modifiers::{escape, escape_all};
+use std::borrow::Cow;
+
+/// Logical column type; mapped to a concrete type by `SqlDialect::column_type`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ColumnType {
+    Int,
+    BigInt,
+    Text,
+    Varchar(u32),
+    Bool,
+    Timestamp,
+    Bytes,
+    Decimal(u8, u8),
+    Json,
+    Uuid,
+    /// Type text written verbatim.
+    Custom(Cow<'static, str>),
+}
+
+impl ColumnType {
+    pub fn custom(ty: impl Into<Cow<'static, str>>) -> Self {
+        ColumnType::Custom(ty.into())
+    }
+
+    /// ANSI spelling, used by dialects without a more specific mapping.
+    pub fn ansi_name(&self) -> String {
+        match self {
+            ColumnType::Int => "INTEGER".to_string(),
+            ColumnType::BigInt => "BIGINT".to_string(),
+            ColumnType::Text => "TEXT".to_string(),
+            ColumnType::Varchar(n) => format!("VARCHAR({n})"),
+            ColumnType::Bool => "BOOLEAN".to_string(),
+            ColumnType::Timestamp => "TIMESTAMP".to_string(),
+            ColumnType::Bytes => "BLOB".to_string(),
+            ColumnType::Decimal(p, s) => format!("DECIMAL({p}, {s})"),
+            ColumnType::Json => "JSON".to_string(),
+            ColumnType::Uuid => "UUID".to_string(),
+            ColumnType::Custom(t) => t.to_string(),
+        }
+    }
+}
+
+/// ColumnDef: one typed column of a CREATE TABLE.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnDef {
+    name: String,
+    ty: ColumnType,
+    nullable: Option<bool>,
+    default: Option<String>,
+    primary_key: bool,
+    unique: bool,
+    auto_increment: bool,
+    comment: Option<String>,
+}
+
+impl ColumnDef {
+    pub fn new(name: &str, ty: ColumnType) -> Self {
+        Self {
+            name: escape(name),
+            ty,
+            nullable: None,
+            default: None,
+            primary_key: false,
+            unique: false,
+            auto_increment: false,
+            comment: None,
+        }
+    }
+
+    pub fn not_null(mut self) -> Self {
+        self.nullable = Some(false);
+        self
+    }
+
+    /// Explicit `NULL`.
+    pub fn nullable(mut self) -> Self {
+        self.nullable = Some(true);
+        self
+    }
+
+    /// Default value as a SQL expression, e.g. `"0"`, `"'new'"` or `"CURRENT_TIMESTAMP"`.
+    pub fn default(mut self, expr: impl Into<String>) -> Self {
+        self.default = Some(expr.into());
+        self
+    }
+
+    pub fn primary_key(mut self) -> Self {
+        self.primary_key = true;
+        self
+    }
+
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+
+    /// `AUTO_INCREMENT` / `GENERATED BY DEFAULT AS IDENTITY` / `IDENTITY(1,1)` / `AUTOINCREMENT`.
+    pub fn auto_increment(mut self) -> Self {
+        self.auto_increment = true;
+        self
+    }
+
+    /// Inline column comment (MySQL, Doris, ClickHouse); ignored elsewhere.
+    pub fn comment(mut self, text: impl Into<String>) -> Self {
+        self.comment = Some(text.into());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn render(&self, flavor: Flavor) -> Result<String, BuildError> {
+        let dialect = flavor.dialect();
+        let unsupported = |feature| BuildError::Unsupported { flavor, feature };
+
+        let mut parts = vec![self.name.clone(), escape(&dialect.column_type(&self.ty))];
+
+        // SQLite only accepts AUTOINCREMENT right after PRIMARY KEY.
+        let trailing_auto_inc = flavor == Flavor::SQLite;
+        let auto_inc = if self.auto_increment {
+            let Some(kw) = dialect.auto_increment_keyword() else {
+                return Err(unsupported("AUTO_INCREMENT"));
+            };
+            if trailing_auto_inc && !self.primary_key {
+                return Err(unsupported("AUTOINCREMENT without PRIMARY KEY"));
+            }
+            Some(kw)
+        } else {
+            None
+        };
+
+        if let (Some(kw), false) = (auto_inc, trailing_auto_inc) {
+            parts.push(kw.to_string());
+        }
+        // CQL columns are always nullable and take no default.
+        if flavor == Flavor::CQL {
+            match (self.nullable, &self.default) {
+                (Some(false), _) => return Err(unsupported("NOT NULL")),
+                (Some(true), _) => return Err(unsupported("NULL")),
+                (None, Some(_)) => return Err(unsupported("DEFAULT")),
+                (None, None) => {}
+            }
+        }
+        match self.nullable {
+            Some(false) => parts.push("NOT NULL".to_string()),
+            Some(true) => parts.push("NULL".to_string()),
+            None => {}
+        }
+        if let Some(d) = &self.default {
+            parts.push(format!("DEFAULT {d}"));
+        }
+        if self.primary_key {
+            parts.push("PRIMARY KEY".to_string());
+        }
+        if let (Some(kw), true) = (auto_inc, trailing_auto_inc) {
+            parts.push(kw.to_string());
+        }
+        if self.unique {
+            if matches!(flavor, Flavor::CQL | Flavor::ClickHouse) {
+                return Err(unsupported("UNIQUE"));
+            }
+            parts.push("UNIQUE".to_string());
+        }
+        if let Some(c) = &self.comment
+            && matches!(flavor, Flavor::MySQL | Flavor::Doris | Flavor::ClickHouse)
+        {
+            parts.push(format!("COMMENT '{}'", escape(&c.replace('\'', "''"))));
+        }
+
+        Ok(parts.join(" "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ConstraintKind {
+    PrimaryKey(Vec<String>),
+    Unique(Vec<String>),
+    Check(String),
+    ForeignKey {
+        cols: Vec<String>,
+        ref_table: String,
+        ref_cols: Vec<String>,
+        on_delete: Option<String>,
+        on_update: Option<String>,
+    },
+}
+
+/// TableConstraint: table-level PRIMARY KEY / UNIQUE / CHECK / FOREIGN KEY.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableConstraint {
+    name: Option<String>,
+    kind: ConstraintKind,
+}
+
+impl TableConstraint {
+    fn new(kind: ConstraintKind) -> Self {
+        Self { name: None, kind }
+    }
+
+    pub fn primary_key<T>(cols: T) -> Self
+    where
+        T: IntoStrings,
+    {
+        Self::new(ConstraintKind::PrimaryKey(escape_all(
+            collect_into_strings(cols),
+        )))
+    }
+
+    pub fn unique<T>(cols: T) -> Self
+    where
+        T: IntoStrings,
+    {
+        Self::new(ConstraintKind::Unique(escape_all(collect_into_strings(
+            cols,
+        ))))
+    }
+
+    pub fn check(expr: impl Into<String>) -> Self {
+        Self::new(ConstraintKind::Check(expr.into()))
+    }
+
+    pub fn foreign_key<C, R>(cols: C, ref_table: &str, ref_cols: R) -> Self
+    where
+        C: IntoStrings,
+        R: IntoStrings,
+    {
+        Self::new(ConstraintKind::ForeignKey {
+            cols: escape_all(collect_into_strings(cols)),
+            ref_table: escape(ref_table),
+            ref_cols: escape_all(collect_into_strings(ref_cols)),
+            on_delete: None,
+            on_update: None,
+        })
+    }
+
+    /// Name the constraint: `CONSTRAINT name ...`.
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(escape(name));
+        self
+    }
+
+    /// Foreign key `ON DELETE` action, e.g. `"CASCADE"`.
+    pub fn on_delete(mut self, action: impl Into<String>) -> Self {
+        if let ConstraintKind::ForeignKey { on_delete, .. } = &mut self.kind {
+            *on_delete = Some(action.into());
+        }
+        self
+    }
+
+    /// Foreign key `ON UPDATE` action, e.g. `"CASCADE"`.
+    pub fn on_update(mut self, action: impl Into<String>) -> Self {
+        if let ConstraintKind::ForeignKey { on_update, .. } = &mut self.kind {
+            *on_update = Some(action.into());
+        }
+        self
+    }
+
+    pub(crate) fn render(&self, flavor: Flavor) -> Result<String, BuildError> {
+        let unsupported = |feature| BuildError::Unsupported { flavor, feature };
+
+        let body = match &self.kind {
+            ConstraintKind::PrimaryKey(cols) => format!("PRIMARY KEY ({})", cols.join(", ")),
+            ConstraintKind::Unique(cols) => {
+                if matches!(flavor, Flavor::CQL | Flavor::ClickHouse) {
+                    return Err(unsupported("UNIQUE"));
+                }
+                format!("UNIQUE ({})", cols.join(", "))
+            }
+            ConstraintKind::Check(expr) => {
+                if flavor == Flavor::CQL {
+                    return Err(unsupported("CHECK"));
+                }
+                format!("CHECK ({expr})")
+            }
+            ConstraintKind::ForeignKey {
+                cols,
+                ref_table,
+                ref_cols,
+                on_delete,
+                on_update,
+            } => {
+                if matches!(flavor, Flavor::CQL | Flavor::ClickHouse) {
+                    return Err(unsupported("FOREIGN KEY"));
+                }
+                let mut s = format!(
+                    "FOREIGN KEY ({}) REFERENCES {ref_table} ({})",
+                    cols.join(", "),
+                    ref_cols.join(", ")
+                );
+                if let Some(a) = on_delete {
+                    s.push_str(" ON DELETE ");
+                    s.push_str(a);
+                }
+                if let Some(a) = on_update {
+                    s.push_str(" ON UPDATE ");
+                    s.push_str(a);
+                }
+                s
+            }
+        };
+
+        Ok(match &self.name {
+            Some(name) => format!("CONSTRAINT {name} {body}"),
+            None => body,
+        })
+    }
+}
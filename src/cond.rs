@@ -1,16 +1,17 @@
 //! Cond: helpers to build WHERE clause expressions.
 
-use crate::args::Args;
+use crate::args::{Args, BuildError};
+use crate::expr::Expr;
 use crate::flavor::Flavor;
 use crate::macros::{IntoStrings, collect_into_strings};
-use crate::modifiers::{Arg, Builder};
+use crate::modifiers::{Arg, Builder, escape};
+use crate::shared::Shared;
 use crate::string_builder::{StringBuilder, filter_empty_strings};
-use std::cell::RefCell;
-use std::rc::Rc;
+use std::sync::Arc;
 
 const MIN_INDEX_BASE: usize = 256;
 
-pub type ArgsRef = Rc<RefCell<Args>>;
+pub type ArgsRef = Shared<Args>;
 
 /// Cond provides helper methods for conditional expressions.
 #[derive(Debug, Clone)]
@@ -26,7 +27,7 @@ impl Cond {
             ..Args::default()
         };
         Self {
-            args: Rc::new(RefCell::new(a)),
+            args: Shared::new(a),
         }
     }
 
@@ -39,7 +40,16 @@ impl Cond {
         self.args.borrow_mut().add(value)
     }
 
-    fn expr_builder(&self, f: impl Fn(Flavor, &[Arg]) -> (String, Vec<Arg>) + 'static) -> String {
+    /// Expr: embed an [`Expr`] fragment and return its placeholder; the fragment's args are
+    /// merged into the builder's args at build time.
+    pub fn expr(&self, e: Expr) -> String {
+        self.var(e)
+    }
+
+    pub(crate) fn expr_builder(
+        &self,
+        f: impl Fn(Flavor) -> (Args, String) + Send + Sync + 'static,
+    ) -> String {
         self.var(Arg::Builder(Box::new(CondDynBuilder::new(f))))
     }
 
@@ -49,14 +59,14 @@ impl Cond {
         }
         let field = field.to_string();
         let value: Arg = value.into();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let v = a.add(value.clone());
             let fmt = format!("{field} = {v}");
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
     pub fn e(&self, field: &str, value: impl Into<Arg>) -> String {
@@ -72,14 +82,14 @@ impl Cond {
         }
         let field = field.to_string();
         let value: Arg = value.into();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let v = a.add(value.clone());
             let fmt = format!("{field} <> {v}");
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
     pub fn ne(&self, field: &str, value: impl Into<Arg>) -> String {
@@ -95,14 +105,14 @@ impl Cond {
         }
         let field = field.to_string();
         let value: Arg = value.into();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let v = a.add(value.clone());
             let fmt = format!("{field} > {v}");
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
     pub fn g(&self, field: &str, value: impl Into<Arg>) -> String {
@@ -118,14 +128,14 @@ impl Cond {
         }
         let field = field.to_string();
         let value: Arg = value.into();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let v = a.add(value.clone());
             let fmt = format!("{field} >= {v}");
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
     pub fn ge(&self, field: &str, value: impl Into<Arg>) -> String {
@@ -141,14 +151,14 @@ impl Cond {
         }
         let field = field.to_string();
         let value: Arg = value.into();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let v = a.add(value.clone());
             let fmt = format!("{field} < {v}");
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
     pub fn l(&self, field: &str, value: impl Into<Arg>) -> String {
@@ -164,14 +174,14 @@ impl Cond {
         }
         let field = field.to_string();
         let value: Arg = value.into();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let v = a.add(value.clone());
             let fmt = format!("{field} <= {v}");
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
     pub fn le(&self, field: &str, value: impl Into<Arg>) -> String {
@@ -187,14 +197,14 @@ impl Cond {
         }
         let field = field.to_string();
         let value: Arg = value.into();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let v = a.add(value.clone());
             let fmt = format!("{field} LIKE {v}");
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
 
@@ -207,7 +217,7 @@ impl Cond {
         let value: Arg = value.into();
 
         // Choose ILIKE or LOWER(...) LIKE LOWER(...) based on flavor
-        let b = CondDynBuilder::new(move |flavor, initial| {
+        let b = CondDynBuilder::new(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
@@ -217,7 +227,7 @@ impl Cond {
                 Flavor::PostgreSQL | Flavor::SQLite => format!("{} ILIKE {v}", field),
                 _ => format!("LOWER({}) LIKE LOWER({v})", field),
             };
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         });
         self.var(Arg::Builder(Box::new(b)))
     }
@@ -228,14 +238,14 @@ impl Cond {
         }
         let field = field.to_string();
         let value: Arg = value.into();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let v = a.add(value.clone());
             let fmt = format!("{field} NOT LIKE {v}");
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
 
@@ -247,7 +257,7 @@ impl Cond {
         let field = field.to_string();
         let value: Arg = value.into();
 
-        let b = CondDynBuilder::new(move |flavor, initial| {
+        let b = CondDynBuilder::new(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
@@ -257,7 +267,7 @@ impl Cond {
                 Flavor::PostgreSQL | Flavor::SQLite => format!("{} NOT ILIKE {v}", field),
                 _ => format!("LOWER({}) NOT LIKE LOWER({v})", field),
             };
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         });
         self.var(Arg::Builder(Box::new(b)))
     }
@@ -266,18 +276,16 @@ impl Cond {
         if field.is_empty() {
             return String::new();
         }
-        let field = field.to_string();
-        self.expr_builder(move |_flavor, initial| (format!("{field} IS NULL"), initial.to_vec()))
+        let field = escape(field);
+        self.expr_builder(move |_flavor| (Args::default(), format!("{field} IS NULL")))
     }
 
     pub fn is_not_null(&self, field: &str) -> String {
         if field.is_empty() {
             return String::new();
         }
-        let field = field.to_string();
-        self.expr_builder(move |_flavor, initial| {
-            (format!("{field} IS NOT NULL"), initial.to_vec())
-        })
+        let field = escape(field);
+        self.expr_builder(move |_flavor| (Args::default(), format!("{field} IS NOT NULL")))
     }
 
     pub fn between(&self, field: &str, lower: impl Into<Arg>, upper: impl Into<Arg>) -> String {
@@ -287,7 +295,7 @@ impl Cond {
         let field = field.to_string();
         let lower: Arg = lower.into();
         let upper: Arg = upper.into();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
@@ -295,7 +303,7 @@ impl Cond {
             let l = a.add(lower.clone());
             let u = a.add(upper.clone());
             let fmt = format!("{field} BETWEEN {l} AND {u}");
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
 
@@ -306,7 +314,7 @@ impl Cond {
         let field = field.to_string();
         let lower: Arg = lower.into();
         let upper: Arg = upper.into();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
@@ -314,7 +322,7 @@ impl Cond {
             let l = a.add(lower.clone());
             let u = a.add(upper.clone());
             let fmt = format!("{field} NOT BETWEEN {l} AND {u}");
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
 
@@ -327,14 +335,14 @@ impl Cond {
             return "0 = 1".to_string();
         }
         let field = field.to_string();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let vals: Vec<String> = values.iter().cloned().map(|v| a.add(v)).collect();
             let fmt = format!("{field} IN ({})", vals.join(", "));
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
 
@@ -347,14 +355,14 @@ impl Cond {
             return "0 = 0".to_string();
         }
         let field = field.to_string();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let vals: Vec<String> = values.iter().cloned().map(|v| a.add(v)).collect();
             let fmt = format!("{field} NOT IN ({})", vals.join(", "));
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
 
@@ -398,27 +406,27 @@ impl Cond {
 
     pub fn exists(&self, subquery: impl Into<Arg>) -> String {
         let subquery: Arg = subquery.into();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let v = a.add(subquery.clone());
             let fmt = format!("EXISTS ({v})");
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
 
     pub fn not_exists(&self, subquery: impl Into<Arg>) -> String {
         let subquery: Arg = subquery.into();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let v = a.add(subquery.clone());
             let fmt = format!("NOT EXISTS ({v})");
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
 
@@ -437,14 +445,14 @@ impl Cond {
         }
         let field = field.to_string();
         let op = op.to_string();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let vals: Vec<String> = values.iter().cloned().map(|v| a.add(v)).collect();
             let fmt = format!("{field} {op} ANY ({})", vals.join(", "));
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
 
@@ -463,14 +471,14 @@ impl Cond {
         }
         let field = field.to_string();
         let op = op.to_string();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let vals: Vec<String> = values.iter().cloned().map(|v| a.add(v)).collect();
             let fmt = format!("{field} {op} ALL ({})", vals.join(", "));
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
 
@@ -489,14 +497,14 @@ impl Cond {
         }
         let field = field.to_string();
         let op = op.to_string();
-        self.expr_builder(move |flavor, initial| {
+        self.expr_builder(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
             };
             let vals: Vec<String> = values.iter().cloned().map(|v| a.add(v)).collect();
             let fmt = format!("{field} {op} SOME ({})", vals.join(", "));
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         })
     }
 
@@ -508,7 +516,7 @@ impl Cond {
         let field = field.to_string();
         let value: Arg = value.into();
 
-        let b = CondDynBuilder::new(move |flavor, initial| {
+        let b = CondDynBuilder::new(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
@@ -536,7 +544,7 @@ impl Cond {
                     )
                 }
             };
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         });
         self.var(Arg::Builder(Box::new(b)))
     }
@@ -549,7 +557,7 @@ impl Cond {
         let field = field.to_string();
         let value: Arg = value.into();
 
-        let b = CondDynBuilder::new(move |flavor, initial| {
+        let b = CondDynBuilder::new(move |flavor| {
             let mut a = Args {
                 flavor,
                 ..Args::default()
@@ -577,7 +585,7 @@ impl Cond {
                     )
                 }
             };
-            a.compile_with_flavor(&fmt, flavor, initial)
+            (a, fmt)
         });
         self.var(Arg::Builder(Box::new(b)))
     }
@@ -586,14 +594,15 @@ impl Cond {
 /// Internal helper for flavor-dependent conditional expressions.
 #[derive(Clone)]
 struct CondDynBuilder {
-    f: Rc<CondBuildFn>,
+    f: Arc<CondBuildFn>,
 }
 
-type CondBuildFn = dyn Fn(Flavor, &[Arg]) -> (String, Vec<Arg>);
+/// Produces the args and `$`-format of the expression for a flavor; compiled by the builder.
+type CondBuildFn = dyn Fn(Flavor) -> (Args, String) + Send + Sync;
 
 impl CondDynBuilder {
-    fn new(f: impl Fn(Flavor, &[Arg]) -> (String, Vec<Arg>) + 'static) -> Self {
-        Self { f: Rc::new(f) }
+    fn new(f: impl Fn(Flavor) -> (Args, String) + Send + Sync + 'static) -> Self {
+        Self { f: Arc::new(f) }
     }
 }
 
@@ -605,7 +614,27 @@ impl Default for Cond {
 
 impl Builder for CondDynBuilder {
     fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
-        (self.f)(flavor, initial_arg)
+        let (args, format) = (self.f)(flavor);
+        args.compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        let (args, format) = (self.f)(flavor);
+        args.try_compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        let (a, format) = (self.f)(flavor);
+        a.compile_into(&format, flavor, sql, args)
     }
 
     fn flavor(&self) -> Flavor {
@@ -3,11 +3,9 @@ mod tests {
     use crate::args::Args;
     use crate::cond::Cond;
     use crate::flavor::Flavor;
-    use crate::modifiers::Builder;
+    use crate::shared::Shared;
     use crate::{from_tables, select_cols, where_exprs};
     use pretty_assertions::assert_eq;
-    use std::cell::RefCell;
-    use std::rc::Rc;
 
     struct TestPair {
         expected: &'static str,
@@ -16,7 +14,7 @@ mod tests {
 
     fn new_test_pair(expected: &'static str, f: impl FnOnce(&Cond) -> String) -> TestPair {
         // Match Go cond_test.go newCond(): Args{} with index_base=0.
-        let args = Rc::new(RefCell::new(Args::default()));
+        let args = Shared::new(Args::default());
         let cond = Cond::with_args(args.clone());
         let fmt = f(&cond);
         let (sql, _) = args
@@ -86,7 +84,7 @@ mod tests {
 
     #[test]
     fn cond_or_and_empty_rules_like_go() {
-        let args = Rc::new(RefCell::new(Args::default()));
+        let args = Shared::new(Args::default());
         let cond = Cond::with_args(args);
         assert_eq!(cond.or([""]), "");
         assert_eq!(cond.or(["", "", ""]), "");
@@ -137,7 +135,7 @@ mod tests {
 
     #[test]
     fn cond_with_flavor_like_go() {
-        let args = Rc::new(RefCell::new(Args::default()));
+        let args = Shared::new(Args::default());
         let cond = Cond::with_args(args.clone());
         let fmt = [
             cond.ilike("f1", 1),
@@ -172,7 +170,7 @@ mod tests {
 
     #[test]
     fn cond_expr_like_go() {
-        let args = Rc::new(RefCell::new(Args::default()));
+        let args = Shared::new(Args::default());
         let cond = Cond::with_args(args.clone());
 
         let sb1 = crate::builder::build("SELECT 1 = 1", Vec::<crate::modifiers::Arg>::new());
@@ -146,12 +146,30 @@ impl std::fmt::Debug for ChainOptions {
     }
 }
 
-/// Join condition metadata.
+/// Join condition metadata; build it with `JoinCondition::new` since fields may be added.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct JoinCondition {
     pub option: Option<JoinOption>,
     pub table: String,
     pub on_expr: Vec<String>,
+    /// Extra ON conditions whose values are bound as args.
+    pub on_conditions: Vec<Condition>,
+}
+
+impl JoinCondition {
+    pub fn new(
+        option: Option<JoinOption>,
+        table: impl Into<String>,
+        on_expr: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            option,
+            table: table.into(),
+            on_expr: on_expr.into_iter().map(Into::into).collect(),
+            on_conditions: Vec::new(),
+        }
+    }
 }
 
 /// Single condition item.
@@ -444,10 +462,22 @@ impl Chain {
     }
 
     pub fn join(
+        self,
+        option: JoinOption,
+        table: impl Into<String>,
+        on_expr: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.join_with_conditions(option, table, on_expr, Vec::new())
+    }
+
+    /// Join whose ON clause also carries conditions with bound values, e.g.
+    /// `Chain::new().equal("o.status", "paid").build()`.
+    pub fn join_with_conditions(
         mut self,
         option: JoinOption,
         table: impl Into<String>,
         on_expr: impl IntoIterator<Item = impl Into<String>>,
+        on_conditions: impl IntoIterator<Item = Condition>,
     ) -> Self {
         self.conditions.push(Condition {
             skip: false,
@@ -462,9 +492,8 @@ impl Chain {
             value: ConditionValue::default(),
             value_fn: None,
             join: Some(JoinCondition {
-                option: Some(option),
-                table: table.into(),
-                on_expr: on_expr.into_iter().map(Into::into).collect(),
+                on_conditions: on_conditions.into_iter().collect(),
+                ..JoinCondition::new(Some(option), table, on_expr)
             }),
             where_clause: None,
         });
@@ -963,7 +992,14 @@ fn apply_select_condition(flavor: Flavor, builder: &mut SelectBuilder, condition
         }
         Operator::Join => {
             if let Some(join) = &condition.join {
-                builder.join_with_option(join.option, join.table.clone(), join.on_expr.clone());
+                let mut on = join.on_expr.clone();
+                for c in join.on_conditions.iter().filter(|c| !should_skip(c)) {
+                    let value = materialize_value(c);
+                    if let Some(expr) = build_expr(flavor, builder, &c.field, c.operator, &value) {
+                        on.push(expr);
+                    }
+                }
+                builder.join_with_option(join.option, join.table.clone(), on);
             }
         }
         _ => {}
@@ -1059,6 +1095,9 @@ pub fn build_update(
 }
 
 /// Build UPDATE with a specific flavor.
+///
+/// Panics when no condition applies and the builder has not opted in with
+/// `UpdateBuilder::allow_full_table`.
 pub fn build_update_with_flavor(
     flavor: Flavor,
     mut builder: UpdateBuilder,
@@ -1141,6 +1180,9 @@ pub fn build_delete(
 }
 
 /// Build DELETE with a specific flavor.
+///
+/// Panics when no condition applies and the builder has not opted in with
+/// `DeleteBuilder::allow_full_table`.
 pub fn build_delete_with_flavor(
     flavor: Flavor,
     mut builder: DeleteBuilder,
@@ -1248,6 +1290,26 @@ mod tests {
         assert_eq!(args, vec![Arg::from("value2")]);
     }
 
+    #[test]
+    fn chain_join_with_bound_conditions() {
+        let chain = Chain::new()
+            .join_with_conditions(
+                JoinOption::LeftJoin,
+                "orders o",
+                ["o.user_id = u.id"],
+                Chain::new().equal("o.status", "paid").build(),
+            )
+            .equal("u.active", true);
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["u.id", "o.total"]).from(vec!["users u"]);
+        let (sql, args) = build_select_with_flavor(Flavor::PostgreSQL, sb, chain.build());
+        assert_eq!(
+            "SELECT u.id, o.total FROM users u LEFT JOIN orders o ON o.user_id = u.id AND \"o\".\"status\" = $1 WHERE \"u\".\"active\" = $2",
+            sql
+        );
+        assert_eq!(args, vec![Arg::from("paid"), Arg::from(true)]);
+    }
+
     #[test]
     fn chain_equal_fluent_modifiers() {
         let chain = Chain::new()
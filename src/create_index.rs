@@ -0,0 +1,272 @@
+//! CreateIndexBuilder: build CREATE INDEX statements.
+
+use crate::args::{Args, BuildError, unwrap_or_panic};
+use crate::condition::quote_with_flavor;
+use crate::flavor::Flavor;
+use crate::injection::{Injection, InjectionMarker};
+use crate::macros::{IntoStrings, collect_into_strings};
+use crate::modifiers::{Arg, Builder, escape};
+use crate::shared::Shared;
+use crate::string_builder::StringBuilder;
+
+const CI_MARKER_INIT: InjectionMarker = 0;
+const CI_MARKER_AFTER_CREATE: InjectionMarker = 1;
+const CI_MARKER_AFTER_ON: InjectionMarker = 2;
+const CI_MARKER_AFTER_WHERE: InjectionMarker = 3;
+
+#[derive(Debug, Clone)]
+pub struct CreateIndexBuilder {
+    name: Option<String>,
+    unique: bool,
+    if_not_exists: bool,
+    table: Option<String>,
+    cols: Vec<String>,
+    method: Option<String>,
+    include: Vec<String>,
+    where_exprs: Vec<String>,
+
+    args: Shared<Args>,
+    injection: Injection,
+    marker: InjectionMarker,
+}
+
+impl Default for CreateIndexBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CreateIndexBuilder {
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            unique: false,
+            if_not_exists: false,
+            table: None,
+            cols: Vec::new(),
+            method: None,
+            include: Vec::new(),
+            where_exprs: Vec::new(),
+            args: Shared::new(Args::default()),
+            injection: Injection::new(),
+            marker: CI_MARKER_INIT,
+        }
+    }
+
+    pub fn set_flavor(&mut self, flavor: Flavor) -> Flavor {
+        let mut a = self.args.borrow_mut();
+        let old = a.flavor;
+        a.flavor = flavor;
+        old
+    }
+
+    pub fn flavor(&self) -> Flavor {
+        self.args.borrow().flavor
+    }
+
+    pub fn create_index(&mut self, name: &str) -> &mut Self {
+        self.name = Some(name.to_string());
+        self.marker = CI_MARKER_AFTER_CREATE;
+        self
+    }
+
+    pub fn unique(&mut self) -> &mut Self {
+        self.unique = true;
+        self
+    }
+
+    pub fn if_not_exists(&mut self) -> &mut Self {
+        self.if_not_exists = true;
+        self
+    }
+
+    /// Indexed table and columns; a column may carry a suffix such as `"created_at DESC"`.
+    pub fn on<T>(&mut self, table: &str, cols: T) -> &mut Self
+    where
+        T: IntoStrings,
+    {
+        self.table = Some(table.to_string());
+        self.cols = collect_into_strings(cols);
+        self.marker = CI_MARKER_AFTER_ON;
+        self
+    }
+
+    /// Index method, e.g. `btree`, `gin` (PostgreSQL) or `BTREE`, `HASH` (MySQL, Doris).
+    pub fn using(&mut self, method: &str) -> &mut Self {
+        self.method = Some(escape(method));
+        self
+    }
+
+    /// Covering columns: `INCLUDE (cols)` (PostgreSQL, SQL Server).
+    pub fn include<T>(&mut self, cols: T) -> &mut Self
+    where
+        T: IntoStrings,
+    {
+        self.include = collect_into_strings(cols);
+        self
+    }
+
+    /// Partial index predicate, joined with AND (PostgreSQL, SQLite, SQL Server).
+    pub fn where_<T>(&mut self, and_expr: T) -> &mut Self
+    where
+        T: IntoStrings,
+    {
+        self.where_exprs.extend(collect_into_strings(and_expr));
+        self.marker = CI_MARKER_AFTER_WHERE;
+        self
+    }
+
+    pub fn sql(&mut self, sql: impl Into<String>) -> &mut Self {
+        self.injection.sql(self.marker, sql);
+        self
+    }
+}
+
+impl CreateIndexBuilder {
+    fn build_format(&self, flavor: Flavor) -> Result<String, BuildError> {
+        use Flavor::*;
+        let mut buf = StringBuilder::new();
+        let unsupported = |feature| BuildError::Unsupported { flavor, feature };
+        write_injection(&mut buf, &self.injection, CI_MARKER_INIT);
+
+        // ClickHouse data-skipping indexes need `TYPE ... GRANULARITY ...`; Presto has no indexes.
+        if matches!(flavor, ClickHouse | Presto) {
+            return Err(unsupported("CREATE INDEX"));
+        }
+
+        buf.write_leading(if self.unique {
+            "CREATE UNIQUE INDEX"
+        } else {
+            "CREATE INDEX"
+        });
+        if self.if_not_exists {
+            if matches!(flavor, MySQL | SQLServer | Oracle | Informix) {
+                return Err(unsupported("CREATE INDEX IF NOT EXISTS"));
+            } else {
+                buf.write_str(" IF NOT EXISTS");
+            }
+        }
+        if let Some(name) = &self.name {
+            buf.write_leading(&quote_ident(flavor, name));
+        }
+        write_injection(&mut buf, &self.injection, CI_MARKER_AFTER_CREATE);
+
+        if let Some(t) = &self.table {
+            buf.write_leading("ON ");
+            buf.write_str(&quote_ident(flavor, t));
+
+            let method_before_cols = match (&self.method, flavor) {
+                (None, _) => false,
+                (Some(_), PostgreSQL) => true,
+                (Some(_), MySQL | Doris) => false,
+                (Some(_), _) => return Err(unsupported("CREATE INDEX ... USING")),
+            };
+            if let (Some(m), true) = (&self.method, method_before_cols) {
+                buf.write_str(" USING ");
+                buf.write_str(m);
+            }
+
+            let cols: Vec<String> = self.cols.iter().map(|c| quote_col(flavor, c)).collect();
+            buf.write_str(" (");
+            buf.write_str(&cols.join(", "));
+            buf.write_str(")");
+
+            if let (Some(m), MySQL | Doris) = (&self.method, flavor) {
+                buf.write_str(" USING ");
+                buf.write_str(m);
+            }
+            write_injection(&mut buf, &self.injection, CI_MARKER_AFTER_ON);
+        }
+
+        if !self.include.is_empty() {
+            if matches!(flavor, PostgreSQL | SQLServer) {
+                let cols: Vec<String> = self
+                    .include
+                    .iter()
+                    .map(|c| quote_ident(flavor, c))
+                    .collect();
+                buf.write_leading("INCLUDE (");
+                buf.write_str(&cols.join(", "));
+                buf.write_str(")");
+            } else {
+                return Err(unsupported("CREATE INDEX ... INCLUDE"));
+            }
+        }
+
+        if !self.where_exprs.is_empty() {
+            if matches!(flavor, PostgreSQL | SQLite | SQLServer) {
+                buf.write_leading("WHERE ");
+                buf.write_str(&self.where_exprs.join(" AND "));
+                write_injection(&mut buf, &self.injection, CI_MARKER_AFTER_WHERE);
+            } else {
+                return Err(unsupported("partial index"));
+            }
+        }
+
+        Ok(buf.into_string())
+    }
+}
+
+impl Builder for CreateIndexBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        let format = unwrap_or_panic(self.build_format(flavor));
+        self.args
+            .snapshot()
+            .compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        let format = self.build_format(flavor)?;
+        self.args
+            .snapshot()
+            .try_compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn flavor(&self) -> Flavor {
+        self.flavor()
+    }
+}
+
+pub fn create_index(name: impl Into<String>) -> CreateIndexBuilder {
+    let mut builder = CreateIndexBuilder::new();
+    builder.create_index(&name.into());
+    builder
+}
+
+/// Quote a (possibly dotted) identifier for `flavor` and escape it for the `$`-format.
+pub(crate) fn quote_ident(flavor: Flavor, ident: &str) -> String {
+    escape(&quote_with_flavor(flavor, ident))
+}
+
+/// Quote an index column that is a plain (possibly dotted) name followed by sort keywords,
+/// e.g. `created_at DESC`; expressions such as `lower(name) DESC` are kept as written.
+fn quote_col(flavor: Flavor, col: &str) -> String {
+    let (name, rest) = col.split_once(' ').unwrap_or((col, ""));
+    let plain_name = name.split('.').all(|p| {
+        p.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && p.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    });
+    let sort_keywords = rest.split_whitespace().all(|w| {
+        ["ASC", "DESC", "NULLS", "FIRST", "LAST"]
+            .iter()
+            .any(|k| w.eq_ignore_ascii_case(k))
+    });
+    match (plain_name && sort_keywords, rest) {
+        (false, _) => escape(col),
+        (true, "") => quote_ident(flavor, name),
+        (true, rest) => format!("{} {}", quote_ident(flavor, name), escape(rest)),
+    }
+}
+
+fn write_injection(buf: &mut StringBuilder, inj: &Injection, marker: InjectionMarker) {
+    let sqls = inj.at(marker);
+    if sqls.is_empty() {
+        return;
+    }
+    buf.write_leading("");
+    buf.write_str(&sqls.join(" "));
+}
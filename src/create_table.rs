@@ -1,28 +1,36 @@
 //! CreateTableBuilder: build CREATE TABLE statements.
 
-use crate::args::Args;
+use crate::args::{Args, BuildError, unwrap_or_panic};
+use crate::column_def::{ColumnDef, TableConstraint};
 use crate::flavor::Flavor;
 use crate::injection::{Injection, InjectionMarker};
 use crate::macros::{IntoStrings, collect_into_strings};
 use crate::modifiers::{Arg, Builder, escape};
+use crate::shared::Shared;
 use crate::string_builder::StringBuilder;
-use std::cell::RefCell;
-use std::rc::Rc;
 
 const CT_MARKER_INIT: InjectionMarker = 0;
 const CT_MARKER_AFTER_CREATE: InjectionMarker = 1;
 const CT_MARKER_AFTER_DEFINE: InjectionMarker = 2;
 const CT_MARKER_AFTER_OPTION: InjectionMarker = 3;
 
+/// One entry of the definition list.
+#[derive(Debug, Clone)]
+enum Definition {
+    Raw(Vec<String>),
+    Column(ColumnDef),
+    Constraint(TableConstraint),
+}
+
 #[derive(Debug, Clone)]
 pub struct CreateTableBuilder {
     verb: &'static str,
     if_not_exists: bool,
     table: Option<String>,
-    defs: Vec<Vec<String>>,
+    defs: Vec<Definition>,
     options: Vec<Vec<String>>,
 
-    args: Rc<RefCell<Args>>,
+    args: Shared<Args>,
     injection: Injection,
     marker: InjectionMarker,
 }
@@ -41,7 +49,7 @@ impl CreateTableBuilder {
             table: None,
             defs: Vec::new(),
             options: Vec::new(),
-            args: Rc::new(RefCell::new(Args::default())),
+            args: Shared::new(Args::default()),
             injection: Injection::new(),
             marker: CT_MARKER_INIT,
         }
@@ -80,7 +88,21 @@ impl CreateTableBuilder {
     where
         T: IntoStrings,
     {
-        self.defs.push(collect_into_strings(def));
+        self.defs.push(Definition::Raw(collect_into_strings(def)));
+        self.marker = CT_MARKER_AFTER_DEFINE;
+        self
+    }
+
+    /// Add a typed column; its type and modifiers are rendered per flavor.
+    pub fn column(&mut self, col: ColumnDef) -> &mut Self {
+        self.defs.push(Definition::Column(col));
+        self.marker = CT_MARKER_AFTER_DEFINE;
+        self
+    }
+
+    /// Add a table-level constraint.
+    pub fn constraint(&mut self, constraint: TableConstraint) -> &mut Self {
+        self.defs.push(Definition::Constraint(constraint));
         self.marker = CT_MARKER_AFTER_DEFINE;
         self
     }
@@ -106,8 +128,8 @@ impl CreateTableBuilder {
     // CreateTableBuilder currently doesn't need placeholders; add later if needed.
 }
 
-impl Builder for CreateTableBuilder {
-    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+impl CreateTableBuilder {
+    fn build_format(&self, flavor: Flavor) -> Result<String, BuildError> {
         let mut buf = StringBuilder::new();
         write_injection(&mut buf, &self.injection, CT_MARKER_INIT);
 
@@ -121,7 +143,15 @@ impl Builder for CreateTableBuilder {
         write_injection(&mut buf, &self.injection, CT_MARKER_AFTER_CREATE);
 
         if !self.defs.is_empty() {
-            let defs: Vec<String> = self.defs.iter().map(|d| d.join(" ")).collect();
+            let defs = self
+                .defs
+                .iter()
+                .map(|def| match def {
+                    Definition::Raw(parts) => Ok(parts.join(" ")),
+                    Definition::Column(col) => col.render(flavor),
+                    Definition::Constraint(c) => c.render(flavor),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
             buf.write_leading("(");
             buf.write_str(&defs.join(", "));
             buf.write_str(")");
@@ -134,11 +164,27 @@ impl Builder for CreateTableBuilder {
             write_injection(&mut buf, &self.injection, CT_MARKER_AFTER_OPTION);
         }
 
-        // flavor currently only affects placeholders; CREATE TABLE typically doesn't produce placeholders
-        let _ = flavor;
+        Ok(buf.into_string())
+    }
+}
+
+impl Builder for CreateTableBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        let format = unwrap_or_panic(self.build_format(flavor));
+        self.args
+            .snapshot()
+            .compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        let format = self.build_format(flavor)?;
         self.args
-            .borrow()
-            .compile_with_flavor(&buf.into_string(), self.flavor(), initial_arg)
+            .snapshot()
+            .try_compile_with_flavor(&format, flavor, initial_arg)
     }
 
     fn flavor(&self) -> Flavor {
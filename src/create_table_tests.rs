@@ -1,7 +1,9 @@
 #[cfg(test)]
 mod tests {
+    use crate::column_def::{ColumnDef, ColumnType, TableConstraint};
     use crate::create_table::CreateTableBuilder;
     use crate::modifiers::Builder;
+    use crate::{BuildError, Flavor};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -67,4 +69,133 @@ mod tests {
         assert!(sql_orig.contains("id BIGINT(20)"));
         assert!(sql_clone.contains("created_at DATETIME"));
     }
+
+    #[test]
+    fn create_table_typed_columns_per_flavor() {
+        let cases = [
+            (
+                Flavor::MySQL,
+                "TRUE",
+                "CREATE TABLE users (id BIGINT AUTO_INCREMENT PRIMARY KEY, name VARCHAR(64) NOT NULL, avatar BLOB COMMENT 'user''s avatar', active BOOLEAN NOT NULL DEFAULT TRUE, CONSTRAINT uq_users_name UNIQUE (name))",
+            ),
+            (
+                Flavor::PostgreSQL,
+                "TRUE",
+                "CREATE TABLE users (id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY, name VARCHAR(64) NOT NULL, avatar BYTEA, active BOOLEAN NOT NULL DEFAULT TRUE, CONSTRAINT uq_users_name UNIQUE (name))",
+            ),
+            (
+                Flavor::SQLite,
+                "1",
+                "CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name VARCHAR(64) NOT NULL, avatar BLOB, active INTEGER NOT NULL DEFAULT 1, CONSTRAINT uq_users_name UNIQUE (name))",
+            ),
+            (
+                Flavor::SQLServer,
+                "1",
+                "CREATE TABLE users (id BIGINT IDENTITY(1,1) PRIMARY KEY, name NVARCHAR(64) NOT NULL, avatar VARBINARY(MAX), active BIT NOT NULL DEFAULT 1, CONSTRAINT uq_users_name UNIQUE (name))",
+            ),
+        ];
+        for (flavor, active_default, expected) in cases {
+            let mut ctb = CreateTableBuilder::new();
+            ctb.create_table("users")
+                .column(
+                    ColumnDef::new("id", ColumnType::BigInt)
+                        .primary_key()
+                        .auto_increment(),
+                )
+                .column(ColumnDef::new("name", ColumnType::Varchar(64)).not_null())
+                .column(ColumnDef::new("avatar", ColumnType::Bytes).comment("user's avatar"))
+                .column(
+                    ColumnDef::new("active", ColumnType::Bool)
+                        .not_null()
+                        .default(active_default),
+                )
+                .constraint(TableConstraint::unique(["name"]).name("uq_users_name"));
+            assert_eq!(ctb.build_with_flavor(flavor, &[]).0, expected);
+        }
+    }
+
+    #[test]
+    fn create_table_typed_columns_unsupported() {
+        let mut ctb = CreateTableBuilder::new();
+        ctb.create_table("users").column(
+            ColumnDef::new("id", ColumnType::BigInt)
+                .primary_key()
+                .auto_increment(),
+        );
+        assert_eq!(
+            ctb.try_build_with_flavor(Flavor::ClickHouse, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::ClickHouse,
+                feature: "AUTO_INCREMENT",
+            })
+        );
+
+        let mut ctb = CreateTableBuilder::new();
+        ctb.create_table("users")
+            .column(ColumnDef::new("id", ColumnType::BigInt).primary_key())
+            .column(ColumnDef::new("name", ColumnType::Text));
+        assert_eq!(
+            ctb.build_with_flavor(Flavor::CQL, &[]).0,
+            "CREATE TABLE users (id bigint PRIMARY KEY, name text)"
+        );
+
+        let cases = [
+            (
+                ColumnDef::new("name", ColumnType::Text).not_null(),
+                "NOT NULL",
+            ),
+            (ColumnDef::new("name", ColumnType::Text).nullable(), "NULL"),
+            (
+                ColumnDef::new("name", ColumnType::Text).default("''"),
+                "DEFAULT",
+            ),
+        ];
+        for (column, feature) in cases {
+            let mut ctb = CreateTableBuilder::new();
+            ctb.create_table("users")
+                .column(ColumnDef::new("id", ColumnType::BigInt).primary_key())
+                .column(column);
+            assert_eq!(
+                ctb.try_build_with_flavor(Flavor::CQL, &[]),
+                Err(BuildError::Unsupported {
+                    flavor: Flavor::CQL,
+                    feature,
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn create_table_typed_constraints_mixed_with_raw() {
+        let mut ctb = CreateTableBuilder::new();
+        ctb.create_table("orders")
+            .define(["id", "BIGINT", "NOT NULL"])
+            .column(ColumnDef::new("user_id", ColumnType::BigInt).not_null())
+            .column(ColumnDef::new("total", ColumnType::Decimal(10, 2)))
+            .column(ColumnDef::new("meta", ColumnType::Json).nullable())
+            .column(ColumnDef::new("ref", ColumnType::Uuid))
+            .constraint(TableConstraint::primary_key(["id"]))
+            .constraint(TableConstraint::check("total >= 0"))
+            .constraint(
+                TableConstraint::foreign_key(["user_id"], "users", ["id"])
+                    .name("fk_orders_user")
+                    .on_delete("CASCADE"),
+            );
+        assert_eq!(ctb.num_define(), 8);
+        assert_eq!(
+            ctb.build_with_flavor(Flavor::Oracle, &[]).0,
+            "CREATE TABLE orders (id BIGINT NOT NULL, user_id NUMBER(19) NOT NULL, total NUMBER(10, 2), meta CLOB NULL, ref CHAR(36), PRIMARY KEY (id), CHECK (total >= 0), CONSTRAINT fk_orders_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE)"
+        );
+
+        let mut ctb = CreateTableBuilder::new();
+        ctb.create_table("t")
+            .column(ColumnDef::new("id", ColumnType::Int).auto_increment());
+        assert_eq!(
+            ctb.try_build_with_flavor(Flavor::SQLite, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::SQLite,
+                feature: "AUTOINCREMENT without PRIMARY KEY",
+            })
+        );
+    }
 }
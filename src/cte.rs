@@ -1,6 +1,6 @@
 //! CTEBuilder: build WITH / WITH RECURSIVE clauses.
 
-use crate::args::Args;
+use crate::args::{Args, BuildError};
 use crate::cte_query::CTEQueryBuilder;
 use crate::delete::DeleteBuilder;
 use crate::flavor::Flavor;
@@ -8,10 +8,9 @@ use crate::injection::{Injection, InjectionMarker};
 use crate::macros::IntoStrings;
 use crate::modifiers::{Arg, Builder};
 use crate::select::SelectBuilder;
+use crate::shared::{CowArc, Shared};
 use crate::string_builder::StringBuilder;
 use crate::update::UpdateBuilder;
-use std::cell::RefCell;
-use std::rc::Rc;
 
 const CTE_MARKER_INIT: InjectionMarker = 0;
 const CTE_MARKER_AFTER_WITH: InjectionMarker = 1;
@@ -33,10 +32,10 @@ pub fn with_recursive(queries: impl IntoIterator<Item = CTEQueryBuilder>) -> CTE
 #[derive(Debug)]
 pub struct CTEBuilder {
     recursive: bool,
-    queries: Vec<CTEQueryBuilder>,
-    query_vars: Vec<String>,
+    queries: CowArc<Vec<CTEQueryBuilder>>,
+    query_vars: CowArc<Vec<String>>,
 
-    args: Rc<RefCell<Args>>,
+    args: Shared<Args>,
     injection: Injection,
     marker: InjectionMarker,
 }
@@ -57,9 +56,9 @@ impl CTEBuilder {
     pub fn new() -> Self {
         Self {
             recursive: false,
-            queries: Vec::new(),
-            query_vars: Vec::new(),
-            args: Rc::new(RefCell::new(Args::default())),
+            queries: CowArc::default(),
+            query_vars: CowArc::default(),
+            args: Shared::new(Args::default()),
             injection: Injection::new(),
             marker: CTE_MARKER_INIT,
         }
@@ -77,25 +76,15 @@ impl CTEBuilder {
     }
 
     pub fn clone_builder(&self) -> Self {
-        let args = Rc::new(RefCell::new(self.args.borrow().clone()));
-        let cloned = Self {
+        // The query builders bound in args are never mutated, so sharing them is safe.
+        Self {
             recursive: self.recursive,
             queries: self.queries.clone(),
             query_vars: self.query_vars.clone(),
-            args,
+            args: Shared::new(self.args.snapshot()),
             injection: self.injection.clone(),
             marker: self.marker,
-        };
-
-        // Rebind query_vars builders to keep args consistent after deep clone
-        for (ph, q) in cloned.query_vars.iter().zip(cloned.queries.iter()) {
-            cloned
-                .args
-                .borrow_mut()
-                .replace(ph, Arg::Builder(Box::new(q.clone_builder())));
         }
-
-        cloned
     }
 
     fn var(&self, v: impl Into<Arg>) -> String {
@@ -174,8 +163,8 @@ impl CTEBuilder {
     }
 }
 
-impl Builder for CTEBuilder {
-    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+impl CTEBuilder {
+    fn build_format(&self) -> String {
         let mut buf = StringBuilder::new();
         write_injection(&mut buf, &self.injection, CTE_MARKER_INIT);
 
@@ -189,9 +178,36 @@ impl Builder for CTEBuilder {
         }
 
         write_injection(&mut buf, &self.injection, CTE_MARKER_AFTER_WITH);
+        buf.into_string()
+    }
+}
+
+impl Builder for CTEBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        self.args
+            .snapshot()
+            .compile_with_flavor(&self.build_format(), flavor, initial_arg)
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        self.args
+            .snapshot()
+            .try_compile_with_flavor(&self.build_format(), flavor, initial_arg)
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
         self.args
-            .borrow()
-            .compile_with_flavor(&buf.into_string(), flavor, initial_arg)
+            .snapshot()
+            .compile_into(&self.build_format(), flavor, sql, args)
     }
 
     fn flavor(&self) -> Flavor {
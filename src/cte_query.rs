@@ -1,13 +1,12 @@
 //! CTEQueryBuilder: build a single CTE entry.
 
-use crate::args::Args;
+use crate::args::{Args, BuildError};
 use crate::flavor::Flavor;
 use crate::injection::{Injection, InjectionMarker};
 use crate::macros::{IntoStrings, collect_into_strings};
 use crate::modifiers::{Arg, Builder};
+use crate::shared::Shared;
 use crate::string_builder::StringBuilder;
-use std::cell::RefCell;
-use std::rc::Rc;
 
 const CTE_QUERY_MARKER_INIT: InjectionMarker = 0;
 const CTE_QUERY_MARKER_AFTER_TABLE: InjectionMarker = 1;
@@ -22,7 +21,7 @@ pub struct CTEQueryBuilder {
     builder: Option<Box<dyn Builder>>,
     auto_add_to_table_list: bool,
 
-    args: Rc<RefCell<Args>>,
+    args: Shared<Args>,
     injection: Injection,
     marker: InjectionMarker,
 }
@@ -53,7 +52,7 @@ impl CTEQueryBuilder {
             builder_var: None,
             builder: None,
             auto_add_to_table_list: false,
-            args: Rc::new(RefCell::new(Args::default())),
+            args: Shared::new(Args::default()),
             injection: Injection::new(),
             marker: CTE_QUERY_MARKER_INIT,
         }
@@ -71,7 +70,7 @@ impl CTEQueryBuilder {
     }
 
     pub fn clone_builder(&self) -> Self {
-        let cloned = Self {
+        Self {
             name: self.name.clone(),
             cols: self.cols.clone(),
             builder_var: self.builder_var.clone(),
@@ -80,19 +79,10 @@ impl CTEQueryBuilder {
                 .as_ref()
                 .map(|b| dyn_clone::clone_box(b.as_ref())),
             auto_add_to_table_list: self.auto_add_to_table_list,
-            args: Rc::new(RefCell::new(self.args.borrow().clone())),
+            args: Shared::new(self.args.snapshot()),
             injection: self.injection.clone(),
             marker: self.marker,
-        };
-
-        if let (Some(ph), Some(b)) = (&self.builder_var, &self.builder) {
-            cloned
-                .args
-                .borrow_mut()
-                .replace(ph, Arg::Builder(dyn_clone::clone_box(b.as_ref())));
         }
-
-        cloned
     }
 
     fn var(&self, v: impl Into<Arg>) -> String {
@@ -137,8 +127,8 @@ impl CTEQueryBuilder {
     }
 }
 
-impl Builder for CTEQueryBuilder {
-    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+impl CTEQueryBuilder {
+    fn build_format(&self) -> String {
         let mut buf = StringBuilder::new();
         write_injection(&mut buf, &self.injection, CTE_QUERY_MARKER_INIT);
 
@@ -159,9 +149,36 @@ impl Builder for CTEQueryBuilder {
             write_injection(&mut buf, &self.injection, CTE_QUERY_MARKER_AFTER_AS);
         }
 
+        buf.into_string()
+    }
+}
+
+impl Builder for CTEQueryBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        self.args
+            .snapshot()
+            .compile_with_flavor(&self.build_format(), flavor, initial_arg)
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        self.args
+            .snapshot()
+            .try_compile_with_flavor(&self.build_format(), flavor, initial_arg)
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
         self.args
-            .borrow()
-            .compile_with_flavor(&buf.into_string(), flavor, initial_arg)
+            .snapshot()
+            .compile_into(&self.build_format(), flavor, sql, args)
     }
 
     fn flavor(&self) -> Flavor {
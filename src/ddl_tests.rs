@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod tests {
+    use crate::create_index::create_index;
+    use crate::drop_index::drop_index;
+    use crate::drop_table::{DropTableBuilder, drop_table};
+    use crate::modifiers::Builder;
+    use crate::truncate_table::truncate_table;
+    use crate::{BuildError, CreateIndexBuilder, Flavor};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn create_index_per_flavor() {
+        let mut cib = create_index("idx_users_email");
+        cib.unique()
+            .if_not_exists()
+            .on("app.users", ["email", "created_at DESC"])
+            .include(["name"])
+            .where_(["deleted_at IS NULL"]);
+
+        assert_eq!(
+            cib.build_with_flavor(Flavor::PostgreSQL, &[]).0,
+            r#"CREATE UNIQUE INDEX IF NOT EXISTS "idx_users_email" ON "app"."users" ("email", "created_at" DESC) INCLUDE ("name") WHERE deleted_at IS NULL"#
+        );
+        assert_eq!(
+            cib.try_build_with_flavor(Flavor::MySQL, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::MySQL,
+                feature: "CREATE INDEX IF NOT EXISTS",
+            })
+        );
+
+        let mut cib = create_index("idx_users_name");
+        cib.on("users", ["lower(name) DESC", "id NULLS LAST", "(a + b)"]);
+        assert_eq!(
+            cib.build_with_flavor(Flavor::PostgreSQL, &[]).0,
+            r#"CREATE INDEX "idx_users_name" ON "users" (lower(name) DESC, "id" NULLS LAST, (a + b))"#
+        );
+
+        let mut cib = CreateIndexBuilder::new();
+        cib.create_index("idx_tags")
+            .on("posts", ["tags"])
+            .using("gin")
+            .sql("/* concurrently? */");
+        assert_eq!(
+            cib.build_with_flavor(Flavor::PostgreSQL, &[]).0,
+            r#"CREATE INDEX "idx_tags" ON "posts" USING gin ("tags") /* concurrently? */"#
+        );
+        assert_eq!(
+            cib.build_with_flavor(Flavor::MySQL, &[]).0,
+            "CREATE INDEX `idx_tags` ON `posts` (`tags`) USING gin /* concurrently? */"
+        );
+        assert_eq!(
+            cib.try_build_with_flavor(Flavor::SQLite, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::SQLite,
+                feature: "CREATE INDEX ... USING",
+            })
+        );
+    }
+
+    #[test]
+    fn index_ddl_unsupported_flavors() {
+        let mut cib = create_index("idx");
+        cib.on("t", ["a"]);
+        for flavor in [Flavor::ClickHouse, Flavor::Presto] {
+            assert_eq!(
+                cib.try_build_with_flavor(flavor, &[]),
+                Err(BuildError::Unsupported {
+                    flavor,
+                    feature: "CREATE INDEX",
+                })
+            );
+        }
+
+        let mut dib = drop_index("idx");
+        dib.on("t");
+        assert_eq!(
+            dib.try_build_with_flavor(Flavor::Presto, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::Presto,
+                feature: "DROP INDEX",
+            })
+        );
+    }
+
+    #[test]
+    fn drop_index_per_flavor() {
+        let mut dib = drop_index("idx_users_email");
+        dib.on("users");
+        assert_eq!(
+            dib.build_with_flavor(Flavor::MySQL, &[]).0,
+            "DROP INDEX `idx_users_email` ON `users`"
+        );
+        assert_eq!(
+            dib.build_with_flavor(Flavor::PostgreSQL, &[]).0,
+            r#"DROP INDEX "idx_users_email""#
+        );
+        dib.if_exists();
+        assert_eq!(
+            dib.build_with_flavor(Flavor::ClickHouse, &[]).0,
+            "ALTER TABLE `users` DROP INDEX IF EXISTS `idx_users_email`"
+        );
+
+        let mut dib = drop_index("idx_users_email");
+        dib.if_exists();
+        assert_eq!(
+            dib.build_with_flavor(Flavor::SQLite, &[]).0,
+            r#"DROP INDEX IF EXISTS "idx_users_email""#
+        );
+        assert_eq!(
+            dib.try_build_with_flavor(Flavor::SQLServer, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::SQLServer,
+                feature: "DROP INDEX without ON table",
+            })
+        );
+    }
+
+    #[test]
+    fn drop_table_and_truncate() {
+        let mut dtb = drop_table("users");
+        dtb.if_exists().cascade();
+        assert_eq!(
+            dtb.build_with_flavor(Flavor::PostgreSQL, &[]).0,
+            r#"DROP TABLE IF EXISTS "users" CASCADE"#
+        );
+        assert_eq!(
+            dtb.try_build_with_flavor(Flavor::Oracle, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::Oracle,
+                feature: "DROP TABLE IF EXISTS",
+            })
+        );
+
+        let mut dtb = DropTableBuilder::new();
+        dtb.drop_table(["a", "b"]).cascade().sql("/* done */");
+        assert_eq!(
+            dtb.build_with_flavor(Flavor::PostgreSQL, &[]).0,
+            r#"DROP TABLE "a", "b" CASCADE /* done */"#
+        );
+        assert_eq!(
+            dtb.try_build_with_flavor(Flavor::Oracle, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::Oracle,
+                feature: "dropping several tables at once",
+            })
+        );
+
+        let ttb = truncate_table("logs");
+        assert_eq!(
+            ttb.build_with_flavor(Flavor::MySQL, &[]).0,
+            "TRUNCATE TABLE `logs`"
+        );
+        assert_eq!(
+            ttb.build_with_flavor(Flavor::SQLite, &[]).0,
+            r#"DELETE FROM "logs""#
+        );
+    }
+}
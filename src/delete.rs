@@ -1,17 +1,24 @@
 //! DeleteBuilder: build DELETE statements.
 
-use crate::args::Args;
+use crate::args::{Args, BuildError, unwrap_or_panic};
 use crate::cond::{ArgsRef, Cond};
 use crate::cte::CTEBuilder;
 use crate::flavor::Flavor;
 use crate::injection::{Injection, InjectionMarker};
 use crate::macros::{IntoStrings, collect_into_strings};
 use crate::modifiers::{Arg, Builder};
+use crate::order_by::{OrderByScope, OrderTerm, write_order_by};
+use crate::select::JoinOption;
+use crate::shared::{CowArc, Shared};
+use crate::sql_dialect::{DeleteJoinStyle, ReturningStyle};
 use crate::string_builder::StringBuilder;
-use crate::where_clause::{WhereClause, WhereClauseBuilder, WhereClauseRef};
-use std::cell::RefCell;
+use crate::update::{TableJoin, table_alias};
+use crate::where_clause::{
+    WhereClause, WhereClauseBuilder, WhereClauseRef, copy_where_clause, rebind_where_var,
+    where_with,
+};
 use std::ops::Deref;
-use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
 
 const DELETE_MARKER_INIT: InjectionMarker = 0;
 const DELETE_MARKER_AFTER_WITH: InjectionMarker = 1;
@@ -20,23 +27,28 @@ const DELETE_MARKER_AFTER_WHERE: InjectionMarker = 3;
 const DELETE_MARKER_AFTER_ORDER_BY: InjectionMarker = 4;
 const DELETE_MARKER_AFTER_LIMIT: InjectionMarker = 5;
 const DELETE_MARKER_AFTER_RETURNING: InjectionMarker = 6;
+const DELETE_MARKER_AFTER_JOIN: InjectionMarker = 7;
 
 #[derive(Debug)]
 pub struct DeleteBuilder {
     args: ArgsRef,
     cond: Cond,
 
-    tables: Vec<String>,
+    tables: CowArc<Vec<String>>,
 
     where_clause: Option<WhereClauseRef>,
     where_var: Option<String>,
+    // See `rebind_where_var`.
+    where_stale: AtomicBool,
     cte_var: Option<String>,
     cte: Option<CTEBuilder>,
 
-    order_by_cols: Vec<String>,
+    order_by_cols: CowArc<Vec<OrderTerm>>,
     order: Option<&'static str>,
     limit_var: Option<String>,
-    returning: Vec<String>,
+    returning: CowArc<Vec<String>>,
+    joins: CowArc<Vec<TableJoin>>,
+    allow_full_table: bool,
 
     injection: Injection,
     marker: InjectionMarker,
@@ -63,18 +75,21 @@ impl Clone for DeleteBuilder {
 
 impl DeleteBuilder {
     pub fn new() -> Self {
-        let args = Rc::new(RefCell::new(Args::default()));
+        let args = Shared::new(Args::default());
         let cond = Cond::with_args(args.clone());
         Self {
             args,
             cond,
-            tables: Vec::new(),
+            tables: CowArc::default(),
             where_clause: None,
             where_var: None,
-            order_by_cols: Vec::new(),
+            where_stale: AtomicBool::new(false),
+            order_by_cols: CowArc::default(),
             order: None,
             limit_var: None,
-            returning: Vec::new(),
+            returning: CowArc::default(),
+            joins: CowArc::default(),
+            allow_full_table: false,
             cte_var: None,
             cte: None,
             injection: Injection::new(),
@@ -96,7 +111,7 @@ impl DeleteBuilder {
     fn table_names(&self) -> Vec<String> {
         let mut table_names = Vec::new();
         if !self.tables.is_empty() {
-            table_names.extend(self.tables.clone());
+            table_names.extend(self.tables.iter().cloned());
         }
         if let Some(cte) = &self.cte {
             table_names.extend(cte.table_names_for_from());
@@ -144,46 +159,27 @@ impl DeleteBuilder {
     }
 
     pub fn clone_builder(&self) -> Self {
-        let old_args = self.args.borrow().clone();
-        let args = Rc::new(RefCell::new(old_args));
+        let args = Shared::new(self.args.snapshot());
         let cond = Cond::with_args(args.clone());
 
-        let mut cloned = Self {
+        Self {
             args,
             cond,
             tables: self.tables.clone(),
-            where_clause: self.where_clause.clone(),
+            where_clause: self.where_clause.as_ref().map(copy_where_clause),
             where_var: self.where_var.clone(),
+            where_stale: AtomicBool::new(self.where_clause.is_some()),
             cte_var: self.cte_var.clone(),
             cte: self.cte.clone(),
             order_by_cols: self.order_by_cols.clone(),
             order: self.order,
             limit_var: self.limit_var.clone(),
             returning: self.returning.clone(),
+            joins: self.joins.clone(),
+            allow_full_table: self.allow_full_table,
             injection: self.injection.clone(),
             marker: self.marker,
-        };
-
-        if let (Some(wc), Some(ph)) = (&self.where_clause, &self.where_var) {
-            let new_wc = Rc::new(RefCell::new(wc.borrow().clone()));
-            cloned.where_clause = Some(new_wc.clone());
-            cloned
-                .args
-                .borrow_mut()
-                .replace(ph, Arg::Builder(Box::new(WhereClauseBuilder::new(new_wc))));
-        }
-
-        if let (Some(cte), Some(ph)) = (&self.cte, &self.cte_var) {
-            let cte_for_arg = cte.clone();
-            let cte_for_field = cte_for_arg.clone();
-            cloned.cte = Some(cte_for_field);
-            cloned
-                .args
-                .borrow_mut()
-                .replace(ph, Arg::Builder(Box::new(cte_for_arg)));
         }
-
-        cloned
     }
 
     pub fn build(&self) -> (String, Vec<Arg>) {
@@ -198,7 +194,7 @@ impl DeleteBuilder {
     where
         T: IntoStrings,
     {
-        self.tables = collect_into_strings(tables);
+        self.tables = collect_into_strings(tables).into();
         self.marker = DELETE_MARKER_AFTER_DELETE_FROM;
         self
     }
@@ -283,23 +279,57 @@ impl DeleteBuilder {
         self
     }
 
+    /// Join: delete rows matching another table, in the form given by
+    /// `SqlDialect::delete_join_style`: `DELETE FROM t USING s` on PostgreSQL,
+    /// `DELETE t FROM t JOIN s ON ...` (no ORDER BY/LIMIT) on MySQL/SQL Server and a correlated
+    /// `EXISTS` subquery holding the WHERE on SQLite/Oracle.
+    pub fn join(&mut self, table: impl Into<String>, on_expr: impl IntoStrings) -> &mut Self {
+        self.join_with_option(None, table, on_expr)
+    }
+
+    pub fn join_with_option(
+        &mut self,
+        option: Option<JoinOption>,
+        table: impl Into<String>,
+        on_expr: impl IntoStrings,
+    ) -> &mut Self {
+        self.joins.push(TableJoin::new(
+            option,
+            table.into(),
+            collect_into_strings(on_expr),
+        ));
+        self.marker = DELETE_MARKER_AFTER_JOIN;
+        self
+    }
+
     pub fn order_by<T>(&mut self, cols: T) -> &mut Self
     where
         T: IntoStrings,
     {
-        self.order_by_cols = collect_into_strings(cols);
+        self.order_by_cols = collect_into_strings(cols)
+            .into_iter()
+            .map(OrderTerm::new)
+            .collect();
+        self.marker = DELETE_MARKER_AFTER_ORDER_BY;
+        self
+    }
+
+    /// OrderByTerm: append a structured term with its own direction and NULLS placement.
+    pub fn order_by_term(&mut self, term: impl Into<OrderTerm>) -> &mut Self {
+        let term = term.into().bind(|a| self.var(a));
+        self.order_by_cols.push(term);
         self.marker = DELETE_MARKER_AFTER_ORDER_BY;
         self
     }
 
     pub fn order_by_asc(&mut self, col: impl Into<String>) -> &mut Self {
-        self.order_by_cols.push(format!("{} ASC", col.into()));
+        self.order_by_cols.push(OrderTerm::new(col).asc());
         self.marker = DELETE_MARKER_AFTER_ORDER_BY;
         self
     }
 
     pub fn order_by_desc(&mut self, col: impl Into<String>) -> &mut Self {
-        self.order_by_cols.push(format!("{} DESC", col.into()));
+        self.order_by_cols.push(OrderTerm::new(col).desc());
         self.marker = DELETE_MARKER_AFTER_ORDER_BY;
         self
     }
@@ -330,19 +360,54 @@ impl DeleteBuilder {
     where
         T: IntoStrings,
     {
-        self.returning = collect_into_strings(cols);
+        self.returning = collect_into_strings(cols).into();
         self.marker = DELETE_MARKER_AFTER_RETURNING;
         self
     }
 
+    /// Permit building without a WHERE predicate. Without it, `build` panics and `try_build`
+    /// returns `BuildError::MissingWhere` so a statement never touches every row by accident.
+    pub fn allow_full_table(&mut self) -> &mut Self {
+        self.allow_full_table = true;
+        self
+    }
+
     pub fn sql(&mut self, sql: impl Into<String>) -> &mut Self {
         self.injection.sql(self.marker, sql);
         self
     }
 }
 
-impl Builder for DeleteBuilder {
-    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+impl DeleteBuilder {
+    /// Passes when a WHERE predicate or an inner join's ON restricts the affected rows. A
+    /// builder without a target table renders nothing and is not guarded.
+    fn check_where(&self) -> Result<(), BuildError> {
+        let has_where = self
+            .where_clause
+            .as_ref()
+            .is_some_and(|wc| !wc.borrow().is_empty());
+        let has_join_on = self
+            .joins
+            .iter()
+            .any(|j| j.is_inner() && !j.on_expr().is_empty());
+        if has_where || has_join_on || self.allow_full_table || self.table_names().is_empty() {
+            return Ok(());
+        }
+        Err(BuildError::MissingWhere {
+            statement: "DELETE",
+        })
+    }
+
+    fn rebind_where_var(&self) {
+        rebind_where_var(
+            &self.args,
+            self.where_clause.as_ref(),
+            self.where_var.as_ref(),
+            &self.where_stale,
+        );
+    }
+
+    fn build_format(&self, flavor: Flavor) -> Result<String, BuildError> {
         let mut buf = StringBuilder::new();
         write_injection(&mut buf, &self.injection, DELETE_MARKER_INIT);
 
@@ -351,15 +416,27 @@ impl Builder for DeleteBuilder {
             write_injection(&mut buf, &self.injection, DELETE_MARKER_AFTER_WITH);
         }
 
+        let join_style = if self.joins.is_empty() {
+            None
+        } else {
+            Some(self.join_style(flavor)?)
+        };
+
         let table_names = self.table_names();
-        if !table_names.is_empty() {
+        if join_style == Some(DeleteJoinStyle::FromJoin) {
+            let aliases: Vec<&str> = self.tables.iter().map(|t| table_alias(t)).collect();
+            buf.write_leading("DELETE");
+            buf.write_str(" ");
+            buf.write_str(&aliases.join(", "));
+        } else if !table_names.is_empty() {
             buf.write_leading("DELETE FROM");
             buf.write_str(" ");
             buf.write_str(&table_names.join(", "));
         }
         write_injection(&mut buf, &self.injection, DELETE_MARKER_AFTER_DELETE_FROM);
 
-        if flavor == Flavor::SQLServer && !self.returning.is_empty() {
+        let returning_style = flavor.dialect().returning_style();
+        if returning_style == ReturningStyle::Output && !self.returning.is_empty() {
             buf.write_leading("OUTPUT");
             buf.write_str(" ");
             let prefixed: Vec<String> = self
@@ -371,19 +448,78 @@ impl Builder for DeleteBuilder {
             write_injection(&mut buf, &self.injection, DELETE_MARKER_AFTER_RETURNING);
         }
 
-        if let Some(ph) = &self.where_var {
-            buf.write_leading(ph);
+        let mut join_where = None;
+        let mut exists = None;
+        if let Some(style) = join_style {
+            let (first, rest) = self.joins.split_first().expect("joins are not empty");
+            let mut source = first.table.clone();
+            for join in rest {
+                source.push(' ');
+                source.push_str(&join.render());
+            }
+            let on = Some(first.on_expr()).filter(|s| !s.is_empty());
+
+            match style {
+                DeleteJoinStyle::Using => {
+                    buf.write_leading("USING");
+                    buf.write_str(" ");
+                    buf.write_str(&source);
+                    join_where = on;
+                }
+                DeleteJoinStyle::FromJoin => {
+                    let joins: Vec<String> = self.joins.iter().map(TableJoin::render).collect();
+                    buf.write_leading("FROM");
+                    buf.write_str(" ");
+                    buf.write_str(&table_names.join(", "));
+                    buf.write_str(" ");
+                    buf.write_str(&joins.join(" "));
+                }
+                DeleteJoinStyle::Exists => {
+                    let mut sub = format!("EXISTS (SELECT 1 FROM {source}");
+                    if let Some(w) =
+                        where_with(self.where_var.as_ref(), self.where_clause.as_ref(), on)
+                    {
+                        sub.push(' ');
+                        sub.push_str(&w);
+                    }
+                    sub.push(')');
+                    exists = Some(sub);
+                }
+                DeleteJoinStyle::Unsupported => unreachable!("rejected by join_style"),
+            }
+            if style != DeleteJoinStyle::Exists {
+                write_injection(&mut buf, &self.injection, DELETE_MARKER_AFTER_JOIN);
+            }
+        }
+
+        let where_sql = match exists {
+            Some(sub) => Some(format!("WHERE {sub}")),
+            None => where_with(
+                self.where_var.as_ref(),
+                self.where_clause.as_ref(),
+                join_where,
+            ),
+        };
+        if let Some(w) = where_sql {
+            buf.write_leading(&w);
+        }
+        if self.where_var.is_some()
+            || matches!(
+                join_style,
+                Some(DeleteJoinStyle::Using | DeleteJoinStyle::Exists)
+            )
+        {
             write_injection(&mut buf, &self.injection, DELETE_MARKER_AFTER_WHERE);
         }
 
         if !self.order_by_cols.is_empty() {
-            buf.write_leading("ORDER BY");
-            buf.write_str(" ");
-            buf.write_str(&self.order_by_cols.join(", "));
-            if let Some(order) = self.order {
-                buf.write_str(" ");
-                buf.write_str(order);
-            }
+            write_order_by(
+                &mut buf,
+                &self.order_by_cols,
+                self.order,
+                flavor,
+                OrderByScope::Statement,
+            )?;
             write_injection(&mut buf, &self.injection, DELETE_MARKER_AFTER_ORDER_BY);
         }
 
@@ -394,17 +530,74 @@ impl Builder for DeleteBuilder {
             write_injection(&mut buf, &self.injection, DELETE_MARKER_AFTER_LIMIT);
         }
 
-        if (flavor == Flavor::PostgreSQL || flavor == Flavor::SQLite) && !self.returning.is_empty()
-        {
+        if returning_style == ReturningStyle::Returning && !self.returning.is_empty() {
             buf.write_leading("RETURNING");
             buf.write_str(" ");
             buf.write_str(&self.returning.join(", "));
             write_injection(&mut buf, &self.injection, DELETE_MARKER_AFTER_RETURNING);
         }
 
+        Ok(buf.into_string())
+    }
+}
+
+impl DeleteBuilder {
+    fn join_style(&self, flavor: Flavor) -> Result<DeleteJoinStyle, BuildError> {
+        let unsupported = |feature| BuildError::Unsupported { flavor, feature };
+        match flavor.dialect().delete_join_style() {
+            DeleteJoinStyle::Unsupported => Err(unsupported("DELETE with JOIN")),
+            // MySQL's multiple-table DELETE syntax has no ORDER BY or LIMIT.
+            DeleteJoinStyle::FromJoin
+                if !self.order_by_cols.is_empty() || self.limit_var.is_some() =>
+            {
+                Err(unsupported("ORDER BY/LIMIT on multi-table DELETE"))
+            }
+            DeleteJoinStyle::FromJoin => Ok(DeleteJoinStyle::FromJoin),
+            // USING and EXISTS move the first join's ON into WHERE, which only preserves
+            // inner-join semantics.
+            _ if !self.joins[0].is_inner() => {
+                Err(unsupported("outer join as the first joined DELETE source"))
+            }
+            style => Ok(style),
+        }
+    }
+}
+
+impl Builder for DeleteBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        self.rebind_where_var();
+        unwrap_or_panic(self.check_where());
+        let format = unwrap_or_panic(self.build_format(flavor));
+        self.args
+            .snapshot()
+            .compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        self.rebind_where_var();
+        self.check_where()?;
+        let format = self.build_format(flavor)?;
+        self.args
+            .snapshot()
+            .try_compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        self.rebind_where_var();
+        self.check_where()?;
+        let format = self.build_format(flavor)?;
         self.args
-            .borrow()
-            .compile_with_flavor(&buf.into_string(), flavor, initial_arg)
+            .snapshot()
+            .compile_into(&format, flavor, sql, args)
     }
 
     fn flavor(&self) -> Flavor {
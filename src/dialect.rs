@@ -7,17 +7,29 @@ pub enum Dialect {
     QuestionMark,
     /// 使用 `$1, $2, ...` 作为占位符（常见于 PostgreSQL）。
     DollarNumbered,
+    /// 使用 `@p1, @p2, ...` 作为占位符（常见于 SQL Server）。
+    AtPNumbered,
+    /// 使用 `:1, :2, ...` 作为占位符（常见于 Oracle）。
+    ColonNumbered,
 }
 
 impl Dialect {
-    #[allow(dead_code)]
-    pub(crate) fn write_placeholder(self, index_1_based: usize, out: &mut String) {
+    /// 写入第 `index_1_based` 个参数的占位符。
+    pub fn write_placeholder(self, index_1_based: usize, out: &mut String) {
         match self {
             Self::QuestionMark => out.push('?'),
             Self::DollarNumbered => {
                 out.push('$');
                 out.push_str(&index_1_based.to_string());
             }
+            Self::AtPNumbered => {
+                out.push_str("@p");
+                out.push_str(&index_1_based.to_string());
+            }
+            Self::ColonNumbered => {
+                out.push(':');
+                out.push_str(&index_1_based.to_string());
+            }
         }
     }
 }
@@ -39,4 +51,12 @@ mod tests {
         Dialect::DollarNumbered.write_placeholder(12, &mut s);
         assert_eq!(s, "$12");
     }
+
+    #[test]
+    fn write_placeholder_at_p_and_colon_numbered() {
+        let mut s = String::new();
+        Dialect::AtPNumbered.write_placeholder(3, &mut s);
+        Dialect::ColonNumbered.write_placeholder(4, &mut s);
+        assert_eq!(s, "@p3:4");
+    }
 }
@@ -0,0 +1,166 @@
+//! DropIndexBuilder: build DROP INDEX statements.
+
+use crate::args::{Args, BuildError, unwrap_or_panic};
+use crate::create_index::quote_ident;
+use crate::flavor::Flavor;
+use crate::injection::{Injection, InjectionMarker};
+use crate::modifiers::{Arg, Builder};
+use crate::shared::Shared;
+use crate::string_builder::StringBuilder;
+
+const DI_MARKER_INIT: InjectionMarker = 0;
+const DI_MARKER_AFTER_DROP: InjectionMarker = 1;
+const DI_MARKER_AFTER_ON: InjectionMarker = 2;
+
+#[derive(Debug, Clone)]
+pub struct DropIndexBuilder {
+    name: Option<String>,
+    if_exists: bool,
+    table: Option<String>,
+
+    args: Shared<Args>,
+    injection: Injection,
+    marker: InjectionMarker,
+}
+
+impl Default for DropIndexBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DropIndexBuilder {
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            if_exists: false,
+            table: None,
+            args: Shared::new(Args::default()),
+            injection: Injection::new(),
+            marker: DI_MARKER_INIT,
+        }
+    }
+
+    pub fn set_flavor(&mut self, flavor: Flavor) -> Flavor {
+        let mut a = self.args.borrow_mut();
+        let old = a.flavor;
+        a.flavor = flavor;
+        old
+    }
+
+    pub fn flavor(&self) -> Flavor {
+        self.args.borrow().flavor
+    }
+
+    pub fn drop_index(&mut self, name: &str) -> &mut Self {
+        self.name = Some(name.to_string());
+        self.marker = DI_MARKER_AFTER_DROP;
+        self
+    }
+
+    pub fn if_exists(&mut self) -> &mut Self {
+        self.if_exists = true;
+        self
+    }
+
+    /// Table owning the index; required by MySQL, Doris, SQL Server and ClickHouse,
+    /// ignored elsewhere.
+    pub fn on(&mut self, table: &str) -> &mut Self {
+        self.table = Some(table.to_string());
+        self.marker = DI_MARKER_AFTER_ON;
+        self
+    }
+
+    pub fn sql(&mut self, sql: impl Into<String>) -> &mut Self {
+        self.injection.sql(self.marker, sql);
+        self
+    }
+}
+
+impl DropIndexBuilder {
+    fn build_format(&self, flavor: Flavor) -> Result<String, BuildError> {
+        use Flavor::*;
+        let mut buf = StringBuilder::new();
+        let unsupported = |feature| BuildError::Unsupported { flavor, feature };
+        write_injection(&mut buf, &self.injection, DI_MARKER_INIT);
+
+        if flavor == Presto {
+            return Err(unsupported("DROP INDEX"));
+        }
+
+        // ClickHouse indexes are data-skipping indexes dropped through ALTER TABLE.
+        if flavor == ClickHouse {
+            let Some(t) = &self.table else {
+                return Err(unsupported("DROP INDEX without ON table"));
+            };
+            buf.write_leading("ALTER TABLE ");
+            buf.write_str(&quote_ident(flavor, t));
+            write_injection(&mut buf, &self.injection, DI_MARKER_AFTER_ON);
+        }
+
+        buf.write_leading("DROP INDEX");
+        if self.if_exists {
+            if matches!(flavor, MySQL | Oracle | Informix) {
+                return Err(unsupported("DROP INDEX IF EXISTS"));
+            } else {
+                buf.write_str(" IF EXISTS");
+            }
+        }
+        if let Some(name) = &self.name {
+            buf.write_leading(&quote_ident(flavor, name));
+        }
+        write_injection(&mut buf, &self.injection, DI_MARKER_AFTER_DROP);
+
+        if matches!(flavor, MySQL | Doris | SQLServer) {
+            match &self.table {
+                Some(t) => {
+                    buf.write_leading("ON ");
+                    buf.write_str(&quote_ident(flavor, t));
+                    write_injection(&mut buf, &self.injection, DI_MARKER_AFTER_ON);
+                }
+                None => return Err(unsupported("DROP INDEX without ON table")),
+            }
+        }
+
+        Ok(buf.into_string())
+    }
+}
+
+impl Builder for DropIndexBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        let format = unwrap_or_panic(self.build_format(flavor));
+        self.args
+            .snapshot()
+            .compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        let format = self.build_format(flavor)?;
+        self.args
+            .snapshot()
+            .try_compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn flavor(&self) -> Flavor {
+        self.flavor()
+    }
+}
+
+pub fn drop_index(name: impl Into<String>) -> DropIndexBuilder {
+    let mut builder = DropIndexBuilder::new();
+    builder.drop_index(&name.into());
+    builder
+}
+
+fn write_injection(buf: &mut StringBuilder, inj: &Injection, marker: InjectionMarker) {
+    let sqls = inj.at(marker);
+    if sqls.is_empty() {
+        return;
+    }
+    buf.write_leading("");
+    buf.write_str(&sqls.join(" "));
+}
@@ -0,0 +1,156 @@
+//! DropTableBuilder: build DROP TABLE statements.
+
+use crate::args::{Args, BuildError, unwrap_or_panic};
+use crate::create_index::quote_ident;
+use crate::flavor::Flavor;
+use crate::injection::{Injection, InjectionMarker};
+use crate::macros::{IntoStrings, collect_into_strings};
+use crate::modifiers::{Arg, Builder};
+use crate::shared::Shared;
+use crate::string_builder::StringBuilder;
+
+const DT_MARKER_INIT: InjectionMarker = 0;
+const DT_MARKER_AFTER_DROP: InjectionMarker = 1;
+
+#[derive(Debug, Clone)]
+pub struct DropTableBuilder {
+    tables: Vec<String>,
+    if_exists: bool,
+    cascade: bool,
+
+    args: Shared<Args>,
+    injection: Injection,
+    marker: InjectionMarker,
+}
+
+impl Default for DropTableBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DropTableBuilder {
+    pub fn new() -> Self {
+        Self {
+            tables: Vec::new(),
+            if_exists: false,
+            cascade: false,
+            args: Shared::new(Args::default()),
+            injection: Injection::new(),
+            marker: DT_MARKER_INIT,
+        }
+    }
+
+    pub fn set_flavor(&mut self, flavor: Flavor) -> Flavor {
+        let mut a = self.args.borrow_mut();
+        let old = a.flavor;
+        a.flavor = flavor;
+        old
+    }
+
+    pub fn flavor(&self) -> Flavor {
+        self.args.borrow().flavor
+    }
+
+    pub fn drop_table<T>(&mut self, tables: T) -> &mut Self
+    where
+        T: IntoStrings,
+    {
+        self.tables = collect_into_strings(tables);
+        self.marker = DT_MARKER_AFTER_DROP;
+        self
+    }
+
+    pub fn if_exists(&mut self) -> &mut Self {
+        self.if_exists = true;
+        self
+    }
+
+    /// `CASCADE` (`CASCADE CONSTRAINTS` on Oracle).
+    pub fn cascade(&mut self) -> &mut Self {
+        self.cascade = true;
+        self
+    }
+
+    pub fn sql(&mut self, sql: impl Into<String>) -> &mut Self {
+        self.injection.sql(self.marker, sql);
+        self
+    }
+}
+
+impl DropTableBuilder {
+    fn build_format(&self, flavor: Flavor) -> Result<String, BuildError> {
+        use Flavor::*;
+        let mut buf = StringBuilder::new();
+        let unsupported = |feature| BuildError::Unsupported { flavor, feature };
+        write_injection(&mut buf, &self.injection, DT_MARKER_INIT);
+
+        buf.write_leading("DROP TABLE");
+        if self.if_exists {
+            if flavor == Oracle {
+                return Err(unsupported("DROP TABLE IF EXISTS"));
+            } else {
+                buf.write_str(" IF EXISTS");
+            }
+        }
+        if self.tables.len() > 1 && matches!(flavor, SQLite | Oracle | CQL) {
+            return Err(unsupported("dropping several tables at once"));
+        }
+        if !self.tables.is_empty() {
+            let tables: Vec<String> = self.tables.iter().map(|t| quote_ident(flavor, t)).collect();
+            buf.write_leading(&tables.join(", "));
+        }
+
+        if self.cascade {
+            match flavor {
+                Oracle => buf.write_str(" CASCADE CONSTRAINTS"),
+                SQLite | SQLServer | CQL | ClickHouse | Doris => {
+                    return Err(unsupported("DROP TABLE ... CASCADE"));
+                }
+                _ => buf.write_str(" CASCADE"),
+            }
+        }
+        write_injection(&mut buf, &self.injection, DT_MARKER_AFTER_DROP);
+
+        Ok(buf.into_string())
+    }
+}
+
+impl Builder for DropTableBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        let format = unwrap_or_panic(self.build_format(flavor));
+        self.args
+            .snapshot()
+            .compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        let format = self.build_format(flavor)?;
+        self.args
+            .snapshot()
+            .try_compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn flavor(&self) -> Flavor {
+        self.flavor()
+    }
+}
+
+pub fn drop_table(table: impl Into<String>) -> DropTableBuilder {
+    let mut builder = DropTableBuilder::new();
+    builder.drop_table(table.into());
+    builder
+}
+
+fn write_injection(buf: &mut StringBuilder, inj: &Injection, marker: InjectionMarker) {
+    let sqls = inj.at(marker);
+    if sqls.is_empty() {
+        return;
+    }
+    buf.write_leading("");
+    buf.write_str(&sqls.join(" "));
+}
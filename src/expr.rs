@@ -1,6 +1,9 @@
 //! SQL 表达式片段。
 
+use crate::args::BuildError;
 use crate::dialect::Dialect;
+use crate::flavor::{Flavor, default_flavor};
+use crate::modifiers::{Arg, Builder};
 use crate::value::SqlValue;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -49,8 +52,8 @@ impl Expr {
         self
     }
 
-    #[allow(dead_code)]
-    pub(crate) fn build(&self, dialect: Dialect) -> (String, Vec<SqlValue>) {
+    /// 按占位符风格独立构建，参数从 1 开始编号。
+    pub fn build(&self, dialect: Dialect) -> (String, Vec<SqlValue>) {
         let mut sql = String::new();
         let mut args = Vec::new();
 
@@ -69,11 +72,66 @@ impl Expr {
     }
 }
 
+/// 作为 `Arg` 嵌入 builder 时，参数并入外层 `Args` 并按 flavor 重新编号。
+impl Builder for Expr {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        let mut sql = String::new();
+        let mut args = initial_arg.to_vec();
+        self.build_into(flavor, &mut sql, &mut args)
+            .expect("expr build never fails");
+        (sql, args)
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        let dialect = flavor.dialect();
+        for part in &self.parts {
+            match part {
+                Part::Sql(s) => sql.push_str(s),
+                Part::Arg(v) => {
+                    dialect.write_placeholder(sql, args.len() + 1);
+                    args.push(Arg::Value(v.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn flavor(&self) -> Flavor {
+        default_flavor()
+    }
+}
+
+impl From<Expr> for Arg {
+    fn from(e: Expr) -> Self {
+        Arg::Builder(Box::new(e))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Expr;
     use crate::Dialect;
     use crate::SqlValue;
+    use crate::flavor::Flavor;
+    use crate::modifiers::{Arg, Builder};
+    use crate::select::SelectBuilder;
+    use crate::update::UpdateBuilder;
+    use pretty_assertions::assert_eq;
+
+    fn field_expr(ids: &[i64]) -> Expr {
+        let mut e = Expr::raw("FIELD(id");
+        for id in ids {
+            e.push_raw(", ");
+            e.push_arg(*id);
+        }
+        e.push_raw(")");
+        e
+    }
 
     #[test]
     fn raw_is_not_parameterized() {
@@ -112,4 +170,66 @@ mod tests {
         assert_eq!(sql, "a = ? AND b = ?");
         assert_eq!(args, vec![SqlValue::I64(1), SqlValue::I64(2)]);
     }
+
+    #[test]
+    fn expr_in_select_where_and_order_by() {
+        let mut sb = SelectBuilder::new();
+        let mut lower = Expr::raw("LOWER(name) = ");
+        lower.push_arg("bob");
+        let total = sb.expr(Expr::raw("COUNT(*) OVER ()"));
+        let filters = [sb.gt("age", 18_i64), sb.expr(lower)];
+        let order = sb.expr(field_expr(&[3, 1]));
+        sb.select(["id".to_string(), total])
+            .from(["users"])
+            .where_(filters)
+            .order_by([order]);
+
+        let (sql, args) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, COUNT(*) OVER () FROM users WHERE age > $1 AND LOWER(name) = $2 ORDER BY FIELD(id, $3, $4)"
+        );
+        let values: Vec<SqlValue> = args
+            .into_iter()
+            .map(|a| match a {
+                Arg::Value(v) => v,
+                other => panic!("unexpected arg {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                SqlValue::I64(18),
+                SqlValue::String("bob".into()),
+                SqlValue::I64(3),
+                SqlValue::I64(1)
+            ]
+        );
+
+        let (sql, _) = sb.build_with_flavor(Flavor::SQLServer, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, COUNT(*) OVER () FROM users WHERE age > @p1 AND LOWER(name) = @p2 ORDER BY FIELD(id, @p3, @p4)"
+        );
+    }
+
+    #[test]
+    fn expr_as_assignment_and_cond_value() {
+        let mut ub = UpdateBuilder::new();
+        let mut bump = Expr::raw("score + ");
+        bump.push_arg(5_i64);
+        let mut since = Expr::raw("NOW() - INTERVAL ");
+        since.push_arg(7_i64);
+        since.push_raw(" DAY");
+        let assigns = [ub.assign("score", bump), ub.assign("name", "x")];
+        let filter = ub.lt("seen_at", since);
+        ub.update(["players"]).set(assigns).where_([filter]);
+
+        let (sql, args) = ub.build_with_flavor(Flavor::Oracle, &[]);
+        assert_eq!(
+            sql,
+            "UPDATE players SET score = score + :1, name = :2 WHERE seen_at < NOW() - INTERVAL :3 DAY"
+        );
+        assert_eq!(args.len(), 3);
+    }
 }
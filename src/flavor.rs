@@ -1,7 +1,8 @@
 //! SQL Flavor (dialects): control placeholders, quoting, and interpolation behavior.
 
+use crate::sql_dialect::{BUILTIN_DIALECTS, DialectId, SqlDialect, custom_dialect};
 use std::fmt;
-use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Mutex, MutexGuard};
 
 /// Flavor enum describing supported SQL dialects.
@@ -18,13 +19,17 @@ pub enum Flavor {
     Oracle,
     Informix,
     Doris,
+    /// A user-defined dialect returned by [`crate::sql_dialect::register_dialect`].
+    Custom(DialectId),
 }
 
-static DEFAULT_FLAVOR: AtomicU8 = AtomicU8::new(Flavor::MySQL as u8);
+const CUSTOM_FLAVOR_BASE: u32 = 16;
+
+static DEFAULT_FLAVOR: AtomicU32 = AtomicU32::new(0);
 static DEFAULT_FLAVOR_LOCK: Mutex<()> = Mutex::new(());
 
 impl Flavor {
-    fn from_u8(v: u8) -> Self {
+    fn from_u32(v: u32) -> Self {
         match v {
             0 => Self::MySQL,
             1 => Self::PostgreSQL,
@@ -36,24 +41,47 @@ impl Flavor {
             7 => Self::Oracle,
             8 => Self::Informix,
             9 => Self::Doris,
+            v if v >= CUSTOM_FLAVOR_BASE => {
+                Self::Custom(DialectId((v - CUSTOM_FLAVOR_BASE) as u16))
+            }
             _ => Self::MySQL,
         }
     }
 
-    fn to_u8(self) -> u8 {
-        self as u8
+    fn to_u32(self) -> u32 {
+        match self {
+            Self::MySQL => 0,
+            Self::PostgreSQL => 1,
+            Self::SQLite => 2,
+            Self::SQLServer => 3,
+            Self::CQL => 4,
+            Self::ClickHouse => 5,
+            Self::Presto => 6,
+            Self::Oracle => 7,
+            Self::Informix => 8,
+            Self::Doris => 9,
+            Self::Custom(id) => CUSTOM_FLAVOR_BASE + u32::from(id.0),
+        }
+    }
+
+    /// The dialect implementation behind this flavor.
+    pub fn dialect(self) -> &'static dyn SqlDialect {
+        match self {
+            Self::Custom(id) => custom_dialect(id).unwrap_or(&BUILTIN_DIALECTS[0]),
+            builtin => &BUILTIN_DIALECTS[builtin.to_u32() as usize],
+        }
     }
 }
 
 /// Get the current global default Flavor.
 pub fn default_flavor() -> Flavor {
-    Flavor::from_u8(DEFAULT_FLAVOR.load(Ordering::Relaxed))
+    Flavor::from_u32(DEFAULT_FLAVOR.load(Ordering::Relaxed))
 }
 
 /// Set the global default Flavor and return the previous one.
 pub fn set_default_flavor(flavor: Flavor) -> Flavor {
-    let old = DEFAULT_FLAVOR.swap(flavor.to_u8(), Ordering::Relaxed);
-    Flavor::from_u8(old)
+    let old = DEFAULT_FLAVOR.swap(flavor.to_u32(), Ordering::Relaxed);
+    Flavor::from_u32(old)
 }
 
 /// RAII guard for temporarily changing the global Flavor (holds a global lock to avoid test interference).
@@ -79,19 +107,7 @@ pub fn set_default_flavor_scoped(flavor: Flavor) -> DefaultFlavorGuard {
 
 impl fmt::Display for Flavor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = match self {
-            Self::MySQL => "MySQL",
-            Self::PostgreSQL => "PostgreSQL",
-            Self::SQLite => "SQLite",
-            Self::SQLServer => "SQLServer",
-            Self::CQL => "CQL",
-            Self::ClickHouse => "ClickHouse",
-            Self::Presto => "Presto",
-            Self::Oracle => "Oracle",
-            Self::Informix => "Informix",
-            Self::Doris => "Doris",
-        };
-        f.write_str(s)
+        f.write_str(self.dialect().name())
     }
 }
 
@@ -110,27 +126,11 @@ pub enum InterpolateError {
 impl Flavor {
     /// Quote an identifier using the dialect's rules.
     pub fn quote(self, name: &str) -> String {
-        match self {
-            Self::MySQL | Self::ClickHouse | Self::Doris => format!("`{name}`"),
-            Self::PostgreSQL
-            | Self::SQLServer
-            | Self::SQLite
-            | Self::Presto
-            | Self::Oracle
-            | Self::Informix => {
-                format!("\"{name}\"")
-            }
-            Self::CQL => format!("'{name}'"),
-        }
+        self.dialect().quote(name)
     }
 
     /// Dialect-specific INSERT/IGNORE keyword choice.
     pub fn prepare_insert_ignore(self) -> &'static str {
-        match self {
-            Flavor::MySQL | Flavor::Oracle => "INSERT IGNORE",
-            Flavor::PostgreSQL => "INSERT",
-            Flavor::SQLite => "INSERT OR IGNORE",
-            _ => "INSERT",
-        }
+        self.dialect().insert_ignore_keyword()
     }
 }
@@ -1,13 +1,14 @@
 //! Injection: inject extra SQL fragments at predefined markers.
 #![allow(dead_code)]
 
+use crate::shared::CowArc;
 use std::collections::HashMap;
 
 pub(crate) type InjectionMarker = usize;
 
 #[derive(Debug, Default, Clone)]
 pub(crate) struct Injection {
-    marker_sqls: HashMap<InjectionMarker, Vec<String>>,
+    marker_sqls: CowArc<HashMap<InjectionMarker, Vec<String>>>,
 }
 
 impl Injection {
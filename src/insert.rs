@@ -1,14 +1,14 @@
 //! InsertBuilder: build INSERT statements.
 
-use crate::args::Args;
+use crate::args::{Args, BuildError, unwrap_or_panic};
 use crate::flavor::Flavor;
 use crate::injection::{Injection, InjectionMarker};
 use crate::macros::{IntoStrings, collect_into_strings};
-use crate::modifiers::{Arg, Builder, escape, escape_all};
+use crate::modifiers::{Arg, Builder, arith_assignment, escape, escape_all, raw};
 use crate::select::SelectBuilder;
+use crate::shared::Shared;
+use crate::sql_dialect::{ReturningStyle, UpsertStyle};
 use crate::string_builder::StringBuilder;
-use std::cell::RefCell;
-use std::rc::Rc;
 
 const INSERT_MARKER_INIT: InjectionMarker = 0;
 const INSERT_MARKER_AFTER_INSERT_INTO: InjectionMarker = 1;
@@ -16,6 +16,30 @@ const INSERT_MARKER_AFTER_COLS: InjectionMarker = 2;
 const INSERT_MARKER_AFTER_VALUES: InjectionMarker = 3;
 const INSERT_MARKER_AFTER_SELECT: InjectionMarker = 4;
 const INSERT_MARKER_AFTER_RETURNING: InjectionMarker = 5;
+const INSERT_MARKER_AFTER_ON_CONFLICT: InjectionMarker = 6;
+
+/// Conflict target of an upsert.
+#[derive(Debug, Clone)]
+enum ConflictTarget {
+    Columns(Vec<String>),
+    Constraint(String),
+}
+
+/// Row a deferred column reference points at; rendered per flavor at build time.
+#[derive(Debug, Clone)]
+enum RowRef {
+    /// The incoming row: `EXCLUDED.col`, `VALUES(col)` or `alias.col`.
+    Excluded(String),
+    /// The existing row: `table.col` under ON CONFLICT, where a bare name is ambiguous.
+    Current(String),
+}
+
+/// What to do with a conflicting row.
+#[derive(Debug, Clone)]
+enum ConflictAction {
+    Nothing,
+    Update(Vec<String>),
+}
 
 #[derive(Debug, Clone)]
 pub struct InsertBuilder {
@@ -25,13 +49,20 @@ pub struct InsertBuilder {
     values: Vec<Vec<String>>,
     returning: Vec<String>,
 
-    args: Rc<RefCell<Args>>,
+    args: Shared<Args>,
 
     injection: Injection,
     marker: InjectionMarker,
 
     // Insert-Select holder
     sb_holder: Option<String>,
+
+    // Upsert
+    conflict_target: Option<ConflictTarget>,
+    conflict_action: Option<ConflictAction>,
+    row_alias: Option<String>,
+    // Placeholders created by `excluded` and the arithmetic helpers, resolved per flavor at build time.
+    row_refs: Shared<Vec<(String, RowRef)>>,
 }
 
 impl Default for InsertBuilder {
@@ -48,10 +79,14 @@ impl InsertBuilder {
             cols: Vec::new(),
             values: Vec::new(),
             returning: Vec::new(),
-            args: Rc::new(RefCell::new(Args::default())),
+            args: Shared::new(Args::default()),
             injection: Injection::new(),
             marker: INSERT_MARKER_INIT,
             sb_holder: None,
+            conflict_target: None,
+            conflict_action: None,
+            row_alias: None,
+            row_refs: Shared::new(Vec::new()),
         }
     }
 
@@ -69,9 +104,10 @@ impl InsertBuilder {
     pub fn clone_builder(&self) -> Self {
         let mut cloned = self.clone();
 
-        // 深拷贝 Args（避免 Rc 共享）
-        let args = Rc::new(RefCell::new(self.args.borrow().clone()));
+        // 深拷贝 Args 与行引用（避免共享）
+        let args = Shared::new(self.args.snapshot());
         cloned.args = args;
+        cloned.row_refs = Shared::new(self.row_refs.borrow().clone());
 
         cloned
     }
@@ -80,6 +116,33 @@ impl InsertBuilder {
         Builder::build(self)
     }
 
+    /// Split the VALUES rows into statements of at most `max_params` bind parameters.
+    ///
+    /// Every statement keeps the columns, RETURNING clause and injected SQL; a single row
+    /// larger than the limit still gets a statement of its own.
+    pub fn build_batches(&self, max_params: usize) -> Vec<(String, Vec<Arg>)> {
+        self.build_batches_with_flavor(self.flavor(), Some(max_params))
+    }
+
+    /// Like `build_batches`; `None` uses the flavor's limit (`SqlDialect::max_params`).
+    pub fn build_batches_with_flavor(
+        &self,
+        flavor: Flavor,
+        max_params: Option<usize>,
+    ) -> Vec<(String, Vec<Arg>)> {
+        self.batches(flavor, max_params, false)
+            .expect("lenient build never fails")
+    }
+
+    /// Fallible version of `build_batches_with_flavor`.
+    pub fn try_build_batches_with_flavor(
+        &self,
+        flavor: Flavor,
+        max_params: Option<usize>,
+    ) -> Result<Vec<(String, Vec<Arg>)>, BuildError> {
+        self.batches(flavor, max_params, true)
+    }
+
     fn var(&self, v: impl Into<Arg>) -> String {
         self.args.borrow_mut().add(v)
     }
@@ -97,10 +160,10 @@ impl InsertBuilder {
         self.table = Some(escape(table));
         self.marker = INSERT_MARKER_AFTER_INSERT_INTO;
 
-        // PostgreSQL: ON CONFLICT DO NOTHING must appear after VALUES
-        if flavor == Flavor::PostgreSQL {
+        // e.g. PostgreSQL: ON CONFLICT DO NOTHING must appear after VALUES
+        if let Some(suffix) = flavor.dialect().insert_ignore_suffix() {
             self.marker = INSERT_MARKER_AFTER_VALUES;
-            self.sql("ON CONFLICT DO NOTHING");
+            self.sql(suffix);
         }
         self
     }
@@ -150,14 +213,114 @@ impl InsertBuilder {
         self
     }
 
+    /// Upsert: set the conflict target columns (`ON CONFLICT (cols)`).
+    ///
+    /// MySQL/Doris have no conflict target and ignore it.
+    pub fn on_conflict<T>(&mut self, cols: T) -> &mut Self
+    where
+        T: IntoStrings,
+    {
+        self.conflict_target = Some(ConflictTarget::Columns(escape_all(collect_into_strings(
+            cols,
+        ))));
+        self.marker = INSERT_MARKER_AFTER_ON_CONFLICT;
+        self
+    }
+
+    /// Upsert: use a named constraint as the conflict target (PostgreSQL only).
+    pub fn on_conflict_constraint(&mut self, name: &str) -> &mut Self {
+        self.conflict_target = Some(ConflictTarget::Constraint(escape(name)));
+        self.marker = INSERT_MARKER_AFTER_ON_CONFLICT;
+        self
+    }
+
+    /// Upsert: update the conflicting row with the given assignments.
+    pub fn do_update<T>(&mut self, assignments: T) -> &mut Self
+    where
+        T: IntoStrings,
+    {
+        self.conflict_action = Some(ConflictAction::Update(collect_into_strings(assignments)));
+        self.marker = INSERT_MARKER_AFTER_ON_CONFLICT;
+        self
+    }
+
+    /// Upsert: keep the existing row.
+    ///
+    /// MySQL/Doris emulate it with a no-op `ON DUPLICATE KEY UPDATE first_col = first_col`.
+    pub fn do_nothing(&mut self) -> &mut Self {
+        self.conflict_action = Some(ConflictAction::Nothing);
+        self.marker = INSERT_MARKER_AFTER_ON_CONFLICT;
+        self
+    }
+
+    /// Upsert: name the incoming row (`VALUES (...) AS alias`, MySQL 8.0.19+).
+    ///
+    /// `excluded` then renders `alias.col` instead of the deprecated `VALUES(col)`.
+    pub fn row_alias(&mut self, alias: &str) -> &mut Self {
+        self.row_alias = Some(escape(alias));
+        self
+    }
+
+    /// Reference a column of the incoming row: `EXCLUDED.col`, `VALUES(col)` or `alias.col`.
+    pub fn excluded(&self, field: &str) -> String {
+        self.row_ref(RowRef::Excluded(escape(field)))
+    }
+
+    pub fn assign(&self, field: &str, value: impl Into<Arg>) -> String {
+        format!("{} = {}", escape(field), self.var(value))
+    }
+
+    /// Assign the incoming row's value: `field = EXCLUDED.field`.
+    pub fn assign_excluded(&self, field: &str) -> String {
+        format!("{} = {}", escape(field), self.excluded(field))
+    }
+
+    /// `field = field + 1`; the right-hand side is qualified with the table under ON CONFLICT.
+    pub fn incr(&self, field: &str) -> String {
+        self.arith(field, '+', "1")
+    }
+
+    pub fn decr(&self, field: &str) -> String {
+        self.arith(field, '-', "1")
+    }
+
+    pub fn add(&self, field: &str, value: impl Into<Arg>) -> String {
+        self.arith(field, '+', &self.var(value))
+    }
+
+    pub fn sub(&self, field: &str, value: impl Into<Arg>) -> String {
+        self.arith(field, '-', &self.var(value))
+    }
+
+    pub fn mul(&self, field: &str, value: impl Into<Arg>) -> String {
+        self.arith(field, '*', &self.var(value))
+    }
+
+    pub fn div(&self, field: &str, value: impl Into<Arg>) -> String {
+        self.arith(field, '/', &self.var(value))
+    }
+
+    fn arith(&self, field: &str, op: char, rhs: &str) -> String {
+        let f = escape(field);
+        let current = self.row_ref(RowRef::Current(f.clone()));
+        arith_assignment(&f, &current, op, rhs)
+    }
+
+    fn row_ref(&self, r: RowRef) -> String {
+        let (RowRef::Excluded(col) | RowRef::Current(col)) = &r;
+        let ph = self.var(raw(col.clone()));
+        self.row_refs.borrow_mut().push((ph.clone(), r));
+        ph
+    }
+
     pub fn sql(&mut self, sql: impl Into<String>) -> &mut Self {
         self.injection.sql(self.marker, sql);
         self
     }
 }
 
-impl Builder for InsertBuilder {
-    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+impl InsertBuilder {
+    fn build_format(&self, flavor: Flavor) -> Result<String, BuildError> {
         let mut buf = StringBuilder::new();
         write_injection(&mut buf, &self.injection, INSERT_MARKER_INIT);
 
@@ -187,10 +350,8 @@ impl Builder for InsertBuilder {
 
             buf.write_str(" SELECT 1 from DUAL");
             write_injection(&mut buf, &self.injection, INSERT_MARKER_AFTER_VALUES);
-            return self
-                .args
-                .borrow()
-                .compile_with_flavor(&buf.into_string(), flavor, initial_arg);
+            self.write_upsert(&mut buf, flavor)?;
+            return Ok(buf.into_string());
         }
 
         if let Some(t) = &self.table {
@@ -207,7 +368,8 @@ impl Builder for InsertBuilder {
             write_injection(&mut buf, &self.injection, INSERT_MARKER_AFTER_COLS);
         }
 
-        if flavor == Flavor::SQLServer && !self.returning.is_empty() {
+        let returning_style = flavor.dialect().returning_style();
+        if returning_style == ReturningStyle::Output && !self.returning.is_empty() {
             buf.write_str(" OUTPUT ");
             let prefixed: Vec<String> = self
                 .returning
@@ -231,21 +393,200 @@ impl Builder for InsertBuilder {
                 .map(|r| format!("({})", r.join(", ")))
                 .collect();
             buf.write_str(&rows.join(", "));
+
+            if let Some(alias) = &self.row_alias
+                && flavor.dialect().upsert_style() == UpsertStyle::OnDuplicateKey
+            {
+                buf.write_str(" AS ");
+                buf.write_str(alias);
+            }
         }
 
         write_injection(&mut buf, &self.injection, INSERT_MARKER_AFTER_VALUES);
+        self.write_upsert(&mut buf, flavor)?;
 
-        if (flavor == Flavor::PostgreSQL || flavor == Flavor::SQLite) && !self.returning.is_empty()
-        {
+        if returning_style == ReturningStyle::Returning && !self.returning.is_empty() {
             buf.write_leading("RETURNING");
             buf.write_str(" ");
             buf.write_str(&self.returning.join(", "));
             write_injection(&mut buf, &self.injection, INSERT_MARKER_AFTER_RETURNING);
         }
 
-        self.args
-            .borrow()
-            .compile_with_flavor(&buf.into_string(), flavor, initial_arg)
+        Ok(buf.into_string())
+    }
+
+    fn write_upsert(&self, buf: &mut StringBuilder, flavor: Flavor) -> Result<(), BuildError> {
+        let Some(action) = &self.conflict_action else {
+            return Ok(());
+        };
+        let unsupported = |feature| BuildError::Unsupported { flavor, feature };
+
+        match flavor.dialect().upsert_style() {
+            UpsertStyle::OnConflict => {
+                buf.write_leading("ON CONFLICT");
+                match &self.conflict_target {
+                    Some(ConflictTarget::Columns(cols)) => {
+                        buf.write_str(" (");
+                        buf.write_str(&cols.join(", "));
+                        buf.write_str(")");
+                    }
+                    Some(ConflictTarget::Constraint(name)) => {
+                        if flavor == Flavor::SQLite {
+                            return Err(unsupported("ON CONFLICT ON CONSTRAINT"));
+                        }
+                        buf.write_str(" ON CONSTRAINT ");
+                        buf.write_str(name);
+                    }
+                    None => {}
+                }
+                match action {
+                    ConflictAction::Nothing => buf.write_str(" DO NOTHING"),
+                    ConflictAction::Update(assignments) => {
+                        if self.conflict_target.is_none() {
+                            return Err(unsupported(
+                                "ON CONFLICT DO UPDATE without a conflict target",
+                            ));
+                        }
+                        buf.write_str(" DO UPDATE SET ");
+                        buf.write_str(&assignments.join(", "));
+                    }
+                }
+            }
+            UpsertStyle::OnDuplicateKey => {
+                if self.row_alias.is_some() && self.sb_holder.is_some() {
+                    return Err(unsupported("row alias on INSERT ... SELECT"));
+                }
+                buf.write_leading("ON DUPLICATE KEY UPDATE ");
+                match action {
+                    ConflictAction::Nothing => {
+                        let Some(first) = self.cols.first() else {
+                            return Err(unsupported("DO NOTHING without insert columns"));
+                        };
+                        buf.write_str(&format!("{first} = {first}"));
+                    }
+                    ConflictAction::Update(assignments) => buf.write_str(&assignments.join(", ")),
+                }
+            }
+            UpsertStyle::Unsupported => return Err(unsupported("upsert")),
+        }
+
+        write_injection(buf, &self.injection, INSERT_MARKER_AFTER_ON_CONFLICT);
+        Ok(())
+    }
+
+    fn build_rows(
+        &self,
+        rows: &[Vec<String>],
+        flavor: Flavor,
+        strict: bool,
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        let mut b = self.clone();
+        b.values = rows.to_vec();
+        if strict {
+            b.try_build_with_flavor(flavor, &[])
+        } else {
+            Ok(b.build_with_flavor(flavor, &[]))
+        }
+    }
+
+    fn batches(
+        &self,
+        flavor: Flavor,
+        max_params: Option<usize>,
+        strict: bool,
+    ) -> Result<Vec<(String, Vec<Arg>)>, BuildError> {
+        let limit = match (
+            max_params.or_else(|| flavor.dialect().max_params()),
+            &self.sb_holder,
+        ) {
+            (Some(limit), None) if self.values.len() > 1 => limit,
+            _ => return Ok(vec![self.build_rows(&self.values, flavor, strict)?]),
+        };
+
+        // Parameters outside VALUES (e.g. upsert assignments) repeat in every statement.
+        let fixed = self.build_rows(&[], flavor, strict)?.1.len();
+        let budget = limit.saturating_sub(fixed);
+
+        let mut batches = Vec::new();
+        let mut start = 0;
+        let mut used = 0;
+        for (i, row) in self.values.iter().enumerate() {
+            let n = self.with_args(flavor, |args| {
+                args.compile_with_flavor(&format!("({})", row.join(", ")), flavor, &[])
+                    .1
+                    .len()
+            });
+            if i > start && used + n > budget {
+                batches.push(self.build_rows(&self.values[start..i], flavor, strict)?);
+                start = i;
+                used = 0;
+            }
+            used += n;
+        }
+        batches.push(self.build_rows(&self.values[start..], flavor, strict)?);
+        Ok(batches)
+    }
+
+    /// Render a deferred row reference for `flavor`.
+    fn row_ref_sql(&self, flavor: Flavor, r: &RowRef) -> String {
+        let style = flavor.dialect().upsert_style();
+        match r {
+            RowRef::Excluded(col) => match style {
+                UpsertStyle::OnDuplicateKey => match &self.row_alias {
+                    Some(alias) => format!("{alias}.{col}"),
+                    None => format!("VALUES({col})"),
+                },
+                _ => format!("EXCLUDED.{col}"),
+            },
+            RowRef::Current(col) => match (style, &self.table) {
+                (UpsertStyle::OnConflict, Some(table)) => format!("{table}.{col}"),
+                _ => col.clone(),
+            },
+        }
+    }
+
+    /// Run `f` with the builder's args, row references resolved for `flavor`.
+    fn with_args<R>(&self, flavor: Flavor, f: impl FnOnce(&Args) -> R) -> R {
+        let refs = self.row_refs.snapshot();
+        if refs.is_empty() {
+            return f(&self.args.snapshot());
+        }
+
+        let mut args = self.args.snapshot();
+        for (ph, r) in refs.iter() {
+            args.replace(ph, raw(self.row_ref_sql(flavor, r)));
+        }
+        f(&args)
+    }
+}
+
+impl Builder for InsertBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        let format = unwrap_or_panic(self.build_format(flavor));
+        self.with_args(flavor, |args| {
+            args.compile_with_flavor(&format, flavor, initial_arg)
+        })
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        let format = self.build_format(flavor)?;
+        self.with_args(flavor, |args| {
+            args.try_compile_with_flavor(&format, flavor, initial_arg)
+        })
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        let format = self.build_format(flavor)?;
+        self.with_args(flavor, |a| a.compile_into(&format, flavor, sql, args))
     }
 
     fn flavor(&self) -> Flavor {
@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::modifiers::{Arg, Builder};
-    use crate::{Flavor, InsertBuilder, set_default_flavor_scoped};
+    use crate::{BuildError, Flavor, InsertBuilder, set_default_flavor_scoped};
     use crate::{insert_cols, returning_cols};
     use pretty_assertions::{assert_eq, assert_ne};
 
@@ -95,4 +95,239 @@ mod tests {
         let (sql_original, _) = ib.build();
         assert_ne!(sql_original, sql_after);
     }
+
+    #[test]
+    fn insert_upsert_matrix() {
+        let _g = set_default_flavor_scoped(Flavor::MySQL);
+        let mut ib = InsertBuilder::new();
+        ib.insert_into("users")
+            .cols(["id", "name", "visits"])
+            .values([Arg::from(1_i64), Arg::from("a"), Arg::from(1_i64)])
+            .on_conflict(["id"]);
+        ib.do_update([ib.assign_excluded("name"), ib.incr("visits")]);
+
+        let (sql, args) = ib.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, name, visits) VALUES ($1, $2, $3) ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name, visits = users.visits + 1"
+        );
+        assert_eq!(args.len(), 3);
+
+        let (sql, _) = ib.build_with_flavor(Flavor::SQLite, &[]);
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, name, visits) VALUES (?, ?, ?) ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name, visits = users.visits + 1"
+        );
+
+        let (sql, args) = ib.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, name, visits) VALUES (?, ?, ?) ON DUPLICATE KEY UPDATE name = VALUES(name), visits = visits + 1"
+        );
+        assert_eq!(args.len(), 3);
+
+        assert_eq!(
+            ib.try_build_with_flavor(Flavor::SQLServer, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::SQLServer,
+                feature: "upsert",
+            })
+        );
+        assert_eq!(
+            ib.try_build_with_flavor(Flavor::Oracle, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::Oracle,
+                feature: "upsert",
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "upsert is not supported by Oracle")]
+    fn insert_upsert_build_panics_without_upsert() {
+        let mut ib = InsertBuilder::new();
+        ib.insert_into("users")
+            .cols(["id", "visits"])
+            .values([1_i64, 1_i64])
+            .on_conflict(["id"]);
+        ib.do_update([ib.incr("visits")]);
+        ib.build_with_flavor(Flavor::Oracle, &[]);
+    }
+
+    #[test]
+    fn insert_upsert_arith_helpers() {
+        let _g = set_default_flavor_scoped(Flavor::PostgreSQL);
+        let mut ib = InsertBuilder::new();
+        ib.insert_into("stats")
+            .cols(["id", "hits", "score", "weight", "ratio"])
+            .values([1_i64, 1_i64, 1_i64, 1_i64, 1_i64])
+            .on_conflict(["id"]);
+        ib.do_update([
+            ib.decr("hits"),
+            ib.add("score", 5_i64),
+            ib.mul("weight", 2_i64),
+            ib.div("ratio", 3_i64),
+        ]);
+
+        let (sql, args) = ib.build();
+        assert_eq!(
+            sql,
+            "INSERT INTO stats (id, hits, score, weight, ratio) VALUES ($1, $2, $3, $4, $5) ON CONFLICT (id) DO UPDATE SET hits = stats.hits - 1, score = stats.score + $6, weight = stats.weight * $7, ratio = stats.ratio / $8"
+        );
+        assert_eq!(args.len(), 8);
+
+        let (sql, _) = ib.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "INSERT INTO stats (id, hits, score, weight, ratio) VALUES (?, ?, ?, ?, ?) ON DUPLICATE KEY UPDATE hits = hits - 1, score = score + ?, weight = weight * ?, ratio = ratio / ?"
+        );
+    }
+
+    #[test]
+    fn insert_upsert_row_alias_and_args() {
+        let _g = set_default_flavor_scoped(Flavor::MySQL);
+        let mut ib = InsertBuilder::new();
+        ib.insert_into("users")
+            .cols(["id", "visits"])
+            .values([1_i64, 1_i64])
+            .row_alias("new");
+        let new_visits = ib.excluded("visits");
+        ib.do_update([format!("visits = visits + {new_visits}")]);
+        let (sql, _) = ib.build();
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, visits) VALUES (?, ?) AS new ON DUPLICATE KEY UPDATE visits = visits + new.visits"
+        );
+
+        ib.on_conflict(["id"]).returning(["visits"]);
+        let (sql, _) = ib.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, visits) VALUES ($1, $2) ON CONFLICT (id) DO UPDATE SET visits = visits + EXCLUDED.visits RETURNING visits"
+        );
+
+        let mut ib = InsertBuilder::new();
+        ib.insert_into("users")
+            .cols(["id", "name"])
+            .values([Arg::from(1_i64), Arg::from("a")]);
+        ib.on_conflict(["id"]);
+        ib.do_update([ib.assign("name", "b")]);
+        let (sql, args) = ib.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, name) VALUES ($1, $2) ON CONFLICT (id) DO UPDATE SET name = $3"
+        );
+        assert_eq!(args, vec![Arg::from(1_i64), Arg::from("a"), Arg::from("b")]);
+    }
+
+    #[test]
+    fn insert_upsert_do_nothing_and_constraint() {
+        let _g = set_default_flavor_scoped(Flavor::MySQL);
+        let mut ib = InsertBuilder::new();
+        ib.insert_into("users")
+            .cols(["id", "name"])
+            .values([Arg::from(1_i64), Arg::from("a")])
+            .on_conflict_constraint("users_pkey")
+            .do_nothing();
+
+        let (sql, _) = ib.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, name) VALUES ($1, $2) ON CONFLICT ON CONSTRAINT users_pkey DO NOTHING"
+        );
+        let (sql, _) = ib.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, name) VALUES (?, ?) ON DUPLICATE KEY UPDATE id = id"
+        );
+        assert_eq!(
+            ib.try_build_with_flavor(Flavor::SQLite, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::SQLite,
+                feature: "ON CONFLICT ON CONSTRAINT",
+            })
+        );
+
+        let mut ib = InsertBuilder::new();
+        ib.insert_into("users").cols(["id"]).values([1_i64]);
+        ib.do_update([ib.incr("visits")]);
+        assert_eq!(
+            ib.try_build_with_flavor(Flavor::PostgreSQL, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::PostgreSQL,
+                feature: "ON CONFLICT DO UPDATE without a conflict target",
+            })
+        );
+    }
+
+    #[test]
+    fn insert_build_batches_by_param_limit() {
+        let _g = set_default_flavor_scoped(Flavor::PostgreSQL);
+        let mut ib = InsertBuilder::new();
+        ib.insert_into("users").cols(["id", "name"]);
+        for i in 1..=5_i64 {
+            ib.values([Arg::from(i), Arg::from(format!("u{i}"))]);
+        }
+        ib.returning(["id"]).sql("/* bulk */");
+
+        let batches = ib.build_batches(4);
+        let sqls: Vec<&str> = batches.iter().map(|(sql, _)| sql.as_str()).collect();
+        assert_eq!(
+            sqls,
+            vec![
+                "INSERT INTO users (id, name) VALUES ($1, $2), ($3, $4) RETURNING id /* bulk */",
+                "INSERT INTO users (id, name) VALUES ($1, $2), ($3, $4) RETURNING id /* bulk */",
+                "INSERT INTO users (id, name) VALUES ($1, $2) RETURNING id /* bulk */",
+            ]
+        );
+        assert_eq!(
+            batches[2].1,
+            vec![Arg::from(5_i64), Arg::from("u5".to_string())]
+        );
+
+        // Flavor default: PostgreSQL allows 65535 parameters, so everything fits.
+        assert_eq!(
+            ib.build_batches_with_flavor(Flavor::PostgreSQL, None),
+            vec![ib.build()]
+        );
+        // No known limit: a single statement.
+        assert_eq!(
+            ib.build_batches_with_flavor(Flavor::ClickHouse, None).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn insert_build_batches_keeps_upsert_params() {
+        let _g = set_default_flavor_scoped(Flavor::SQLServer);
+        let mut ib = InsertBuilder::new();
+        ib.insert_into("t").cols(["a"]);
+        for i in 0..2100_i64 {
+            ib.values([i]);
+        }
+        let batches = ib.build_batches_with_flavor(Flavor::SQLServer, None);
+        assert_eq!(batches.len(), 1);
+
+        ib.values([2100_i64]);
+        let batches = ib.build_batches_with_flavor(Flavor::SQLServer, None);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].1.len(), 2100);
+        assert_eq!(batches[1].0, "INSERT INTO t (a) VALUES (@p1)");
+
+        let mut ib = InsertBuilder::new();
+        ib.insert_into("t").cols(["a", "b"]).on_conflict(["a"]);
+        ib.do_update([ib.assign("b", 0_i64)]);
+        for i in 0..3_i64 {
+            ib.values([i, i]);
+        }
+        let batches = ib.build_batches_with_flavor(Flavor::PostgreSQL, Some(5));
+        let sqls: Vec<&str> = batches.iter().map(|(sql, _)| sql.as_str()).collect();
+        assert_eq!(
+            sqls,
+            vec![
+                "INSERT INTO t (a, b) VALUES ($1, $2), ($3, $4) ON CONFLICT (a) DO UPDATE SET b = $5",
+                "INSERT INTO t (a, b) VALUES ($1, $2) ON CONFLICT (a) DO UPDATE SET b = $3",
+            ]
+        );
+    }
 }
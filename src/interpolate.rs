@@ -2,32 +2,39 @@
 //!
 //! Safety warning: interpolation is less safe than prepared parameters; use only for drivers without parameter support.
 
+use crate::dialect::Dialect;
 use crate::flavor::{Flavor, InterpolateError};
 use crate::modifiers::Arg;
 use crate::value::{SqlDateTime, SqlValue};
 use time::format_description::FormatItem;
 
+type EncodeFn<'a> = &'a dyn Fn(&mut String, &SqlValue) -> Result<(), InterpolateError>;
+
 impl Flavor {
     pub fn interpolate(self, sql: &str, args: &[Arg]) -> Result<String, InterpolateError> {
-        match self {
-            Flavor::MySQL => mysql_like_interpolate(self, sql, args),
-            Flavor::SQLite => mysql_like_interpolate(self, sql, args),
-            Flavor::CQL => mysql_like_interpolate(self, sql, args),
-            Flavor::ClickHouse => mysql_like_interpolate(self, sql, args),
-            Flavor::Presto => mysql_like_interpolate(self, sql, args),
-            Flavor::Informix => mysql_like_interpolate(self, sql, args),
-            Flavor::Doris => mysql_like_interpolate(self, sql, args),
-            Flavor::PostgreSQL => postgresql_interpolate(sql, args),
-            Flavor::SQLServer => sqlserver_interpolate(sql, args),
-            Flavor::Oracle => oracle_interpolate(sql, args),
-        }
+        self.dialect().interpolate(sql, args)
+    }
+}
+
+/// Scan `sql` for placeholders of the given style and replace them using `encode`.
+pub(crate) fn interpolate_with(
+    style: Dialect,
+    sql: &str,
+    args: &[Arg],
+    encode: EncodeFn<'_>,
+) -> Result<String, InterpolateError> {
+    match style {
+        Dialect::QuestionMark => mysql_like_interpolate(sql, args, encode),
+        Dialect::DollarNumbered => postgresql_interpolate(sql, args, encode),
+        Dialect::AtPNumbered => sqlserver_interpolate(sql, args, encode),
+        Dialect::ColonNumbered => oracle_interpolate(sql, args, encode),
     }
 }
 
 fn mysql_like_interpolate(
-    flavor: Flavor,
     query: &str,
     args: &[Arg],
+    encode: EncodeFn<'_>,
 ) -> Result<String, InterpolateError> {
     let mut out = String::with_capacity(query.len() + args.len() * 20);
     let mut quote: Option<char> = None;
@@ -58,7 +65,7 @@ fn mysql_like_interpolate(
                 if arg_idx >= args.len() {
                     return Err(InterpolateError::MissingArgs);
                 }
-                encode_value(&mut out, &args[arg_idx], flavor)?;
+                encode_value(&mut out, &args[arg_idx], encode)?;
                 arg_idx += 1;
             }
             _ => out.push(c),
@@ -68,7 +75,11 @@ fn mysql_like_interpolate(
     Ok(out)
 }
 
-fn postgresql_interpolate(query: &str, args: &[Arg]) -> Result<String, InterpolateError> {
+fn postgresql_interpolate(
+    query: &str,
+    args: &[Arg],
+    encode: EncodeFn<'_>,
+) -> Result<String, InterpolateError> {
     let mut out = String::with_capacity(query.len() + args.len() * 20);
     let mut quote: Option<char> = None; // '\'' | '"' | '$'(dollar-quote)
     let mut escaping = false;
@@ -156,7 +167,7 @@ fn postgresql_interpolate(query: &str, args: &[Arg]) -> Result<String, Interpola
                     if n == 0 || n > args.len() {
                         return Err(InterpolateError::MissingArgs);
                     }
-                    encode_value(&mut out, &args[n - 1], Flavor::PostgreSQL)?;
+                    encode_value(&mut out, &args[n - 1], encode)?;
                     i = j;
                     continue;
                 }
@@ -188,7 +199,11 @@ fn postgresql_interpolate(query: &str, args: &[Arg]) -> Result<String, Interpola
     Ok(out)
 }
 
-fn sqlserver_interpolate(query: &str, args: &[Arg]) -> Result<String, InterpolateError> {
+fn sqlserver_interpolate(
+    query: &str,
+    args: &[Arg],
+    encode: EncodeFn<'_>,
+) -> Result<String, InterpolateError> {
     let mut out = String::with_capacity(query.len() + args.len() * 20);
     let mut quote: Option<char> = None;
     let mut escaping = false;
@@ -240,7 +255,7 @@ fn sqlserver_interpolate(query: &str, args: &[Arg]) -> Result<String, Interpolat
                         if n == 0 || n > args.len() {
                             return Err(InterpolateError::MissingArgs);
                         }
-                        encode_value(&mut out, &args[n - 1], Flavor::SQLServer)?;
+                        encode_value(&mut out, &args[n - 1], encode)?;
                         i = j;
                         continue;
                     }
@@ -258,7 +273,11 @@ fn sqlserver_interpolate(query: &str, args: &[Arg]) -> Result<String, Interpolat
     Ok(out)
 }
 
-fn oracle_interpolate(query: &str, args: &[Arg]) -> Result<String, InterpolateError> {
+fn oracle_interpolate(
+    query: &str,
+    args: &[Arg],
+    encode: EncodeFn<'_>,
+) -> Result<String, InterpolateError> {
     // Oracle-style interpolate: support :n and :tag: forms, skipping interpolation inside :tag: blocks.
     let mut out = String::with_capacity(query.len() + args.len() * 20);
     let mut quote: Option<char> = None; // '\'' | '"' | ':'(colon-quote)
@@ -345,7 +364,7 @@ fn oracle_interpolate(query: &str, args: &[Arg]) -> Result<String, InterpolateEr
                     if n == 0 || n > args.len() {
                         return Err(InterpolateError::MissingArgs);
                     }
-                    encode_value(&mut out, &args[n - 1], Flavor::Oracle)?;
+                    encode_value(&mut out, &args[n - 1], encode)?;
                     i = j;
                     continue;
                 }
@@ -377,18 +396,18 @@ fn oracle_interpolate(query: &str, args: &[Arg]) -> Result<String, InterpolateEr
     Ok(out)
 }
 
-fn encode_value(out: &mut String, arg: &Arg, flavor: Flavor) -> Result<(), InterpolateError> {
+fn encode_value(out: &mut String, arg: &Arg, encode: EncodeFn<'_>) -> Result<(), InterpolateError> {
     match arg {
-        Arg::Value(v) => encode_sql_value(out, v, flavor),
+        Arg::Value(v) => encode(out, v),
         Arg::Valuer(v) => {
             let vv = v.value()?;
-            encode_sql_value(out, &vv, flavor)
+            encode(out, &vv)
         }
         _ => Err(InterpolateError::UnsupportedArgs),
     }
 }
 
-fn encode_sql_value(
+pub(crate) fn encode_sql_value(
     out: &mut String,
     v: &SqlValue,
     flavor: Flavor,
@@ -512,7 +531,11 @@ fn encode_datetime(
     let dt = v.dt + time::Duration::nanoseconds(500);
 
     match flavor {
-        Flavor::MySQL | Flavor::ClickHouse | Flavor::Informix | Flavor::Doris => {
+        Flavor::MySQL
+        | Flavor::ClickHouse
+        | Flavor::Informix
+        | Flavor::Doris
+        | Flavor::Custom(_) => {
             // 'YYYY-MM-DD HH:MM:SS.ffffff'
             format_dt(
                 out,
@@ -577,3 +600,32 @@ fn format_dt(out: &mut String, dt: &time::OffsetDateTime, fmt: &[u8]) {
     let s = dt.format(&items).expect("format failed");
     out.push_str(&s);
 }
+
+/// ANSI literal encoding used by dialects that do not override `encode_literal`.
+pub(crate) fn encode_ansi_value(out: &mut String, v: &SqlValue) {
+    match v {
+        SqlValue::Null => out.push_str("NULL"),
+        SqlValue::Bool(b) => out.push_str(if *b { "TRUE" } else { "FALSE" }),
+        SqlValue::I64(n) => out.push_str(&n.to_string()),
+        SqlValue::U64(n) => out.push_str(&n.to_string()),
+        SqlValue::F64(n) => out.push_str(&n.to_string()),
+        SqlValue::String(s) => {
+            out.push('\'');
+            out.push_str(&s.replace('\'', "''"));
+            out.push('\'');
+        }
+        SqlValue::Bytes(b) => {
+            out.push_str("X'");
+            push_hex(out, b);
+            out.push('\'');
+        }
+        SqlValue::DateTime(dt) => {
+            let dt = dt.dt + time::Duration::nanoseconds(500);
+            format_dt(
+                out,
+                &dt,
+                b"'[year]-[month]-[day] [hour]:[minute]:[second].[subsecond digits:6]'",
+            );
+        }
+    }
+}
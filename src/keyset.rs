@@ -0,0 +1,349 @@
+//! Keyset: seek pagination (`WHERE (a, b) > ($1, $2) ORDER BY a, b LIMIT n`) and opaque cursors.
+
+use crate::args::Args;
+use crate::cond::Cond;
+use crate::flavor::Flavor;
+use crate::order_by::Direction;
+use crate::value::{SqlDateTime, SqlValue};
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CursorError {
+    #[error("cursor is not valid base64url")]
+    Encoding,
+    #[error("cursor is truncated")]
+    Truncated,
+    #[error("cursor has unknown value tag {0:#04x}")]
+    UnknownTag(u8),
+    #[error("cursor string is not valid UTF-8")]
+    Utf8,
+    #[error("cursor datetime is out of range")]
+    DateTime,
+    #[error("cursor has {got} values, keyset has {expected} keys")]
+    KeyCount { expected: usize, got: usize },
+    #[error("cursor value for key {0} is NULL")]
+    NullKey(usize),
+}
+
+/// Keyset: ordered sort keys, the last-seen values and the page size.
+///
+/// Keys must be NOT NULL columns: `col > NULL` matches no row, so NULL seek values are rejected.
+///
+/// ```ignore
+/// let ks = Keyset::new().desc("created_at").desc("id").after_cursor(&cursor)?.limit(20);
+/// sb.keyset(&ks);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Keyset {
+    keys: Vec<(String, Direction)>,
+    after: Vec<SqlValue>,
+    limit: Option<i64>,
+}
+
+impl Keyset {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn asc(mut self, col: impl Into<String>) -> Self {
+        self.keys.push((col.into(), Direction::Asc));
+        self
+    }
+
+    pub fn desc(mut self, col: impl Into<String>) -> Self {
+        self.keys.push((col.into(), Direction::Desc));
+        self
+    }
+
+    /// Seek past these values, one per key in key order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values differs from the number of keys or a value is NULL; use
+    /// [`Keyset::after_cursor`] for untrusted input.
+    pub fn after(mut self, values: Vec<SqlValue>) -> Self {
+        assert_eq!(
+            values.len(),
+            self.keys.len(),
+            "keyset values must match keys"
+        );
+        assert!(
+            !values.iter().any(|v| matches!(v, SqlValue::Null)),
+            "keyset values must not be NULL"
+        );
+        self.after = values;
+        self
+    }
+
+    /// Seek past the values of a cursor produced by [`encode_cursor`].
+    pub fn after_cursor(mut self, cursor: &str) -> Result<Self, CursorError> {
+        let values = decode_cursor(cursor)?;
+        if values.len() != self.keys.len() {
+            return Err(CursorError::KeyCount {
+                expected: self.keys.len(),
+                got: values.len(),
+            });
+        }
+        if let Some(i) = values.iter().position(|v| matches!(v, SqlValue::Null)) {
+            return Err(CursorError::NullKey(i));
+        }
+        self.after = values;
+        Ok(self)
+    }
+
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub(crate) fn keys(&self) -> &[(String, Direction)] {
+        &self.keys
+    }
+
+    pub(crate) fn page_size(&self) -> Option<i64> {
+        self.limit
+    }
+
+    /// Seek predicate bound to `cond`'s args; `None` on the first page.
+    pub(crate) fn predicate(&self, cond: &Cond) -> Option<String> {
+        if self.after.is_empty() {
+            return None;
+        }
+        let keys = self.keys.clone();
+        let values = self.after.clone();
+        Some(cond.expr_builder(move |flavor| {
+            let mut a = Args {
+                flavor,
+                ..Args::default()
+            };
+            let fmt = seek_predicate(&mut a, flavor, &keys, &values);
+            (a, fmt)
+        }))
+    }
+}
+
+fn supports_row_values(flavor: Flavor) -> bool {
+    matches!(
+        flavor,
+        Flavor::PostgreSQL | Flavor::MySQL | Flavor::SQLite | Flavor::ClickHouse
+    )
+}
+
+fn seek_op(dir: Direction) -> &'static str {
+    match dir {
+        Direction::Asc => ">",
+        Direction::Desc => "<",
+    }
+}
+
+fn seek_predicate(
+    a: &mut Args,
+    flavor: Flavor,
+    keys: &[(String, Direction)],
+    values: &[SqlValue],
+) -> String {
+    if let [(col, dir)] = keys {
+        return format!("{col} {} {}", seek_op(*dir), a.add(values[0].clone()));
+    }
+
+    let uniform = keys.iter().all(|(_, d)| *d == keys[0].1);
+    if uniform && supports_row_values(flavor) {
+        let cols: Vec<&str> = keys.iter().map(|(c, _)| c.as_str()).collect();
+        let vals: Vec<String> = values.iter().map(|v| a.add(v.clone())).collect();
+        return format!(
+            "({}) {} ({})",
+            cols.join(", "),
+            seek_op(keys[0].1),
+            vals.join(", ")
+        );
+    }
+
+    // (a > $1) OR (a = $2 AND b < $3) OR ...
+    let mut branches = Vec::with_capacity(keys.len());
+    for i in 0..keys.len() {
+        let mut terms: Vec<String> = keys[..i]
+            .iter()
+            .zip(values)
+            .map(|((col, _), v)| format!("{col} = {}", a.add(v.clone())))
+            .collect();
+        let (col, dir) = &keys[i];
+        terms.push(format!(
+            "{col} {} {}",
+            seek_op(*dir),
+            a.add(values[i].clone())
+        ));
+        branches.push(format!("({})", terms.join(" AND ")));
+    }
+    format!("({})", branches.join(" OR "))
+}
+
+const TAG_NULL: u8 = 0;
+const TAG_BOOL: u8 = 1;
+const TAG_I64: u8 = 2;
+const TAG_U64: u8 = 3;
+const TAG_F64: u8 = 4;
+const TAG_STRING: u8 = 5;
+const TAG_BYTES: u8 = 6;
+const TAG_DATETIME: u8 = 7;
+
+/// Encode last-seen values as an opaque, URL-safe cursor.
+pub fn encode_cursor(values: &[SqlValue]) -> String {
+    let mut buf = Vec::new();
+    for v in values {
+        match v {
+            SqlValue::Null => buf.push(TAG_NULL),
+            SqlValue::Bool(b) => {
+                buf.push(TAG_BOOL);
+                buf.push(u8::from(*b));
+            }
+            SqlValue::I64(n) => {
+                buf.push(TAG_I64);
+                buf.extend_from_slice(&n.to_le_bytes());
+            }
+            SqlValue::U64(n) => {
+                buf.push(TAG_U64);
+                buf.extend_from_slice(&n.to_le_bytes());
+            }
+            SqlValue::F64(f) => {
+                buf.push(TAG_F64);
+                buf.extend_from_slice(&f.to_bits().to_le_bytes());
+            }
+            SqlValue::String(s) => {
+                buf.push(TAG_STRING);
+                put_bytes(&mut buf, s.as_bytes());
+            }
+            SqlValue::Bytes(b) => {
+                buf.push(TAG_BYTES);
+                put_bytes(&mut buf, b);
+            }
+            SqlValue::DateTime(dt) => {
+                buf.push(TAG_DATETIME);
+                buf.extend_from_slice(&dt.dt.unix_timestamp_nanos().to_le_bytes());
+                buf.extend_from_slice(&dt.dt.offset().whole_seconds().to_le_bytes());
+                put_bytes(
+                    &mut buf,
+                    dt.tz_abbr.as_deref().unwrap_or_default().as_bytes(),
+                );
+            }
+        }
+    }
+    base64url_encode(&buf)
+}
+
+/// Decode a cursor produced by [`encode_cursor`].
+pub fn decode_cursor(cursor: &str) -> Result<Vec<SqlValue>, CursorError> {
+    let buf = base64url_decode(cursor)?;
+    let mut r = Reader { buf: &buf, pos: 0 };
+    let mut values = Vec::new();
+    while r.pos < buf.len() {
+        let v = match r.byte()? {
+            TAG_NULL => SqlValue::Null,
+            TAG_BOOL => SqlValue::Bool(r.byte()? != 0),
+            TAG_I64 => SqlValue::I64(i64::from_le_bytes(r.array()?)),
+            TAG_U64 => SqlValue::U64(u64::from_le_bytes(r.array()?)),
+            TAG_F64 => SqlValue::F64(f64::from_bits(u64::from_le_bytes(r.array()?))),
+            TAG_STRING => SqlValue::String(Cow::Owned(r.string()?)),
+            TAG_BYTES => SqlValue::Bytes(r.bytes()?.to_vec()),
+            TAG_DATETIME => {
+                let nanos = i128::from_le_bytes(r.array()?);
+                let offset = i32::from_le_bytes(r.array()?);
+                let abbr = r.string()?;
+                let offset = time::UtcOffset::from_whole_seconds(offset)
+                    .map_err(|_| CursorError::DateTime)?;
+                let dt = time::OffsetDateTime::from_unix_timestamp_nanos(nanos)
+                    .map_err(|_| CursorError::DateTime)?
+                    .to_offset(offset);
+                let mut dt = SqlDateTime::new(dt);
+                if !abbr.is_empty() {
+                    dt = dt.with_tz_abbr(abbr);
+                }
+                SqlValue::DateTime(dt)
+            }
+            tag => return Err(CursorError::UnknownTag(tag)),
+        };
+        values.push(v);
+    }
+    Ok(values)
+}
+
+fn put_bytes(buf: &mut Vec<u8>, b: &[u8]) {
+    buf.extend_from_slice(&(b.len() as u32).to_le_bytes());
+    buf.extend_from_slice(b);
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes_n(&mut self, n: usize) -> Result<&'a [u8], CursorError> {
+        let end = self.pos.checked_add(n).ok_or(CursorError::Truncated)?;
+        let out = self.buf.get(self.pos..end).ok_or(CursorError::Truncated)?;
+        self.pos = end;
+        Ok(out)
+    }
+
+    fn byte(&mut self) -> Result<u8, CursorError> {
+        Ok(self.bytes_n(1)?[0])
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], CursorError> {
+        let mut out = [0; N];
+        out.copy_from_slice(self.bytes_n(N)?);
+        Ok(out)
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], CursorError> {
+        let len = u32::from_le_bytes(self.array()?) as usize;
+        self.bytes_n(len)
+    }
+
+    fn string(&mut self) -> Result<String, CursorError> {
+        String::from_utf8(self.bytes()?.to_vec()).map_err(|_| CursorError::Utf8)
+    }
+}
+
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+fn base64url_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (u32::from(*b) << (16 - 8 * i)));
+        for i in 0..=chunk.len() {
+            out.push(BASE64URL[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
+fn base64url_decode(s: &str) -> Result<Vec<u8>, CursorError> {
+    let sextets = s
+        .bytes()
+        .map(|c| {
+            BASE64URL
+                .iter()
+                .position(|&b| b == c)
+                .map(|p| p as u32)
+                .ok_or(CursorError::Encoding)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut out = Vec::with_capacity(sextets.len() * 3 / 4);
+    for chunk in sextets.chunks(4) {
+        if chunk.len() == 1 {
+            return Err(CursorError::Encoding);
+        }
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, s)| n | (s << (18 - 6 * i)));
+        for i in 0..chunk.len() - 1 {
+            out.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Ok(out)
+}
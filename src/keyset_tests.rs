@@ -0,0 +1,228 @@
+#[cfg(test)]
+mod tests {
+    use crate::args::BuildError;
+    use crate::flavor::Flavor;
+    use crate::keyset::{CursorError, Keyset, decode_cursor, encode_cursor};
+    use crate::modifiers::{Arg, Builder};
+    use crate::select::SelectBuilder;
+    use crate::value::{SqlDateTime, SqlValue};
+    use pretty_assertions::assert_eq;
+    use time::macros::datetime;
+
+    #[test]
+    fn keyset_first_page() {
+        let ks = Keyset::new().desc("created_at").desc("id").limit(20);
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id", "created_at"]).from(vec!["events"]);
+        sb.where_(vec![sb.equal("tenant", 7_i64)]);
+        sb.keyset(&ks);
+        let (sql, args) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, created_at FROM events WHERE tenant = $1 \
+             ORDER BY created_at DESC, id DESC LIMIT $2"
+        );
+        assert_eq!(args, vec![Arg::from(7_i64), Arg::from(20_i64)]);
+    }
+
+    #[test]
+    fn keyset_row_value_comparison() {
+        let ks = Keyset::new()
+            .asc("created_at")
+            .asc("id")
+            .after(vec![SqlValue::from("2024-01-01"), SqlValue::from(42_i64)])
+            .limit(10);
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id", "created_at"]).from(vec!["events"]);
+        sb.where_(vec![sb.equal("tenant", 7_i64)]);
+        sb.keyset(&ks);
+
+        let (sql, args) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, created_at FROM events WHERE tenant = $1 AND (created_at, id) > ($2, $3) \
+             ORDER BY created_at ASC, id ASC LIMIT $4"
+        );
+        assert_eq!(
+            args,
+            vec![
+                Arg::from(7_i64),
+                Arg::from("2024-01-01"),
+                Arg::from(42_i64),
+                Arg::from(10_i64)
+            ]
+        );
+
+        // No row values on SQL Server: expanded form.
+        let (sql, args) = sb.build_with_flavor(Flavor::SQLServer, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, created_at FROM events WHERE tenant = @p1 \
+             AND ((created_at > @p2) OR (created_at = @p3 AND id > @p4)) \
+             ORDER BY created_at ASC, id ASC OFFSET 0 ROWS FETCH NEXT @p5 ROWS ONLY"
+        );
+        assert_eq!(args.len(), 5);
+    }
+
+    #[test]
+    fn keyset_mixed_directions() {
+        let ks = Keyset::new()
+            .desc("score")
+            .asc("id")
+            .after(vec![SqlValue::from(90_i64), SqlValue::from(5_i64)]);
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id", "created_at"]).from(vec!["events"]);
+        sb.where_(vec![sb.equal("tenant", 7_i64)]);
+        sb.keyset(&ks);
+        let (sql, args) = sb.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, created_at FROM events WHERE tenant = ? \
+             AND ((score < ?) OR (score = ? AND id > ?)) ORDER BY score DESC, id ASC"
+        );
+        assert_eq!(
+            args,
+            vec![
+                Arg::from(7_i64),
+                Arg::from(90_i64),
+                Arg::from(90_i64),
+                Arg::from(5_i64)
+            ]
+        );
+    }
+
+    #[test]
+    fn keyset_single_key() {
+        let ks = Keyset::new()
+            .desc("id")
+            .after(vec![SqlValue::from(100_i64)]);
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id", "created_at"]).from(vec!["events"]);
+        sb.where_(vec![sb.equal("tenant", 7_i64)]);
+        sb.keyset(&ks);
+        let (sql, _) = sb.build_with_flavor(Flavor::Oracle, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, created_at FROM events WHERE tenant = :1 AND id < :2 ORDER BY id DESC"
+        );
+    }
+
+    #[test]
+    fn keyset_cursor_round_trip() {
+        let values = vec![
+            SqlValue::Null,
+            SqlValue::from(true),
+            SqlValue::from(-42_i64),
+            SqlValue::U64(u64::MAX),
+            SqlValue::from(1.5_f64),
+            SqlValue::from("héllo"),
+            SqlValue::Bytes(vec![0, 255, 7]),
+            SqlValue::DateTime(
+                SqlDateTime::new(datetime!(2024-05-06 07:08:09.123456789 +02:00))
+                    .with_tz_abbr("CEST"),
+            ),
+        ];
+        let cursor = encode_cursor(&values);
+        assert!(
+            cursor
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+        );
+        assert_eq!(decode_cursor(&cursor).unwrap(), values);
+        assert_eq!(decode_cursor("").unwrap(), Vec::<SqlValue>::new());
+    }
+
+    #[test]
+    fn keyset_cursor_errors() {
+        assert_eq!(decode_cursor("not+base64"), Err(CursorError::Encoding));
+        let cursor = encode_cursor(&[SqlValue::from(1_i64)]);
+        assert_eq!(
+            decode_cursor(&cursor[..cursor.len() - 2]),
+            Err(CursorError::Truncated)
+        );
+        assert_eq!(decode_cursor("_w"), Err(CursorError::UnknownTag(0xff)));
+
+        let err = Keyset::new()
+            .asc("a")
+            .asc("b")
+            .after_cursor(&cursor)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            CursorError::KeyCount {
+                expected: 2,
+                got: 1
+            }
+        );
+
+        let cursor = encode_cursor(&[SqlValue::from(3_i64), SqlValue::from("x")]);
+        let ks = Keyset::new()
+            .asc("a")
+            .asc("b")
+            .after_cursor(&cursor)
+            .unwrap();
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["a", "b"]).from(vec!["t"]).keyset(&ks);
+        let (sql, _) = sb.build_with_flavor(Flavor::SQLite, &[]);
+        assert_eq!(
+            sql,
+            "SELECT a, b FROM t WHERE (a, b) > (?, ?) ORDER BY a ASC, b ASC"
+        );
+
+        let cursor = encode_cursor(&[SqlValue::from(3_i64), SqlValue::Null]);
+        assert_eq!(
+            Keyset::new().asc("a").asc("b").after_cursor(&cursor),
+            Err(CursorError::NullKey(1))
+        );
+    }
+
+    #[test]
+    fn keyset_keys_lead_existing_order_by() {
+        let ks = Keyset::new()
+            .desc("id")
+            .after(vec![SqlValue::from(100_i64)])
+            .limit(5);
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id"])
+            .from(vec!["events"])
+            .order_by_asc("kind");
+        sb.keyset(&ks);
+        let (sql, _) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id FROM events WHERE id < $1 ORDER BY id DESC, kind ASC LIMIT $2"
+        );
+    }
+
+    #[test]
+    fn keyset_keeps_legacy_direction_on_tie_breakers() {
+        let ks = Keyset::new().asc("id").after(vec![SqlValue::from(7_i64)]);
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id"])
+            .from(vec!["events"])
+            .order_by(vec!["b"])
+            .desc();
+        sb.keyset(&ks);
+        let (sql, _) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id FROM events WHERE id > $1 ORDER BY id ASC, b DESC"
+        );
+
+        // With no terms of its own the legacy keyword has nothing left to sort.
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id"]).from(vec!["events"]).desc();
+        sb.keyset(&ks);
+        assert_eq!(
+            sb.try_build_with_flavor(Flavor::PostgreSQL, &[])
+                .unwrap_err(),
+            BuildError::MixedOrderBy
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "keyset values must not be NULL")]
+    fn keyset_after_rejects_null() {
+        let _ = Keyset::new().asc("a").after(vec![SqlValue::Null]);
+    }
+}
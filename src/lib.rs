@@ -1,17 +1,22 @@
 //! halo-sqlbuilder: composable SQL builder and argument collector.
 
+pub mod alter_table;
+#[cfg(test)]
+mod alter_table_tests;
 pub mod args;
 #[cfg(test)]
 mod args_tests;
 pub mod builder;
 #[cfg(test)]
 mod builder_tests;
+pub mod column_def;
 pub mod cond;
 #[cfg(test)]
 mod cond_tests;
 #[cfg(test)]
 mod cond_where_tests;
 pub mod condition;
+pub mod create_index;
 pub mod create_table;
 #[cfg(test)]
 mod create_table_tests;
@@ -19,10 +24,14 @@ pub mod cte;
 pub mod cte_query;
 #[cfg(test)]
 mod cte_tests;
+#[cfg(test)]
+mod ddl_tests;
 pub mod delete;
 #[cfg(test)]
 mod delete_more_tests;
 pub mod dialect;
+pub mod drop_index;
+pub mod drop_table;
 pub mod expr;
 pub mod field_mapper;
 pub mod flavor;
@@ -35,23 +44,45 @@ mod insert_tests;
 pub mod interpolate;
 #[cfg(test)]
 mod interpolate_tests;
+pub mod keyset;
+#[cfg(test)]
+mod keyset_tests;
 pub mod macros;
 pub use crate::macros::*;
 #[cfg(test)]
 mod macros_tests;
+pub mod merge;
+#[cfg(test)]
+mod merge_tests;
 pub mod modifiers;
 #[cfg(test)]
 mod modifiers_more_tests;
+pub mod order_by;
+#[cfg(test)]
+mod order_by_tests;
+pub mod prepared;
+#[cfg(test)]
+mod prepared_tests;
+pub mod row_lock;
+#[cfg(test)]
+mod row_lock_tests;
 pub mod scan;
 pub mod select;
 #[cfg(test)]
 mod select_more_tests;
 #[cfg(test)]
 mod select_tests;
+pub mod shared;
+#[cfg(test)]
+mod shared_tests;
+pub mod sql_dialect;
+#[cfg(test)]
+mod sql_dialect_tests;
 pub mod string_builder;
 pub mod structs;
 #[cfg(test)]
 mod structs_tests;
+pub mod truncate_table;
 pub mod union;
 #[cfg(test)]
 mod union_cte_create_table_tests;
@@ -65,9 +96,12 @@ pub mod valuer;
 pub mod where_clause;
 #[cfg(test)]
 mod where_clause_tests;
+pub mod window;
 
-pub use crate::args::{Args, CompileError};
+pub use crate::alter_table::AlterTableBuilder;
+pub use crate::args::{Args, BuildError, CompileError, ParsedFormat};
 pub use crate::builder::{build, build_named, buildf, with_flavor};
+pub use crate::column_def::{ColumnDef, ColumnType, TableConstraint};
 pub use crate::cond::Cond;
 pub use crate::condition::{
     Chain, ChainOptions, Condition, ConditionValue, JoinCondition, Operator, UpdateField,
@@ -75,11 +109,14 @@ pub use crate::condition::{
     build_delete_with_flavor, build_select, build_select_with_flavor, build_update,
     build_update_with_flavor, quote_with_flavor, to_field_slice, unquote,
 };
+pub use crate::create_index::CreateIndexBuilder;
 pub use crate::create_table::CreateTableBuilder;
 pub use crate::cte::{CTEBuilder, with, with_recursive};
 pub use crate::cte_query::CTEQueryBuilder;
 pub use crate::delete::DeleteBuilder;
 pub use crate::dialect::Dialect;
+pub use crate::drop_index::DropIndexBuilder;
+pub use crate::drop_table::DropTableBuilder;
 pub use crate::expr::Expr;
 pub use crate::field_mapper::{
     FieldMapperFunc, default_field_mapper, identity_mapper, set_default_field_mapper,
@@ -89,18 +126,30 @@ pub use crate::flavor::{
     Flavor, InterpolateError, default_flavor, set_default_flavor, set_default_flavor_scoped,
 };
 pub use crate::insert::InsertBuilder;
+pub use crate::keyset::{CursorError, Keyset, decode_cursor, encode_cursor};
+pub use crate::merge::{MergeBuilder, MergeWhen};
 pub use crate::modifiers::{
     FlattenIntoArgs, Raw, RcBuilder, SqlNamedArg, escape, escape_all, flatten, list, named, raw,
     rc_builder, tuple, tuple_names,
 };
+pub use crate::order_by::{Direction, Nulls, OrderTerm};
+pub use crate::prepared::{BindError, PreparedTemplate};
+pub use crate::row_lock::{LockStrength, LockWait, RowLock};
 pub use crate::scan::{ScanCell, ScanError, scan_tokens};
 pub use crate::select::{JoinOption, SelectBuilder};
+pub use crate::shared::{Shared, shared};
+pub use crate::sql_dialect::{
+    DeleteJoinStyle, DialectId, Pagination, ReturningStyle, SqlDialect, UpdateJoinStyle,
+    UpsertStyle, register_dialect,
+};
 pub use crate::structs::{FieldMeta, FieldOpt, SqlStruct, Struct};
-pub use crate::union::UnionBuilder;
+pub use crate::truncate_table::TruncateTableBuilder;
+pub use crate::union::{SetOp, UnionBuilder};
 pub use crate::update::UpdateBuilder;
 pub use crate::value::SqlValue;
 pub use crate::valuer::{SqlValuer, ValuerError};
 pub use crate::where_clause::{WhereClause, WhereClauseBuilder, WhereClauseRef, copy_where_clause};
+pub use crate::window::{FrameBound, FrameUnit, WindowSpec};
 
 /// Preferred namespace: `use halo_space::sqlbuilder::{...}`.
 pub mod sqlbuilder {
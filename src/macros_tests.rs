@@ -1,7 +1,6 @@
 #[cfg(test)]
 mod tests {
     use crate::SelectBuilder;
-    use crate::modifiers::Builder;
 
     #[test]
     fn select_macro_variadic_builds_sql() {
@@ -0,0 +1,527 @@
+//! MergeBuilder: build MERGE statements (SQL Server, Oracle, PostgreSQL 15+).
+
+use crate::args::{Args, BuildError, unwrap_or_panic};
+use crate::cond::{ArgsRef, Cond};
+use crate::flavor::Flavor;
+use crate::injection::{Injection, InjectionMarker};
+use crate::macros::{IntoStrings, collect_into_strings};
+use crate::modifiers::{Arg, Builder, arith_assignment, escape, escape_all};
+use crate::shared::Shared;
+use crate::sql_dialect::ReturningStyle;
+use crate::string_builder::StringBuilder;
+use std::ops::Deref;
+
+const MERGE_MARKER_INIT: InjectionMarker = 0;
+const MERGE_MARKER_AFTER_MERGE_INTO: InjectionMarker = 1;
+const MERGE_MARKER_AFTER_USING: InjectionMarker = 2;
+const MERGE_MARKER_AFTER_ON: InjectionMarker = 3;
+const MERGE_MARKER_AFTER_WHEN: InjectionMarker = 4;
+const MERGE_MARKER_AFTER_RETURNING: InjectionMarker = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MergeMatch {
+    Matched,
+    NotMatched,
+    NotMatchedBySource,
+}
+
+#[derive(Debug, Clone)]
+enum MergeAction {
+    Update(Vec<String>),
+    Delete,
+    Insert {
+        cols: Vec<String>,
+        values: Vec<String>,
+    },
+    DoNothing,
+}
+
+/// MergeWhen: one `WHEN [NOT] MATCHED [AND cond] THEN ...` branch of a MERGE.
+#[derive(Debug, Clone)]
+pub struct MergeWhen {
+    kind: MergeMatch,
+    and_expr: Vec<String>,
+    action: MergeAction,
+}
+
+impl MergeWhen {
+    fn new(kind: MergeMatch) -> Self {
+        Self {
+            kind,
+            and_expr: Vec::new(),
+            action: MergeAction::DoNothing,
+        }
+    }
+
+    /// `WHEN MATCHED`.
+    pub fn matched() -> Self {
+        Self::new(MergeMatch::Matched)
+    }
+
+    /// `WHEN NOT MATCHED` (no row in the target).
+    pub fn not_matched() -> Self {
+        Self::new(MergeMatch::NotMatched)
+    }
+
+    /// `WHEN NOT MATCHED BY SOURCE` (SQL Server, PostgreSQL 17+).
+    pub fn not_matched_by_source() -> Self {
+        Self::new(MergeMatch::NotMatchedBySource)
+    }
+
+    /// Extra branch condition, joined with AND.
+    pub fn and<T>(mut self, and_expr: T) -> Self
+    where
+        T: IntoStrings,
+    {
+        self.and_expr.extend(collect_into_strings(and_expr));
+        self
+    }
+
+    pub fn then_update<T>(mut self, assignments: T) -> Self
+    where
+        T: IntoStrings,
+    {
+        self.action = MergeAction::Update(collect_into_strings(assignments));
+        self
+    }
+
+    pub fn then_delete(mut self) -> Self {
+        self.action = MergeAction::Delete;
+        self
+    }
+
+    /// `THEN INSERT (cols) VALUES (values)`; values are SQL expressions, usually source columns.
+    pub fn then_insert<C, V>(mut self, cols: C, values: V) -> Self
+    where
+        C: IntoStrings,
+        V: IntoStrings,
+    {
+        self.action = MergeAction::Insert {
+            cols: escape_all(collect_into_strings(cols)),
+            values: collect_into_strings(values),
+        };
+        self
+    }
+
+    /// `THEN DO NOTHING` (PostgreSQL only).
+    pub fn then_do_nothing(mut self) -> Self {
+        self.action = MergeAction::DoNothing;
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct MergeBuilder {
+    args: ArgsRef,
+    cond: Cond,
+
+    target: Option<String>,
+    target_alias: Option<String>,
+    source: Option<String>,
+    source_is_builder: bool,
+    source_alias: Option<String>,
+    on: Vec<String>,
+    whens: Vec<MergeWhen>,
+    returning: Vec<String>,
+
+    injection: Injection,
+    marker: InjectionMarker,
+}
+
+impl Deref for MergeBuilder {
+    type Target = Cond;
+    fn deref(&self) -> &Self::Target {
+        &self.cond
+    }
+}
+
+impl Default for MergeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for MergeBuilder {
+    fn clone(&self) -> Self {
+        self.clone_builder()
+    }
+}
+
+impl MergeBuilder {
+    pub fn new() -> Self {
+        let args = Shared::new(Args::default());
+        let cond = Cond::with_args(args.clone());
+        Self {
+            args,
+            cond,
+            target: None,
+            target_alias: None,
+            source: None,
+            source_is_builder: false,
+            source_alias: None,
+            on: Vec::new(),
+            whens: Vec::new(),
+            returning: Vec::new(),
+            injection: Injection::new(),
+            marker: MERGE_MARKER_INIT,
+        }
+    }
+
+    pub fn set_flavor(&mut self, flavor: Flavor) -> Flavor {
+        let mut a = self.args.borrow_mut();
+        let old = a.flavor;
+        a.flavor = flavor;
+        old
+    }
+
+    pub fn flavor(&self) -> Flavor {
+        self.args.borrow().flavor
+    }
+
+    pub fn clone_builder(&self) -> Self {
+        let args = Shared::new(self.args.snapshot());
+        let cond = Cond::with_args(args.clone());
+        Self {
+            args,
+            cond,
+            target: self.target.clone(),
+            target_alias: self.target_alias.clone(),
+            source: self.source.clone(),
+            source_is_builder: self.source_is_builder,
+            source_alias: self.source_alias.clone(),
+            on: self.on.clone(),
+            whens: self.whens.clone(),
+            returning: self.returning.clone(),
+            injection: self.injection.clone(),
+            marker: self.marker,
+        }
+    }
+
+    pub fn build(&self) -> (String, Vec<Arg>) {
+        Builder::build(self)
+    }
+
+    fn var(&self, v: impl Into<Arg>) -> String {
+        self.args.borrow_mut().add(v)
+    }
+
+    pub fn merge_into(&mut self, table: &str) -> &mut Self {
+        self.target = Some(escape(table));
+        self.marker = MERGE_MARKER_AFTER_MERGE_INTO;
+        self
+    }
+
+    /// Alias of the target table.
+    pub fn target_alias(&mut self, alias: &str) -> &mut Self {
+        self.target_alias = Some(escape(alias));
+        self
+    }
+
+    /// Use a table as the source.
+    pub fn using(&mut self, table: &str) -> &mut Self {
+        self.source = Some(escape(table));
+        self.source_is_builder = false;
+        self.marker = MERGE_MARKER_AFTER_USING;
+        self
+    }
+
+    /// Use a nested builder (typically a `SelectBuilder`) as the source.
+    pub fn using_builder(&mut self, builder: impl Builder + 'static) -> &mut Self {
+        self.source = Some(self.var(Arg::Builder(Box::new(builder))));
+        self.source_is_builder = true;
+        self.marker = MERGE_MARKER_AFTER_USING;
+        self
+    }
+
+    /// Alias of the source table or subquery.
+    pub fn source_alias(&mut self, alias: &str) -> &mut Self {
+        self.source_alias = Some(escape(alias));
+        self
+    }
+
+    /// Join condition between target and source, joined with AND.
+    pub fn on<T>(&mut self, on_expr: T) -> &mut Self
+    where
+        T: IntoStrings,
+    {
+        self.on.extend(collect_into_strings(on_expr));
+        self.marker = MERGE_MARKER_AFTER_ON;
+        self
+    }
+
+    /// Append a WHEN branch; branches are rendered in order.
+    pub fn when(&mut self, branch: MergeWhen) -> &mut Self {
+        self.whens.push(branch);
+        self.marker = MERGE_MARKER_AFTER_WHEN;
+        self
+    }
+
+    /// `RETURNING cols` (PostgreSQL 17+) or `OUTPUT cols` (SQL Server); Oracle reports
+    /// `BuildError::Unsupported`.
+    pub fn returning<T>(&mut self, cols: T) -> &mut Self
+    where
+        T: IntoStrings,
+    {
+        self.returning = collect_into_strings(cols);
+        self.marker = MERGE_MARKER_AFTER_RETURNING;
+        self
+    }
+
+    pub fn assign(&self, field: &str, value: impl Into<Arg>) -> String {
+        format!("{} = {}", escape(field), self.var(value))
+    }
+
+    /// `field = t.field + 1`; the existing value is qualified with the target alias (or table)
+    /// set so far, since the source usually has a column of the same name.
+    pub fn incr(&self, field: &str) -> String {
+        self.arith(field, '+', "1")
+    }
+
+    pub fn decr(&self, field: &str) -> String {
+        self.arith(field, '-', "1")
+    }
+
+    pub fn add(&self, field: &str, value: impl Into<Arg>) -> String {
+        self.arith(field, '+', &self.var(value))
+    }
+
+    pub fn sub(&self, field: &str, value: impl Into<Arg>) -> String {
+        self.arith(field, '-', &self.var(value))
+    }
+
+    fn arith(&self, field: &str, op: char, rhs: &str) -> String {
+        let f = escape(field);
+        let current = match self.target_alias.as_ref().or(self.target.as_ref()) {
+            Some(t) => format!("{t}.{f}"),
+            None => f.clone(),
+        };
+        arith_assignment(&f, &current, op, rhs)
+    }
+
+    pub fn sql(&mut self, sql: impl Into<String>) -> &mut Self {
+        self.injection.sql(self.marker, sql);
+        self
+    }
+}
+
+impl MergeBuilder {
+    fn build_format(&self, flavor: Flavor) -> Result<String, BuildError> {
+        let mut buf = StringBuilder::new();
+        write_injection(&mut buf, &self.injection, MERGE_MARKER_INIT);
+
+        if !matches!(
+            flavor,
+            Flavor::PostgreSQL | Flavor::SQLServer | Flavor::Oracle
+        ) {
+            return Err(BuildError::Unsupported {
+                flavor,
+                feature: "MERGE",
+            });
+        }
+
+        // Oracle does not accept AS before table aliases.
+        let alias_kw = if flavor == Flavor::Oracle {
+            " "
+        } else {
+            " AS "
+        };
+
+        if let Some(t) = &self.target {
+            buf.write_leading("MERGE INTO ");
+            buf.write_str(t);
+            if let Some(alias) = &self.target_alias {
+                buf.write_str(alias_kw);
+                buf.write_str(alias);
+            }
+        }
+        write_injection(&mut buf, &self.injection, MERGE_MARKER_AFTER_MERGE_INTO);
+
+        if let Some(src) = &self.source {
+            buf.write_leading("USING ");
+            if self.source_is_builder {
+                buf.write_str("(");
+                buf.write_str(src);
+                buf.write_str(")");
+            } else {
+                buf.write_str(src);
+            }
+            if let Some(alias) = &self.source_alias {
+                buf.write_str(alias_kw);
+                buf.write_str(alias);
+            }
+            write_injection(&mut buf, &self.injection, MERGE_MARKER_AFTER_USING);
+        }
+
+        if !self.on.is_empty() {
+            buf.write_leading("ON ");
+            if flavor == Flavor::Oracle {
+                buf.write_str("(");
+                buf.write_str(&self.on.join(" AND "));
+                buf.write_str(")");
+            } else {
+                buf.write_str(&self.on.join(" AND "));
+            }
+            write_injection(&mut buf, &self.injection, MERGE_MARKER_AFTER_ON);
+        }
+
+        for when in &self.whens {
+            write_when(&mut buf, when, flavor)?;
+        }
+        if !self.whens.is_empty() {
+            write_injection(&mut buf, &self.injection, MERGE_MARKER_AFTER_WHEN);
+        }
+
+        if !self.returning.is_empty() {
+            match flavor.dialect().returning_style() {
+                ReturningStyle::Output => {
+                    buf.write_leading("OUTPUT ");
+                    let cols: Vec<String> =
+                        self.returning.iter().map(|c| output_column(c)).collect();
+                    buf.write_str(&cols.join(", "));
+                    write_injection(&mut buf, &self.injection, MERGE_MARKER_AFTER_RETURNING);
+                }
+                ReturningStyle::Returning => {
+                    buf.write_leading("RETURNING ");
+                    buf.write_str(&self.returning.join(", "));
+                    write_injection(&mut buf, &self.injection, MERGE_MARKER_AFTER_RETURNING);
+                }
+                ReturningStyle::Unsupported => {
+                    return Err(BuildError::Unsupported {
+                        flavor,
+                        feature: "MERGE ... RETURNING",
+                    });
+                }
+            }
+        }
+
+        // SQL Server requires MERGE to be terminated by a semicolon.
+        if flavor == Flavor::SQLServer {
+            buf.write_str(";");
+        }
+
+        Ok(buf.into_string())
+    }
+}
+
+fn write_when(buf: &mut StringBuilder, when: &MergeWhen, flavor: Flavor) -> Result<(), BuildError> {
+    let unsupported = |feature| BuildError::Unsupported { flavor, feature };
+    let oracle = flavor == Flavor::Oracle;
+
+    let kind = match when.kind {
+        MergeMatch::Matched => "WHEN MATCHED",
+        MergeMatch::NotMatched => "WHEN NOT MATCHED",
+        MergeMatch::NotMatchedBySource => {
+            if oracle {
+                return Err(unsupported("WHEN NOT MATCHED BY SOURCE"));
+            }
+            "WHEN NOT MATCHED BY SOURCE"
+        }
+    };
+
+    let action = match &when.action {
+        MergeAction::Update(assignments) => {
+            if when.kind == MergeMatch::NotMatched {
+                return Err(unsupported("UPDATE in WHEN NOT MATCHED"));
+            }
+            format!("UPDATE SET {}", assignments.join(", "))
+        }
+        MergeAction::Delete => {
+            if when.kind == MergeMatch::NotMatched {
+                return Err(unsupported("DELETE in WHEN NOT MATCHED"));
+            }
+            if oracle {
+                return Err(unsupported("MERGE ... THEN DELETE"));
+            }
+            "DELETE".to_string()
+        }
+        MergeAction::Insert { cols, values } => {
+            if when.kind != MergeMatch::NotMatched {
+                return Err(unsupported("INSERT in WHEN MATCHED"));
+            }
+            let mut s = String::from("INSERT");
+            if !cols.is_empty() {
+                s.push_str(" (");
+                s.push_str(&cols.join(", "));
+                s.push(')');
+            }
+            s.push_str(" VALUES (");
+            s.push_str(&values.join(", "));
+            s.push(')');
+            s
+        }
+        MergeAction::DoNothing => {
+            if flavor != Flavor::PostgreSQL {
+                return Err(unsupported("MERGE ... DO NOTHING"));
+            }
+            "DO NOTHING".to_string()
+        }
+    };
+
+    buf.write_leading(kind);
+    // Oracle puts branch conditions in a WHERE after the action.
+    if !when.and_expr.is_empty() && !oracle {
+        buf.write_str(" AND ");
+        buf.write_str(&when.and_expr.join(" AND "));
+    }
+    buf.write_str(" THEN ");
+    buf.write_str(&action);
+    if !when.and_expr.is_empty() && oracle {
+        buf.write_str(" WHERE ");
+        buf.write_str(&when.and_expr.join(" AND "));
+    }
+    Ok(())
+}
+
+/// SQL Server OUTPUT column: unqualified columns default to `INSERTED.`.
+fn output_column(col: &str) -> String {
+    if col.contains('.') || col.starts_with("$$") {
+        col.to_string()
+    } else {
+        format!("INSERTED.{col}")
+    }
+}
+
+impl Builder for MergeBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        let format = unwrap_or_panic(self.build_format(flavor));
+        self.args
+            .snapshot()
+            .compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        let format = self.build_format(flavor)?;
+        self.args
+            .snapshot()
+            .try_compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        let format = self.build_format(flavor)?;
+        self.args
+            .snapshot()
+            .compile_into(&format, flavor, sql, args)
+    }
+
+    fn flavor(&self) -> Flavor {
+        self.flavor()
+    }
+}
+
+fn write_injection(buf: &mut StringBuilder, inj: &Injection, marker: InjectionMarker) {
+    let sqls = inj.at(marker);
+    if sqls.is_empty() {
+        return;
+    }
+    buf.write_leading("");
+    buf.write_str(&sqls.join(" "));
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use crate::modifiers::Builder;
+    use crate::{
+        BuildError, Flavor, MergeBuilder, MergeWhen, SelectBuilder, set_default_flavor_scoped,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn merge_flavor_matrix() {
+        let _g = set_default_flavor_scoped(Flavor::MySQL);
+        let mut mb = MergeBuilder::new();
+        mb.merge_into("users")
+            .target_alias("t")
+            .using("staging_users")
+            .source_alias("s")
+            .on(["t.id = s.id"]);
+        let active = mb.equal("s.active", true);
+        mb.when(
+            MergeWhen::matched()
+                .and([active])
+                .then_update(["name = s.name"]),
+        )
+        .when(MergeWhen::not_matched().then_insert(["id", "name"], ["s.id", "s.name"]));
+
+        let (sql, args) = mb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "MERGE INTO users AS t USING staging_users AS s ON t.id = s.id WHEN MATCHED AND s.active = $1 THEN UPDATE SET name = s.name WHEN NOT MATCHED THEN INSERT (id, name) VALUES (s.id, s.name)"
+        );
+        assert_eq!(args.len(), 1);
+
+        let (sql, _) = mb.build_with_flavor(Flavor::SQLServer, &[]);
+        assert_eq!(
+            sql,
+            "MERGE INTO users AS t USING staging_users AS s ON t.id = s.id WHEN MATCHED AND s.active = @p1 THEN UPDATE SET name = s.name WHEN NOT MATCHED THEN INSERT (id, name) VALUES (s.id, s.name);"
+        );
+
+        let (sql, _) = mb.build_with_flavor(Flavor::Oracle, &[]);
+        assert_eq!(
+            sql,
+            "MERGE INTO users t USING staging_users s ON (t.id = s.id) WHEN MATCHED THEN UPDATE SET name = s.name WHERE s.active = :1 WHEN NOT MATCHED THEN INSERT (id, name) VALUES (s.id, s.name)"
+        );
+
+        assert_eq!(
+            mb.try_build_with_flavor(Flavor::MySQL, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::MySQL,
+                feature: "MERGE",
+            })
+        );
+    }
+
+    #[test]
+    fn merge_select_source_and_output() {
+        let _g = set_default_flavor_scoped(Flavor::SQLServer);
+        let mut src = SelectBuilder::new();
+        src.select(["id", "qty"]).from(["orders"]);
+        src.where_([src.gt("qty", 0_i64)]);
+
+        let mut mb = MergeBuilder::new();
+        mb.merge_into("stock")
+            .using_builder(src)
+            .source_alias("o")
+            .on(["stock.id = o.id"]);
+        let bump = mb.add("qty", 1_i64);
+        mb.when(MergeWhen::matched().then_update([bump]))
+            .when(MergeWhen::not_matched_by_source().then_delete())
+            .returning(["$$action", "id", "DELETED.qty"]);
+
+        let (sql, args) = mb.build();
+        assert_eq!(
+            sql,
+            "MERGE INTO stock USING (SELECT id, qty FROM orders WHERE qty > @p1) AS o ON stock.id = o.id WHEN MATCHED THEN UPDATE SET qty = stock.qty + @p2 WHEN NOT MATCHED BY SOURCE THEN DELETE OUTPUT $action, INSERTED.id, DELETED.qty;"
+        );
+        assert_eq!(args.len(), 2);
+
+        mb.returning(["merge_action()", "stock.id"]);
+        let (sql, _) = mb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "MERGE INTO stock USING (SELECT id, qty FROM orders WHERE qty > $1) AS o ON stock.id = o.id WHEN MATCHED THEN UPDATE SET qty = stock.qty + $2 WHEN NOT MATCHED BY SOURCE THEN DELETE RETURNING merge_action(), stock.id"
+        );
+
+        assert_eq!(
+            mb.try_build_with_flavor(Flavor::Oracle, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::Oracle,
+                feature: "WHEN NOT MATCHED BY SOURCE",
+            })
+        );
+    }
+
+    #[test]
+    fn merge_returning_unsupported_on_oracle() {
+        let mut mb = MergeBuilder::new();
+        mb.merge_into("stock")
+            .target_alias("t")
+            .using("orders")
+            .source_alias("o")
+            .on(["t.id = o.id"]);
+        let bump = mb.incr("qty");
+        mb.when(MergeWhen::matched().then_update([bump]));
+
+        let (sql, _) = mb.build_with_flavor(Flavor::Oracle, &[]);
+        assert_eq!(
+            sql,
+            "MERGE INTO stock t USING orders o ON (t.id = o.id) WHEN MATCHED THEN UPDATE SET qty = t.qty + 1"
+        );
+
+        mb.returning(["t.id"]);
+        assert_eq!(
+            mb.try_build_with_flavor(Flavor::Oracle, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::Oracle,
+                feature: "MERGE ... RETURNING",
+            })
+        );
+    }
+
+    #[test]
+    fn merge_do_nothing_and_injection() {
+        let _g = set_default_flavor_scoped(Flavor::PostgreSQL);
+        let mut mb = MergeBuilder::new();
+        mb.merge_into("t")
+            .sql("/* target */")
+            .using("s")
+            .on(["t.id = s.id"])
+            .sql("/* on */")
+            .when(MergeWhen::matched().then_do_nothing())
+            .sql("/* when */");
+
+        let (sql, _) = mb.build();
+        assert_eq!(
+            sql,
+            "MERGE INTO t /* target */ USING s ON t.id = s.id /* on */ WHEN MATCHED THEN DO NOTHING /* when */"
+        );
+
+        assert_eq!(
+            mb.try_build_with_flavor(Flavor::SQLServer, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::SQLServer,
+                feature: "MERGE ... DO NOTHING",
+            })
+        );
+
+        let cloned = mb.clone_builder();
+        assert_eq!(cloned.build(), mb.build());
+    }
+}
@@ -1,11 +1,11 @@
 //! Argument modifiers and helpers for builders.
 
+use crate::args::BuildError;
 use crate::flavor::Flavor;
+use crate::shared::Shared;
 use crate::value::SqlValue;
 use crate::valuer::SqlValuer;
 use dyn_clone::DynClone;
-use std::cell::RefCell;
-use std::rc::Rc;
 
 /// Escape: replace `$` with `$$` to avoid being parsed as an expression by `Args::compile`.
 pub fn escape(ident: &str) -> String {
@@ -17,6 +17,12 @@ pub fn escape_all(idents: impl IntoIterator<Item = impl AsRef<str>>) -> Vec<Stri
     idents.into_iter().map(|s| escape(s.as_ref())).collect()
 }
 
+/// ArithAssignment: `field = current op rhs`, where `current` is how the statement reads the
+/// column's existing value.
+pub(crate) fn arith_assignment(field: &str, current: &str, op: char, rhs: &str) -> String {
+    format!("{field} = {current} {op} {rhs}")
+}
+
 /// Raw: inline SQL fragment without becoming a placeholder.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Raw {
@@ -178,31 +184,99 @@ impl Builder for Box<dyn Builder> {
         (**self).build_with_flavor(flavor, initial_arg)
     }
 
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        (**self).try_build_with_flavor(flavor, initial_arg)
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        (**self).build_into(flavor, sql, args)
+    }
+
+    fn needs_subquery_in_compound(&self) -> bool {
+        (**self).needs_subquery_in_compound()
+    }
+
     fn flavor(&self) -> Flavor {
         (**self).flavor()
     }
 }
 
 /// Builder: trait for objects that can build SQL (nestable).
-pub trait Builder: DynClone {
+///
+/// Builders are `Send + Sync` so they can be held across `.await` points and moved between
+/// threads.
+pub trait Builder: DynClone + Send + Sync {
     fn build(&self) -> (String, Vec<Arg>) {
         self.build_with_flavor(self.flavor(), &[])
     }
 
     fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>);
 
+    /// TryBuild: like `build`, but malformed args are returned as `BuildError`
+    /// instead of being written into the SQL.
+    fn try_build(&self) -> Result<(String, Vec<Arg>), BuildError> {
+        self.try_build_with_flavor(self.flavor(), &[])
+    }
+
+    /// TryBuildWithFlavor: strict variant of `build_with_flavor`.
+    ///
+    /// The default implementation cannot detect errors and defers to `build_with_flavor`.
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        Ok(self.build_with_flavor(flavor, initial_arg))
+    }
+
+    /// BuildInto: strict build that appends the SQL to `sql` and the args to `args`.
+    ///
+    /// `args` doubles as the initial args, so placeholders are numbered after the values it
+    /// already holds. Reusing both buffers across calls avoids per-build allocations; nested
+    /// builders write into the same buffers instead of cloning the arg list at every level.
+    /// On error both buffers are left as they were.
+    ///
+    /// The default implementation builds into fresh buffers and moves the result over.
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        let (s, a) = self.try_build_with_flavor(flavor, args)?;
+        sql.push_str(&s);
+        *args = a;
+        Ok(())
+    }
+
+    /// NeedsSubqueryInCompound: the statement cannot stand bare between UNION/INTERSECT/EXCEPT
+    /// operators, because it is a compound itself or ends with ORDER BY/LIMIT/OFFSET. Flavors
+    /// without parenthesized members (SQLite) then wrap it as `SELECT * FROM (...)`.
+    fn needs_subquery_in_compound(&self) -> bool {
+        false
+    }
+
     fn flavor(&self) -> Flavor;
 }
 
 dyn_clone::clone_trait_object!(Builder);
 
-/// RcBuilder: wrap `Rc<RefCell<T>>` as a Builder to enable shared, late-bound builders.
+/// RcBuilder: wrap a `Shared<T>` as a Builder to enable shared, late-bound builders.
 ///
 /// Typical usage: pass a `SelectBuilder` as a subquery argument while allowing further mutation,
 /// so the final build uses the latest state.
 #[derive(Debug)]
 pub struct RcBuilder<T: Builder> {
-    inner: Rc<RefCell<T>>,
+    inner: Shared<T>,
 }
 
 impl<T: Builder> Clone for RcBuilder<T> {
@@ -214,11 +288,13 @@ impl<T: Builder> Clone for RcBuilder<T> {
 }
 
 impl<T: Builder> RcBuilder<T> {
-    pub fn new(inner: Rc<RefCell<T>>) -> Self {
+    pub fn new(inner: Shared<T>) -> Self {
         Self { inner }
     }
 
-    pub fn inner(&self) -> Rc<RefCell<T>> {
+    /// Inner: the shared builder. Do not `borrow_mut` it while a guard is held, including from
+    /// inside a build of this `RcBuilder`; that deadlocks where `RefCell` used to panic.
+    pub fn inner(&self) -> Shared<T> {
         self.inner.clone()
     }
 }
@@ -228,12 +304,26 @@ impl<T: Builder> Builder for RcBuilder<T> {
         self.inner.borrow().build_with_flavor(flavor, initial_arg)
     }
 
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        self.inner
+            .borrow()
+            .try_build_with_flavor(flavor, initial_arg)
+    }
+
+    fn needs_subquery_in_compound(&self) -> bool {
+        self.inner.borrow().needs_subquery_in_compound()
+    }
+
     fn flavor(&self) -> Flavor {
         self.inner.borrow().flavor()
     }
 }
 
-pub fn rc_builder<T: Builder>(inner: Rc<RefCell<T>>) -> RcBuilder<T> {
+pub fn rc_builder<T: Builder>(inner: Shared<T>) -> RcBuilder<T> {
     RcBuilder::new(inner)
 }
 
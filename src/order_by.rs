@@ -0,0 +1,216 @@
+//! OrderTerm: one ORDER BY item with its own direction and NULLS placement.
+
+use crate::args::BuildError;
+use crate::expr::Expr;
+use crate::flavor::Flavor;
+use crate::modifiers::Arg;
+use crate::string_builder::StringBuilder;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nulls {
+    First,
+    Last,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    Sql(String),
+    Expr(Expr),
+}
+
+/// OrderTerm: `expr [ASC|DESC] [NULLS FIRST|LAST]`.
+///
+/// MySQL and SQL Server have no NULLS keyword; there the placement is emulated with an extra
+/// leading sort key on `expr IS NULL`. SQL Server only sorts by select-list items after UNION
+/// and SELECT DISTINCT, so the emulation reports `BuildError::Unsupported` there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderTerm {
+    target: Target,
+    direction: Option<Direction>,
+    nulls: Option<Nulls>,
+}
+
+impl OrderTerm {
+    /// Order by a column or SQL expression (may contain `$n` placeholders from the builder).
+    pub fn new(expr: impl Into<String>) -> Self {
+        Self {
+            target: Target::Sql(expr.into()),
+            direction: None,
+            nulls: None,
+        }
+    }
+
+    /// Order by a parameterized [`Expr`], e.g. `FIELD(id, ?, ?)`.
+    pub fn expr(e: Expr) -> Self {
+        Self {
+            target: Target::Expr(e),
+            direction: None,
+            nulls: None,
+        }
+    }
+
+    pub fn asc(mut self) -> Self {
+        self.direction = Some(Direction::Asc);
+        self
+    }
+
+    pub fn desc(mut self) -> Self {
+        self.direction = Some(Direction::Desc);
+        self
+    }
+
+    pub fn nulls_first(mut self) -> Self {
+        self.nulls = Some(Nulls::First);
+        self
+    }
+
+    pub fn nulls_last(mut self) -> Self {
+        self.nulls = Some(Nulls::Last);
+        self
+    }
+
+    /// Turn the legacy `asc()`/`desc()` keyword into this term's direction; `false` if the term
+    /// already has one.
+    pub(crate) fn apply_legacy_order(&mut self, order: &str) -> bool {
+        if self.direction.is_some() {
+            return false;
+        }
+        self.direction = Some(if order == "DESC" {
+            Direction::Desc
+        } else {
+            Direction::Asc
+        });
+        true
+    }
+
+    /// Register an `Expr` target with the owning builder, replacing it by its placeholder.
+    pub(crate) fn bind(self, var: impl FnOnce(Arg) -> String) -> Self {
+        match self.target {
+            Target::Expr(e) => Self {
+                target: Target::Sql(var(e.into())),
+                ..self
+            },
+            Target::Sql(_) => self,
+        }
+    }
+
+    fn sql(&self) -> &str {
+        match &self.target {
+            Target::Sql(s) => s,
+            // Unbound exprs only appear if a builder forgot to call `bind`.
+            Target::Expr(_) => "",
+        }
+    }
+
+    fn with_direction(&self) -> String {
+        match self.direction {
+            Some(Direction::Asc) => format!("{} ASC", self.sql()),
+            Some(Direction::Desc) => format!("{} DESC", self.sql()),
+            None => self.sql().to_string(),
+        }
+    }
+
+    fn render_standard(&self) -> String {
+        match self.nulls {
+            Some(Nulls::First) => format!("{} NULLS FIRST", self.with_direction()),
+            Some(Nulls::Last) => format!("{} NULLS LAST", self.with_direction()),
+            None => self.with_direction(),
+        }
+    }
+
+    fn render(&self, flavor: Flavor, scope: OrderByScope) -> Result<String, BuildError> {
+        let Some(nulls) = self.nulls else {
+            return Ok(self.with_direction());
+        };
+        let expr = self.sql();
+        let s = match flavor {
+            Flavor::MySQL => {
+                let key = match nulls {
+                    Nulls::First => "IS NOT NULL",
+                    Nulls::Last => "IS NULL",
+                };
+                format!("{expr} {key}, {}", self.with_direction())
+            }
+            Flavor::SQLServer if scope != OrderByScope::Statement => {
+                return Err(BuildError::Unsupported {
+                    flavor,
+                    feature: match scope {
+                        OrderByScope::Union => "NULLS FIRST/LAST in a UNION ORDER BY",
+                        _ => "NULLS FIRST/LAST with SELECT DISTINCT",
+                    },
+                });
+            }
+            Flavor::SQLServer => {
+                let (null, not_null) = match nulls {
+                    Nulls::First => (0, 1),
+                    Nulls::Last => (1, 0),
+                };
+                format!(
+                    "CASE WHEN {expr} IS NULL THEN {null} ELSE {not_null} END, {}",
+                    self.with_direction()
+                )
+            }
+            Flavor::CQL => {
+                return Err(BuildError::Unsupported {
+                    flavor,
+                    feature: "NULLS FIRST/LAST",
+                });
+            }
+            _ => self.render_standard(),
+        };
+        Ok(s)
+    }
+}
+
+impl From<&str> for OrderTerm {
+    fn from(expr: &str) -> Self {
+        Self::new(expr)
+    }
+}
+
+impl From<String> for OrderTerm {
+    fn from(expr: String) -> Self {
+        Self::new(expr)
+    }
+}
+
+/// Where an ORDER BY is written; decides whether extra sort keys may be emulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OrderByScope {
+    Statement,
+    Distinct,
+    Union,
+}
+
+/// Write `ORDER BY terms`; `order` is the legacy `asc()`/`desc()` keyword, which has always been
+/// written after the list and so sorts the last term. That term must not have its own direction.
+pub(crate) fn write_order_by(
+    buf: &mut StringBuilder,
+    terms: &[OrderTerm],
+    order: Option<&str>,
+    flavor: Flavor,
+    scope: OrderByScope,
+) -> Result<(), BuildError> {
+    let mut last = terms.last().cloned();
+    if let (Some(order), Some(term)) = (order, last.as_mut())
+        && !term.apply_legacy_order(order)
+    {
+        return Err(BuildError::MixedOrderBy);
+    }
+    let items = terms[..terms.len().saturating_sub(1)]
+        .iter()
+        .chain(last.as_ref())
+        .map(|term| term.render(flavor, scope))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    buf.write_leading("ORDER BY");
+    buf.write_str(" ");
+    buf.write_str(&items.join(", "));
+    Ok(())
+}
@@ -0,0 +1,210 @@
+#[cfg(test)]
+mod tests {
+    use crate::args::BuildError;
+    use crate::delete::DeleteBuilder;
+    use crate::expr::Expr;
+    use crate::flavor::Flavor;
+    use crate::modifiers::{Arg, Builder};
+    use crate::order_by::OrderTerm;
+    use crate::select::SelectBuilder;
+    use crate::union::UnionBuilder;
+    use crate::update::UpdateBuilder;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn order_terms_per_column_direction_and_nulls() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id", "name"]).from(vec!["users"]);
+        sb.order_by_term(OrderTerm::new("last_login").desc().nulls_last());
+        sb.order_by_term("name");
+        sb.order_by_term(OrderTerm::new("id").asc());
+
+        let (sql, _) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, name FROM users ORDER BY last_login DESC NULLS LAST, name, id ASC"
+        );
+
+        let (sql, _) = sb.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, name FROM users ORDER BY last_login IS NULL, last_login DESC, name, id ASC"
+        );
+
+        let (sql, _) = sb.build_with_flavor(Flavor::SQLServer, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, name FROM users ORDER BY CASE WHEN last_login IS NULL THEN 1 ELSE 0 END, \
+             last_login DESC, name, id ASC"
+        );
+    }
+
+    #[test]
+    fn order_terms_nulls_first_emulation() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id", "name"]).from(vec!["users"]);
+        sb.order_by_term(OrderTerm::new("score").nulls_first());
+
+        let (sql, _) = sb.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, name FROM users ORDER BY score IS NOT NULL, score"
+        );
+        let (sql, _) = sb.build_with_flavor(Flavor::SQLServer, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, name FROM users ORDER BY CASE WHEN score IS NULL THEN 0 ELSE 1 END, score"
+        );
+        let (sql, _) = sb.build_with_flavor(Flavor::Oracle, &[]);
+        assert_eq!(sql, "SELECT id, name FROM users ORDER BY score NULLS FIRST");
+    }
+
+    #[test]
+    fn order_terms_parameterized_expr() {
+        let mut field = Expr::raw("FIELD(id, ");
+        field.push_arg(3_i64);
+        field.push_raw(", ");
+        field.push_arg(1_i64);
+        field.push_raw(")");
+
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id", "name"]).from(vec!["users"]);
+        let cond = sb.greater_than("id", 0_i64);
+        sb.where_(vec![cond]);
+        sb.order_by_term(OrderTerm::expr(field).desc());
+
+        let (sql, args) = sb.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, name FROM users WHERE id > ? ORDER BY FIELD(id, ?, ?) DESC"
+        );
+        assert_eq!(
+            args,
+            vec![Arg::from(0_i64), Arg::from(3_i64), Arg::from(1_i64)]
+        );
+
+        let (sql, _) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, name FROM users WHERE id > $1 ORDER BY FIELD(id, $2, $3) DESC"
+        );
+
+        let mut nickname = Expr::raw("NULLIF(nickname, ");
+        nickname.push_arg("");
+        nickname.push_raw(")");
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id", "name"]).from(vec!["users"]);
+        sb.order_by_term(OrderTerm::expr(nickname).nulls_last());
+        let (sql, args) = sb.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, name FROM users ORDER BY NULLIF(nickname, ?) IS NULL, NULLIF(nickname, ?)"
+        );
+        assert_eq!(args, vec![Arg::from(""), Arg::from("")]);
+    }
+
+    #[test]
+    fn order_terms_unsupported_nulls_on_cql() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id", "name"]).from(vec!["users"]);
+        sb.order_by_term(OrderTerm::new("ts").nulls_last());
+
+        assert_eq!(
+            sb.try_build_with_flavor(Flavor::CQL, &[]).unwrap_err(),
+            BuildError::Unsupported {
+                flavor: Flavor::CQL,
+                feature: "NULLS FIRST/LAST",
+            }
+        );
+    }
+
+    #[test]
+    fn order_terms_in_update_delete_union() {
+        let mut ub = UpdateBuilder::new();
+        ub.update(vec!["jobs"]);
+        let set = ub.assign("state", "queued");
+        ub.set(vec![set]);
+        ub.order_by_term(OrderTerm::new("priority").desc().nulls_last());
+        ub.limit(10);
+        ub.allow_full_table();
+        let (sql, _) = ub.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "UPDATE jobs SET state = ? ORDER BY priority IS NULL, priority DESC LIMIT ?"
+        );
+
+        let mut db = DeleteBuilder::new();
+        db.delete_from(vec!["jobs"]);
+        db.order_by_term(OrderTerm::new("created_at").asc());
+        db.order_by_term(OrderTerm::new("id").desc());
+        db.allow_full_table();
+        let (sql, _) = db.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(sql, "DELETE FROM jobs ORDER BY created_at ASC, id DESC");
+
+        let mut active = SelectBuilder::new();
+        active.select(vec!["id", "name"]).from(vec!["active_users"]);
+        let mut archived = SelectBuilder::new();
+        archived
+            .select(vec!["id", "name"])
+            .from(vec!["archived_users"]);
+        let mut ub = UnionBuilder::new();
+        ub.union(vec![active, archived]);
+        ub.order_by_term(OrderTerm::new("name").nulls_first());
+        let (sql, _) = ub.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "(SELECT id, name FROM active_users) UNION (SELECT id, name FROM archived_users) \
+             ORDER BY name NULLS FIRST"
+        );
+
+        assert_eq!(
+            ub.try_build_with_flavor(Flavor::SQLServer, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::SQLServer,
+                feature: "NULLS FIRST/LAST in a UNION ORDER BY",
+            })
+        );
+    }
+
+    #[test]
+    fn order_terms_nulls_emulation_with_distinct() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["name"]).distinct().from(vec!["users"]);
+        sb.order_by_term(OrderTerm::new("name").nulls_last());
+
+        let (sql, _) = sb.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT DISTINCT name FROM users ORDER BY name IS NULL, name"
+        );
+        assert_eq!(
+            sb.try_build_with_flavor(Flavor::SQLServer, &[]),
+            Err(BuildError::Unsupported {
+                flavor: Flavor::SQLServer,
+                feature: "NULLS FIRST/LAST with SELECT DISTINCT",
+            })
+        );
+    }
+
+    #[test]
+    fn order_terms_with_legacy_direction() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id"]).from(vec!["users"]);
+        sb.order_by_asc("a").order_by(vec!["b"]).desc();
+        let (sql, _) = sb.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(sql, "SELECT id FROM users ORDER BY a ASC, b DESC");
+
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id"]).from(vec!["users"]);
+        sb.order_by_term(OrderTerm::new("name").nulls_last()).desc();
+        let (sql, _) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(sql, "SELECT id FROM users ORDER BY name DESC NULLS LAST");
+
+        sb.order_by_term(OrderTerm::new("id").asc());
+        assert_eq!(
+            sb.try_build_with_flavor(Flavor::PostgreSQL, &[])
+                .unwrap_err(),
+            BuildError::MixedOrderBy
+        );
+    }
+}
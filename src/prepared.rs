@@ -0,0 +1,172 @@
+//! PreparedTemplate: compile a builder once, then rebind values without rebuilding the SQL.
+
+use crate::args::{BuildError, record_slot_names};
+use crate::flavor::Flavor;
+use crate::modifiers::{Arg, Builder, SqlNamedArg};
+use crate::valuer::ValuerError;
+use std::collections::HashMap;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum BindError {
+    #[error("template has {expected} slots, got {got} values")]
+    Arity { expected: usize, got: usize },
+    #[error("template has no slot named {0}")]
+    UnknownSlot(String),
+    #[error("no value bound for slot {0}")]
+    MissingSlot(String),
+    #[error("slot {slot} takes a value; builders, lists and raw SQL would change the statement")]
+    NotAValue { slot: usize },
+    #[error(transparent)]
+    Valuer(#[from] ValuerError),
+}
+
+/// Slot: one entry of the arg vector the template's SQL expects.
+#[derive(Debug, Clone, PartialEq)]
+struct Slot {
+    /// Set for `${name}` placeholders that filled exactly this slot, and for `@name` args.
+    name: Option<String>,
+    /// Bound values are wrapped back into `Arg::SqlNamed` (`@name` placeholders).
+    sql_named: bool,
+}
+
+/// PreparedTemplate: the final SQL of a builder for one flavor plus the slots its args fill.
+///
+/// ```ignore
+/// let tpl = PreparedTemplate::new(&sb, Flavor::PostgreSQL)?;
+/// let args = tpl.bind([42_i64, 7_i64])?;
+/// let args = tpl.bind_named([("status", Arg::from(1_i64))])?;
+/// ```
+///
+/// Slots follow the order of the args the builder produced. A `${name}` that compiles to a single
+/// value and every `@name` arg can also be addressed by name.
+#[derive(Debug, Clone)]
+pub struct PreparedTemplate {
+    sql: String,
+    flavor: Flavor,
+    slots: Vec<Slot>,
+    args: Vec<Arg>,
+}
+
+impl PreparedTemplate {
+    /// New: strictly build `builder` for `flavor` and record its slots.
+    pub fn new(builder: &(impl Builder + ?Sized), flavor: Flavor) -> Result<Self, BuildError> {
+        let mut sql = String::new();
+        let mut args = Vec::new();
+        let (built, names) = record_slot_names(|| builder.build_into(flavor, &mut sql, &mut args));
+        built?;
+
+        let mut slots: Vec<Slot> = args
+            .iter()
+            .map(|a| match a {
+                Arg::SqlNamed(a) => Slot {
+                    name: Some(a.name.clone()),
+                    sql_named: true,
+                },
+                _ => Slot {
+                    name: None,
+                    sql_named: false,
+                },
+            })
+            .collect();
+        for (pos, name) in names {
+            if let Some(slot) = slots.get_mut(pos) {
+                slot.name = Some(name);
+            }
+        }
+
+        Ok(Self {
+            sql,
+            flavor,
+            slots,
+            args,
+        })
+    }
+
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    pub fn flavor(&self) -> Flavor {
+        self.flavor
+    }
+
+    /// Arity: number of values `bind` expects.
+    pub fn arity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// SlotNames: the name of each slot in order, `None` for positional-only slots.
+    pub fn slot_names(&self) -> impl Iterator<Item = Option<&str>> {
+        self.slots.iter().map(|s| s.name.as_deref())
+    }
+
+    /// Args: the values captured when the template was prepared.
+    pub fn args(&self) -> &[Arg] {
+        &self.args
+    }
+
+    /// Bind: one value per slot, in slot order.
+    pub fn bind(
+        &self,
+        values: impl IntoIterator<Item = impl Into<Arg>>,
+    ) -> Result<Vec<Arg>, BindError> {
+        let values: Vec<Arg> = values.into_iter().map(Into::into).collect();
+        if values.len() != self.slots.len() {
+            return Err(BindError::Arity {
+                expected: self.slots.len(),
+                got: values.len(),
+            });
+        }
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| self.slot_value(i, v))
+            .collect()
+    }
+
+    /// BindNamed: values for named slots; every name must be bound and slots without a name
+    /// keep the values captured when the template was prepared.
+    pub fn bind_named(
+        &self,
+        values: impl IntoIterator<Item = (impl Into<String>, impl Into<Arg>)>,
+    ) -> Result<Vec<Arg>, BindError> {
+        let values: HashMap<String, Arg> = values
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        if let Some(name) = values
+            .keys()
+            .find(|k| !self.slots.iter().any(|s| s.name.as_ref() == Some(*k)))
+        {
+            return Err(BindError::UnknownSlot(name.clone()));
+        }
+
+        self.slots
+            .iter()
+            .enumerate()
+            .map(|(i, slot)| match &slot.name {
+                Some(name) => match values.get(name) {
+                    Some(v) => self.slot_value(i, v.clone()),
+                    None => Err(BindError::MissingSlot(name.clone())),
+                },
+                None => Ok(self.args[i].clone()),
+            })
+            .collect()
+    }
+
+    fn slot_value(&self, i: usize, value: Arg) -> Result<Arg, BindError> {
+        let value = match value {
+            Arg::Value(_) => value,
+            Arg::Valuer(v) => Arg::Value(v.value()?),
+            _ => return Err(BindError::NotAValue { slot: i }),
+        };
+        let slot = &self.slots[i];
+        match (&slot.name, slot.sql_named) {
+            (Some(name), true) => Ok(Arg::SqlNamed(SqlNamedArg {
+                name: name.clone(),
+                value: Box::new(value),
+            })),
+            _ => Ok(value),
+        }
+    }
+}
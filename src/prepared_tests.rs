@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod tests {
+    use crate::args::record_slot_names;
+    use crate::builder::{build, build_named};
+    use crate::modifiers::{Arg, Builder, SqlNamedArg, named, raw};
+    use crate::prepared::{BindError, PreparedTemplate};
+    use crate::select::SelectBuilder;
+    use crate::{Flavor, SqlValue};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn prepared_template_rebinds_positional_slots() {
+        let mut sb = SelectBuilder::new();
+        sb.select(["id", "name"]).from(["users"]);
+        sb.where_([sb.equal("status", 1_i64), sb.in_("id", [10_i64, 11])]);
+        let tpl = PreparedTemplate::new(&sb, Flavor::PostgreSQL).unwrap();
+        assert_eq!(
+            tpl.sql(),
+            "SELECT id, name FROM users WHERE status = $1 AND id IN ($2, $3)"
+        );
+        assert_eq!(tpl.flavor(), Flavor::PostgreSQL);
+        assert_eq!(tpl.arity(), 3);
+        assert_eq!(tpl.slot_names().collect::<Vec<_>>(), vec![None, None, None]);
+        assert_eq!(tpl.args(), &[1_i64.into(), 10_i64.into(), 11_i64.into()]);
+
+        let args = tpl.bind([2_i64, 20, 21]).unwrap();
+        let mut sb = SelectBuilder::new();
+        sb.select(["id", "name"]).from(["users"]);
+        sb.where_([sb.equal("status", 2_i64), sb.in_("id", [20_i64, 21])]);
+        let (sql, expected) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(sql, tpl.sql());
+        assert_eq!(args, expected);
+
+        assert_eq!(
+            tpl.bind([2_i64, 20]),
+            Err(BindError::Arity {
+                expected: 3,
+                got: 2
+            })
+        );
+        assert_eq!(
+            tpl.bind([Arg::from(2_i64), raw("NOW()"), 21_i64.into()]),
+            Err(BindError::NotAValue { slot: 1 })
+        );
+    }
+
+    #[test]
+    fn prepared_template_binds_by_name() {
+        let b = build_named(
+            "SELECT * FROM orders WHERE status = ${status} AND (owner = ${user} OR editor = ${user}) LIMIT ${limit}",
+            [
+                ("status".to_string(), Arg::from("paid")),
+                ("user".to_string(), Arg::from(7_i64)),
+                ("limit".to_string(), Arg::from(10_i64)),
+            ],
+        );
+        let tpl = PreparedTemplate::new(&b, Flavor::MySQL).unwrap();
+        assert_eq!(
+            tpl.sql(),
+            "SELECT * FROM orders WHERE status = ? AND (owner = ? OR editor = ?) LIMIT ?"
+        );
+        assert_eq!(
+            tpl.slot_names().collect::<Vec<_>>(),
+            vec![Some("status"), Some("user"), Some("user"), Some("limit")]
+        );
+
+        let args = tpl
+            .bind_named([
+                ("status", Arg::from("open")),
+                ("user", Arg::from(9_i64)),
+                ("limit", Arg::from(5_i64)),
+            ])
+            .unwrap();
+        assert_eq!(
+            args,
+            vec!["open".into(), 9_i64.into(), 9_i64.into(), 5_i64.into()]
+        );
+
+        assert_eq!(
+            tpl.bind_named([("status", Arg::from("open")), ("user", Arg::from(9_i64))]),
+            Err(BindError::MissingSlot("limit".to_string()))
+        );
+        assert_eq!(
+            tpl.bind_named([("owner", Arg::from(9_i64))]),
+            Err(BindError::UnknownSlot("owner".to_string()))
+        );
+    }
+
+    #[test]
+    fn prepared_template_mixes_named_and_positional_slots() {
+        let b = build(
+            "SELECT * FROM t WHERE a = ${a} AND b = $0 AND c = $1 AND d = $2",
+            [
+                named("a", 1_i64),
+                Arg::from(2_i64),
+                Arg::SqlNamed(SqlNamedArg::new("d", 4_i64)),
+            ],
+        );
+        let tpl = PreparedTemplate::new(&b, Flavor::SQLServer).unwrap();
+        assert_eq!(
+            tpl.sql(),
+            "SELECT * FROM t WHERE a = @p1 AND b = @p2 AND c = @p3 AND d = @d"
+        );
+        assert_eq!(
+            tpl.slot_names().collect::<Vec<_>>(),
+            vec![Some("a"), None, None, Some("d")]
+        );
+
+        // Unnamed slots keep their prepared values; `@name` slots stay wrapped.
+        let args = tpl
+            .bind_named([("a", Arg::from(10_i64)), ("d", Arg::from(40_i64))])
+            .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                10_i64.into(),
+                1_i64.into(),
+                2_i64.into(),
+                Arg::SqlNamed(SqlNamedArg::new("d", 40_i64)),
+            ]
+        );
+
+        let args = tpl
+            .bind([
+                SqlValue::I64(10),
+                SqlValue::I64(11),
+                SqlValue::I64(12),
+                SqlValue::I64(40),
+            ])
+            .unwrap();
+        assert_eq!(args[3], Arg::SqlNamed(SqlNamedArg::new("d", 40_i64)));
+    }
+
+    #[test]
+    fn slot_name_recording_survives_a_panicking_build() {
+        let named_select = |name: &str| {
+            build_named(
+                format!("SELECT ${{{name}}}"),
+                [(name.to_string(), Arg::from(1_i64))],
+            )
+        };
+        let build_into = |b: &dyn Builder| {
+            b.build_into(Flavor::MySQL, &mut String::new(), &mut Vec::new())
+                .unwrap()
+        };
+
+        let ((), names) = record_slot_names(|| {
+            build_into(&*named_select("outer_before"));
+            let inner = std::panic::catch_unwind(|| {
+                record_slot_names(|| {
+                    build_into(&*named_select("inner"));
+                    panic!("build failed");
+                })
+            });
+            assert!(inner.is_err());
+            build_into(&*named_select("outer_after"));
+        });
+        assert_eq!(
+            names,
+            vec![
+                (0, "outer_before".to_string()),
+                (0, "outer_after".to_string())
+            ]
+        );
+    }
+}
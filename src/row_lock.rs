@@ -0,0 +1,152 @@
+//! RowLock: row-locking clause for `SelectBuilder` (`FOR UPDATE ... SKIP LOCKED` and friends).
+
+use crate::args::BuildError;
+use crate::flavor::Flavor;
+use crate::macros::{IntoStrings, collect_into_strings};
+
+/// Lock strength.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockStrength {
+    Update,
+    /// PostgreSQL only.
+    NoKeyUpdate,
+    Share,
+    /// PostgreSQL only.
+    KeyShare,
+}
+
+/// What to do when a row is already locked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LockWait {
+    #[default]
+    Wait,
+    NoWait,
+    SkipLocked,
+}
+
+/// RowLock: `FOR <strength> [OF t, ...] [NOWAIT | SKIP LOCKED]`.
+///
+/// SQL Server has no locking clause; the lock is rendered as `WITH (UPDLOCK, ...)` table hints
+/// after the locked tables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowLock {
+    strength: LockStrength,
+    of: Vec<String>,
+    wait: LockWait,
+}
+
+impl RowLock {
+    pub fn new(strength: LockStrength) -> Self {
+        Self {
+            strength,
+            of: Vec::new(),
+            wait: LockWait::Wait,
+        }
+    }
+
+    pub fn update() -> Self {
+        Self::new(LockStrength::Update)
+    }
+
+    pub fn no_key_update() -> Self {
+        Self::new(LockStrength::NoKeyUpdate)
+    }
+
+    pub fn share() -> Self {
+        Self::new(LockStrength::Share)
+    }
+
+    pub fn key_share() -> Self {
+        Self::new(LockStrength::KeyShare)
+    }
+
+    /// Restrict the lock to these tables (Oracle and Informix: columns).
+    pub fn of<T>(mut self, tables: T) -> Self
+    where
+        T: IntoStrings,
+    {
+        self.of.extend(collect_into_strings(tables));
+        self
+    }
+
+    pub fn nowait(mut self) -> Self {
+        self.wait = LockWait::NoWait;
+        self
+    }
+
+    pub fn skip_locked(mut self) -> Self {
+        self.wait = LockWait::SkipLocked;
+        self
+    }
+
+    pub(crate) fn validate(&self, flavor: Flavor) -> Result<(), BuildError> {
+        use Flavor::*;
+        let unsupported = |feature| Err(BuildError::Unsupported { flavor, feature });
+
+        match flavor {
+            SQLite | ClickHouse | CQL | Presto | Doris => return unsupported("row locking"),
+            PostgreSQL => {}
+            _ if matches!(
+                self.strength,
+                LockStrength::NoKeyUpdate | LockStrength::KeyShare
+            ) =>
+            {
+                return unsupported("FOR NO KEY UPDATE/FOR KEY SHARE");
+            }
+            Oracle | Informix if self.strength == LockStrength::Share => {
+                return unsupported("FOR SHARE");
+            }
+            Informix if self.wait != LockWait::Wait => return unsupported("NOWAIT/SKIP LOCKED"),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Trailing locking clause; `None` for SQL Server, which uses table hints instead.
+    pub(crate) fn clause(&self, flavor: Flavor) -> Result<Option<String>, BuildError> {
+        self.validate(flavor)?;
+        if flavor == Flavor::SQLServer {
+            return Ok(None);
+        }
+        let mut s = String::from(match self.strength {
+            LockStrength::Update => "FOR UPDATE",
+            LockStrength::NoKeyUpdate => "FOR NO KEY UPDATE",
+            LockStrength::Share => "FOR SHARE",
+            LockStrength::KeyShare => "FOR KEY SHARE",
+        });
+        if !self.of.is_empty() {
+            s.push_str(" OF ");
+            s.push_str(&self.of.join(", "));
+        }
+        match self.wait {
+            LockWait::Wait => {}
+            LockWait::NoWait => s.push_str(" NOWAIT"),
+            LockWait::SkipLocked => s.push_str(" SKIP LOCKED"),
+        }
+        Ok(Some(s))
+    }
+
+    /// SQL Server table hint, e.g. `WITH (UPDLOCK, READPAST)`.
+    pub(crate) fn table_hint(&self) -> String {
+        let mut hints = vec![match self.strength {
+            LockStrength::Share | LockStrength::KeyShare => "HOLDLOCK",
+            LockStrength::Update | LockStrength::NoKeyUpdate => "UPDLOCK",
+        }];
+        match self.wait {
+            LockWait::Wait => {}
+            LockWait::NoWait => hints.push("NOWAIT"),
+            LockWait::SkipLocked => hints.push("READPAST"),
+        }
+        format!("WITH ({})", hints.join(", "))
+    }
+
+    /// Whether the table expression (`t`, `t AS a` or `t a`) should carry the hint.
+    pub(crate) fn locks_table(&self, table: &str) -> bool {
+        if self.of.is_empty() {
+            return true;
+        }
+        let first = table.split_whitespace().next().unwrap_or_default();
+        let last = table.split_whitespace().last().unwrap_or_default();
+        self.of.iter().any(|t| t == first || t == last)
+    }
+}
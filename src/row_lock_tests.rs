@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests {
+    use crate::args::BuildError;
+    use crate::flavor::Flavor;
+    use crate::modifiers::Builder;
+    use crate::row_lock::RowLock;
+    use crate::select::SelectBuilder;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn row_lock_skip_locked() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id"]);
+        sb.from(vec!["jobs"]);
+        let cond = sb.equal("state", "queued");
+        sb.where_(vec![cond]);
+        sb.limit(1);
+        sb.lock(RowLock::update().skip_locked());
+
+        let (sql, _) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id FROM jobs WHERE state = $1 LIMIT $2 FOR UPDATE SKIP LOCKED"
+        );
+        let (sql, _) = sb.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id FROM jobs WHERE state = ? LIMIT ? FOR UPDATE SKIP LOCKED"
+        );
+        let (sql, _) = sb.build_with_flavor(Flavor::SQLServer, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id FROM jobs WITH (UPDLOCK, READPAST) WHERE state = @p1 \
+             ORDER BY 1 OFFSET 0 ROWS FETCH NEXT @p2 ROWS ONLY"
+        );
+    }
+
+    #[test]
+    fn row_lock_strength_of_and_nowait() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["o.id"]);
+        sb.from(vec!["orders o"]);
+        sb.join("customers c", vec!["c.id = o.customer_id"]);
+        sb.lock(RowLock::no_key_update().of(vec!["o"]).nowait());
+
+        let (sql, _) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT o.id FROM orders o JOIN customers c ON c.id = o.customer_id \
+             FOR NO KEY UPDATE OF o NOWAIT"
+        );
+        assert_eq!(
+            sb.try_build_with_flavor(Flavor::MySQL, &[]).unwrap_err(),
+            BuildError::Unsupported {
+                flavor: Flavor::MySQL,
+                feature: "FOR NO KEY UPDATE/FOR KEY SHARE",
+            }
+        );
+
+        sb.lock(RowLock::update().of(vec!["o"]).nowait());
+        let (sql, _) = sb.build_with_flavor(Flavor::SQLServer, &[]);
+        assert_eq!(
+            sql,
+            "SELECT o.id FROM orders o WITH (UPDLOCK, NOWAIT) \
+             JOIN customers c ON c.id = o.customer_id"
+        );
+        let (sql, _) = sb.build_with_flavor(Flavor::Oracle, &[]);
+        assert_eq!(
+            sql,
+            "SELECT o.id FROM orders o JOIN customers c ON c.id = o.customer_id \
+             FOR UPDATE OF o NOWAIT"
+        );
+
+        sb.lock(RowLock::key_share());
+        let (sql, _) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert!(sql.ends_with(" FOR KEY SHARE"));
+    }
+
+    #[test]
+    fn row_lock_share_modes() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["*"]);
+        sb.from(vec!["accounts"]);
+        sb.for_share();
+
+        let (sql, _) = sb.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(sql, "SELECT * FROM accounts FOR SHARE");
+        let (sql, _) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(sql, "SELECT * FROM accounts FOR SHARE");
+        let (sql, _) = sb.build_with_flavor(Flavor::SQLServer, &[]);
+        assert_eq!(sql, "SELECT * FROM accounts WITH (HOLDLOCK)");
+
+        sb.lock(RowLock::share().skip_locked());
+        let (sql, _) = sb.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(sql, "SELECT * FROM accounts FOR SHARE SKIP LOCKED");
+    }
+
+    #[test]
+    fn row_lock_unsupported_flavors() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["*"]);
+        sb.from(vec!["jobs"]);
+        sb.for_update();
+        sb.sql("/* locked */");
+
+        for flavor in [
+            Flavor::SQLite,
+            Flavor::ClickHouse,
+            Flavor::CQL,
+            Flavor::Presto,
+            Flavor::Doris,
+        ] {
+            assert_eq!(
+                sb.try_build_with_flavor(flavor, &[]).unwrap_err(),
+                BuildError::Unsupported {
+                    flavor,
+                    feature: "row locking",
+                }
+            );
+        }
+
+        let (sql, _) = sb.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(sql, "SELECT * FROM jobs FOR UPDATE /* locked */");
+    }
+
+    #[test]
+    #[should_panic(expected = "row locking is not supported by SQLite")]
+    fn row_lock_build_panics_without_row_locking() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["*"]);
+        sb.from(vec!["jobs"]);
+        sb.for_update();
+        sb.build_with_flavor(Flavor::SQLite, &[]);
+    }
+}
@@ -1,17 +1,24 @@
 //! SelectBuilder: build SELECT statements with composable clauses.
 
-use crate::args::Args;
+use crate::args::{Args, BuildError, unwrap_or_panic};
 use crate::cond::{ArgsRef, Cond};
 use crate::cte::CTEBuilder;
 use crate::flavor::Flavor;
 use crate::injection::{Injection, InjectionMarker};
+use crate::keyset::Keyset;
 use crate::macros::{IntoStrings, collect_into_strings};
 use crate::modifiers::{Arg, Builder};
+use crate::order_by::{Direction, OrderByScope, OrderTerm, write_order_by};
+use crate::row_lock::RowLock;
+use crate::shared::{CowArc, Shared};
+use crate::sql_dialect::Pagination;
 use crate::string_builder::StringBuilder;
-use crate::where_clause::{WhereClause, WhereClauseBuilder, WhereClauseRef};
-use std::cell::RefCell;
+use crate::where_clause::{
+    WhereClause, WhereClauseBuilder, WhereClauseRef, copy_where_clause, rebind_where_var,
+};
+use crate::window::WindowSpec;
 use std::ops::Deref;
-use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
 
 const SELECT_MARKER_INIT: InjectionMarker = 0;
 const SELECT_MARKER_AFTER_WITH: InjectionMarker = 1;
@@ -23,6 +30,31 @@ const SELECT_MARKER_AFTER_GROUP_BY: InjectionMarker = 6;
 const SELECT_MARKER_AFTER_ORDER_BY: InjectionMarker = 7;
 const SELECT_MARKER_AFTER_LIMIT: InjectionMarker = 8;
 const SELECT_MARKER_AFTER_FOR: InjectionMarker = 9;
+const SELECT_MARKER_AFTER_WINDOW: InjectionMarker = 10;
+
+/// One element of a GROUP BY list.
+#[derive(Debug, Clone)]
+enum GroupingElement {
+    Column(String),
+    Rollup(Vec<String>),
+    Cube(Vec<String>),
+    GroupingSets(Vec<Vec<String>>),
+}
+
+impl GroupingElement {
+    fn render(&self) -> String {
+        match self {
+            Self::Column(c) => c.clone(),
+            Self::Rollup(cols) => format!("ROLLUP ({})", cols.join(", ")),
+            Self::Cube(cols) => format!("CUBE ({})", cols.join(", ")),
+            Self::GroupingSets(sets) => {
+                let sets: Vec<String> =
+                    sets.iter().map(|s| format!("({})", s.join(", "))).collect();
+                format!("GROUPING SETS ({})", sets.join(", "))
+            }
+        }
+    }
+}
 
 /// JoinOption variants for explicit join keywords.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -37,7 +69,7 @@ pub enum JoinOption {
 }
 
 impl JoinOption {
-    fn as_str(self) -> &'static str {
+    pub(crate) fn as_str(self) -> &'static str {
         match self {
             Self::FullJoin => "FULL",
             Self::FullOuterJoin => "FULL OUTER",
@@ -50,31 +82,131 @@ impl JoinOption {
     }
 }
 
+#[derive(Debug, Clone)]
+enum JoinKind {
+    Join(Option<JoinOption>),
+    Cross,
+    Natural(Option<JoinOption>),
+    Lateral { outer: bool },
+}
+
+#[derive(Debug, Clone)]
+enum JoinTarget {
+    Table(String),
+    Subquery { var: String, alias: String },
+}
+
+#[derive(Debug, Clone)]
+enum JoinConstraint {
+    None,
+    On(Vec<String>),
+    Using(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+struct Join {
+    kind: JoinKind,
+    target: JoinTarget,
+    constraint: JoinConstraint,
+}
+
+impl Join {
+    fn render(
+        &self,
+        flavor: Flavor,
+        hinted: &dyn Fn(&String) -> String,
+    ) -> Result<String, BuildError> {
+        let unsupported = |feature| BuildError::Unsupported { flavor, feature };
+        let target = match &self.target {
+            JoinTarget::Table(t) => hinted(t),
+            // Oracle does not accept AS before a table alias.
+            JoinTarget::Subquery { var, alias } if flavor == Flavor::Oracle => {
+                format!("({var}) {alias}")
+            }
+            JoinTarget::Subquery { var, alias } => format!("({var}) AS {alias}"),
+        };
+
+        let mut s = match self.kind {
+            JoinKind::Join(opt) => match opt {
+                Some(opt) => format!("{} JOIN {target}", opt.as_str()),
+                None => format!("JOIN {target}"),
+            },
+            JoinKind::Cross => format!("CROSS JOIN {target}"),
+            JoinKind::Natural(opt) => {
+                if flavor == Flavor::SQLServer {
+                    return Err(unsupported("NATURAL JOIN"));
+                }
+                match opt {
+                    Some(opt) => format!("NATURAL {} JOIN {target}", opt.as_str()),
+                    None => format!("NATURAL JOIN {target}"),
+                }
+            }
+            JoinKind::Lateral { outer } => match flavor {
+                Flavor::SQLServer | Flavor::Oracle if outer => format!("OUTER APPLY {target}"),
+                Flavor::SQLServer | Flavor::Oracle => format!("CROSS APPLY {target}"),
+                Flavor::SQLite
+                | Flavor::CQL
+                | Flavor::ClickHouse
+                | Flavor::Doris
+                | Flavor::Informix => return Err(unsupported("LATERAL join")),
+                _ if outer => format!("LEFT JOIN LATERAL {target} ON TRUE"),
+                _ => format!("CROSS JOIN LATERAL {target}"),
+            },
+        };
+
+        match &self.constraint {
+            JoinConstraint::None => {}
+            JoinConstraint::On(exprs) => {
+                let on: Vec<&str> = exprs
+                    .iter()
+                    .filter(|s| !s.is_empty())
+                    .map(String::as_str)
+                    .collect();
+                if !on.is_empty() {
+                    s.push_str(" ON ");
+                    s.push_str(&on.join(" AND "));
+                }
+            }
+            JoinConstraint::Using(cols) => {
+                if flavor == Flavor::SQLServer {
+                    return Err(unsupported("JOIN ... USING"));
+                }
+                s.push_str(" USING (");
+                s.push_str(&cols.join(", "));
+                s.push(')');
+            }
+        }
+        Ok(s)
+    }
+}
+
 #[derive(Debug)]
 pub struct SelectBuilder {
     args: ArgsRef,
     cond: Cond,
 
     distinct: bool,
-    tables: Vec<String>,
-    select_cols: Vec<String>,
+    tables: CowArc<Vec<String>>,
+    select_cols: CowArc<Vec<String>>,
 
-    join_options: Vec<Option<JoinOption>>,
-    join_tables: Vec<String>,
-    join_exprs: Vec<Vec<String>>,
+    joins: CowArc<Vec<Join>>,
 
     where_clause: Option<WhereClauseRef>,
     where_var: Option<String>,
+    // Set by `clone_builder`: `where_var` still renders the original's clause until rebound.
+    where_stale: AtomicBool,
     cte_var: Option<String>,
     cte: Option<CTEBuilder>,
 
-    having_exprs: Vec<String>,
-    group_by_cols: Vec<String>,
-    order_by_cols: Vec<String>,
+    having_exprs: CowArc<Vec<String>>,
+    group_by_cols: CowArc<Vec<GroupingElement>>,
+    // Named windows: (name, rendered spec).
+    windows: CowArc<Vec<(String, String)>>,
+    order_by_cols: CowArc<Vec<OrderTerm>>,
     order: Option<&'static str>,
     limit_var: Option<String>,
     offset_var: Option<String>,
-    for_what: Option<&'static str>,
+    row_lock: Option<RowLock>,
 
     injection: Injection,
     marker: InjectionMarker,
@@ -89,28 +221,28 @@ impl Deref for SelectBuilder {
 
 impl SelectBuilder {
     pub fn new() -> Self {
-        let args = Rc::new(RefCell::new(Args::default()));
+        let args = Shared::new(Args::default());
         let cond = Cond::with_args(args.clone());
         Self {
             args,
             cond,
             distinct: false,
-            tables: Vec::new(),
-            select_cols: Vec::new(),
-            join_options: Vec::new(),
-            join_tables: Vec::new(),
-            join_exprs: Vec::new(),
+            tables: CowArc::default(),
+            select_cols: CowArc::default(),
+            joins: CowArc::default(),
             where_clause: None,
             where_var: None,
+            where_stale: AtomicBool::new(false),
             cte_var: None,
             cte: None,
-            having_exprs: Vec::new(),
-            group_by_cols: Vec::new(),
-            order_by_cols: Vec::new(),
+            having_exprs: CowArc::default(),
+            group_by_cols: CowArc::default(),
+            windows: CowArc::default(),
+            order_by_cols: CowArc::default(),
             order: None,
             limit_var: None,
             offset_var: None,
-            for_what: None,
+            row_lock: None,
             injection: Injection::new(),
             marker: SELECT_MARKER_INIT,
         }
@@ -148,11 +280,11 @@ impl SelectBuilder {
         }
 
         if cte_tables.is_empty() {
-            return self.tables.clone();
+            return self.tables.to_vec();
         }
 
         let mut out = Vec::with_capacity(self.tables.len() + cte_tables.len());
-        out.extend(self.tables.clone());
+        out.extend(self.tables.iter().cloned());
         out.extend(cte_tables);
         out
     }
@@ -215,55 +347,93 @@ impl SelectBuilder {
     }
 
     pub fn clone_builder(&self) -> Self {
-        let old_args = self.args.borrow().clone();
-        let args = Rc::new(RefCell::new(old_args));
+        // Args, where clause and list fields are copy-on-write, so this is O(1); the clone's
+        // where placeholder is rebound on its first build.
+        let args = Shared::new(self.args.snapshot());
         let cond = Cond::with_args(args.clone());
 
-        let mut cloned = Self {
+        Self {
             args,
             cond,
             distinct: self.distinct,
             tables: self.tables.clone(),
             select_cols: self.select_cols.clone(),
-            join_options: self.join_options.clone(),
-            join_tables: self.join_tables.clone(),
-            join_exprs: self.join_exprs.clone(),
-            where_clause: self.where_clause.clone(),
+            joins: self.joins.clone(),
+            where_clause: self.where_clause.as_ref().map(copy_where_clause),
             where_var: self.where_var.clone(),
+            where_stale: AtomicBool::new(self.where_clause.is_some()),
             cte_var: self.cte_var.clone(),
             cte: self.cte.clone(),
             having_exprs: self.having_exprs.clone(),
             group_by_cols: self.group_by_cols.clone(),
+            windows: self.windows.clone(),
             order_by_cols: self.order_by_cols.clone(),
             order: self.order,
             limit_var: self.limit_var.clone(),
             offset_var: self.offset_var.clone(),
-            for_what: self.for_what,
+            row_lock: self.row_lock.clone(),
             injection: self.injection.clone(),
             marker: self.marker,
-        };
+        }
+    }
 
-        // Deep clone WhereClause and fix Builder placeholder in args to point to the new WhereClause
-        if let (Some(wc), Some(ph)) = (&self.where_clause, &self.where_var) {
-            let new_wc = Rc::new(RefCell::new(wc.borrow().clone()));
-            cloned.where_clause = Some(new_wc.clone());
-            cloned
-                .args
-                .borrow_mut()
-                .replace(ph, Arg::Builder(Box::new(WhereClauseBuilder::new(new_wc))));
+    /// CountBuilder: derive the total-count query. ORDER BY, LIMIT/OFFSET and row locks are
+    /// dropped; DISTINCT, GROUP BY or named windows wrap the query as
+    /// `SELECT COUNT(*) FROM (<query>) t`.
+    pub fn count_builder(&self) -> Self {
+        let mut inner = self.clone_builder();
+        inner.order_by_cols.clear();
+        inner.order = None;
+        inner.limit_var = None;
+        inner.offset_var = None;
+        inner.row_lock = None;
+
+        if !inner.distinct && inner.group_by_cols.is_empty() && inner.windows.is_empty() {
+            inner.select_cols = vec!["COUNT(*)".to_string()].into();
+            return inner;
         }
 
-        if let (Some(cte), Some(ph)) = (&self.cte, &self.cte_var) {
-            let new_cte = cte.clone();
-            let new_cte_for_field = new_cte.clone();
-            cloned.cte = Some(new_cte_for_field);
-            cloned
-                .args
-                .borrow_mut()
-                .replace(ph, Arg::Builder(Box::new(new_cte)));
+        let mut outer = Self::new();
+        outer.set_flavor(self.flavor());
+        let sub = outer.var(Arg::Builder(Box::new(inner)));
+        outer.select(vec!["COUNT(*)"]);
+        outer.from(vec![format!("({sub}) t")]);
+        outer
+    }
+
+    /// Keyset: seek pagination. Puts the keys first in ORDER BY (existing terms follow as
+    /// tie-breakers, keeping a legacy `asc()`/`desc()` on the last of them), adds the seek predicate past the last-seen values to WHERE, and the page
+    /// size as LIMIT. The predicate is a row-value comparison when all keys share a direction and
+    /// the flavor supports it, and the expanded OR/AND form otherwise.
+    pub fn keyset(&mut self, keyset: &Keyset) -> &mut Self {
+        if let Some(pred) = keyset.predicate(&self.cond) {
+            self.where_(vec![pred]);
+        }
+        let keys = keyset.keys().iter().map(|(col, dir)| {
+            let term = OrderTerm::new(col.as_str());
+            match dir {
+                Direction::Asc => term.asc(),
+                Direction::Desc => term.desc(),
+            }
+        });
+        if let (Some(order), Some(last)) = (self.order, self.order_by_cols.last_mut())
+            && last.apply_legacy_order(order)
+        {
+            self.order = None;
+        }
+        self.order_by_cols.splice(0..0, keys);
+        self.marker = SELECT_MARKER_AFTER_ORDER_BY;
+        if let Some(n) = keyset.page_size() {
+            self.limit(n);
         }
+        self
+    }
 
-        cloned
+    /// Paginate: `(page query, count query)`; `page` is 1-based like `Chain::page`.
+    pub fn paginate(&self, page: i64, size: i64) -> (Self, Self) {
+        let mut page_builder = self.clone_builder();
+        page_builder.offset((page - 1) * size).limit(size);
+        (page_builder, self.count_builder())
     }
 
     pub fn build(&self) -> (String, Vec<Arg>) {
@@ -278,7 +448,7 @@ impl SelectBuilder {
     where
         T: IntoStrings,
     {
-        self.select_cols = collect_into_strings(cols);
+        self.select_cols = collect_into_strings(cols).into();
         self.marker = SELECT_MARKER_AFTER_SELECT;
         self
     }
@@ -302,7 +472,7 @@ impl SelectBuilder {
     where
         T: IntoStrings,
     {
-        self.tables = collect_into_strings(tables);
+        self.tables = collect_into_strings(tables).into();
         self.marker = SELECT_MARKER_AFTER_FROM;
         self
     }
@@ -317,9 +487,107 @@ impl SelectBuilder {
         table: impl Into<String>,
         on_expr: impl IntoStrings,
     ) -> &mut Self {
-        self.join_options.push(option);
-        self.join_tables.push(table.into());
-        self.join_exprs.push(collect_into_strings(on_expr));
+        self.push_join(
+            JoinKind::Join(option),
+            JoinTarget::Table(table.into()),
+            JoinConstraint::On(collect_into_strings(on_expr)),
+        )
+    }
+
+    /// CrossJoin: `CROSS JOIN table`.
+    pub fn cross_join(&mut self, table: impl Into<String>) -> &mut Self {
+        self.push_join(
+            JoinKind::Cross,
+            JoinTarget::Table(table.into()),
+            JoinConstraint::None,
+        )
+    }
+
+    /// NaturalJoin: `NATURAL [option] JOIN table`.
+    pub fn natural_join(
+        &mut self,
+        option: Option<JoinOption>,
+        table: impl Into<String>,
+    ) -> &mut Self {
+        self.push_join(
+            JoinKind::Natural(option),
+            JoinTarget::Table(table.into()),
+            JoinConstraint::None,
+        )
+    }
+
+    /// JoinUsing: `[option] JOIN table USING (cols)`.
+    pub fn join_using<T>(
+        &mut self,
+        option: Option<JoinOption>,
+        table: impl Into<String>,
+        cols: T,
+    ) -> &mut Self
+    where
+        T: IntoStrings,
+    {
+        self.push_join(
+            JoinKind::Join(option),
+            JoinTarget::Table(table.into()),
+            JoinConstraint::Using(collect_into_strings(cols)),
+        )
+    }
+
+    /// JoinSubquery: `[option] JOIN (subquery) AS alias ON ...`; the subquery's args are merged
+    /// at build time.
+    pub fn join_subquery(
+        &mut self,
+        option: Option<JoinOption>,
+        builder: impl Builder + 'static,
+        alias: &str,
+        on_expr: impl IntoStrings,
+    ) -> &mut Self {
+        let var = self.var(Arg::Builder(Box::new(builder)));
+        self.push_join(
+            JoinKind::Join(option),
+            JoinTarget::Subquery {
+                var,
+                alias: alias.to_string(),
+            },
+            JoinConstraint::On(collect_into_strings(on_expr)),
+        )
+    }
+
+    /// JoinLateral: `CROSS JOIN LATERAL (subquery) AS alias`; `CROSS APPLY` on SQL Server and
+    /// Oracle.
+    pub fn join_lateral(&mut self, builder: impl Builder + 'static, alias: &str) -> &mut Self {
+        self.lateral(false, builder, alias)
+    }
+
+    /// LeftJoinLateral: `LEFT JOIN LATERAL (subquery) AS alias ON TRUE`; `OUTER APPLY` on SQL
+    /// Server and Oracle.
+    pub fn left_join_lateral(&mut self, builder: impl Builder + 'static, alias: &str) -> &mut Self {
+        self.lateral(true, builder, alias)
+    }
+
+    fn lateral(&mut self, outer: bool, builder: impl Builder + 'static, alias: &str) -> &mut Self {
+        let var = self.var(Arg::Builder(Box::new(builder)));
+        self.push_join(
+            JoinKind::Lateral { outer },
+            JoinTarget::Subquery {
+                var,
+                alias: alias.to_string(),
+            },
+            JoinConstraint::None,
+        )
+    }
+
+    fn push_join(
+        &mut self,
+        kind: JoinKind,
+        target: JoinTarget,
+        constraint: JoinConstraint,
+    ) -> &mut Self {
+        self.joins.push(Join {
+            kind,
+            target,
+            constraint,
+        });
         self.marker = SELECT_MARKER_AFTER_JOIN;
         self
     }
@@ -389,28 +657,90 @@ impl SelectBuilder {
     where
         T: IntoStrings,
     {
-        self.group_by_cols.extend(collect_into_strings(cols));
+        self.group_by_cols.extend(
+            collect_into_strings(cols)
+                .into_iter()
+                .map(GroupingElement::Column),
+        );
         self.marker = SELECT_MARKER_AFTER_GROUP_BY;
         self
     }
 
+    /// GroupByRollup: `ROLLUP (cols)`; MySQL renders `cols WITH ROLLUP` when it is the only
+    /// grouping element.
+    pub fn group_by_rollup<T>(&mut self, cols: T) -> &mut Self
+    where
+        T: IntoStrings,
+    {
+        self.group_by_cols
+            .push(GroupingElement::Rollup(collect_into_strings(cols)));
+        self.marker = SELECT_MARKER_AFTER_GROUP_BY;
+        self
+    }
+
+    /// GroupByCube: `CUBE (cols)`.
+    pub fn group_by_cube<T>(&mut self, cols: T) -> &mut Self
+    where
+        T: IntoStrings,
+    {
+        self.group_by_cols
+            .push(GroupingElement::Cube(collect_into_strings(cols)));
+        self.marker = SELECT_MARKER_AFTER_GROUP_BY;
+        self
+    }
+
+    /// GroupByGroupingSets: `GROUPING SETS ((a, b), (a), ())`; an empty set is the grand total.
+    pub fn group_by_grouping_sets<I, T>(&mut self, sets: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: IntoStrings,
+    {
+        self.group_by_cols.push(GroupingElement::GroupingSets(
+            sets.into_iter().map(collect_into_strings).collect(),
+        ));
+        self.marker = SELECT_MARKER_AFTER_GROUP_BY;
+        self
+    }
+
+    /// Window: add a named window, emitted as `WINDOW name AS (...)` before ORDER BY.
+    pub fn window(&mut self, name: &str, spec: &WindowSpec) -> &mut Self {
+        let rendered = spec.render(&mut |a| self.var(a));
+        self.windows.push((name.to_string(), rendered));
+        self.marker = SELECT_MARKER_AFTER_WINDOW;
+        self
+    }
+
+    /// Over: `func OVER (spec)`, e.g. `sb.over("ROW_NUMBER()", &spec)`.
+    pub fn over(&self, func: &str, spec: &WindowSpec) -> String {
+        format!("{func} OVER ({})", spec.render(&mut |a| self.var(a)))
+    }
+
     pub fn order_by<T>(&mut self, cols: T) -> &mut Self
     where
         T: IntoStrings,
     {
-        self.order_by_cols.extend(collect_into_strings(cols));
+        self.order_by_cols
+            .extend(collect_into_strings(cols).into_iter().map(OrderTerm::new));
+        self.marker = SELECT_MARKER_AFTER_ORDER_BY;
+        self
+    }
+
+    /// OrderByTerm: append a structured term with its own direction and NULLS placement.
+    pub fn order_by_term(&mut self, term: impl Into<OrderTerm>) -> &mut Self {
+        let term = term.into().bind(|a| self.var(a));
+        self.order_by_cols.push(term);
         self.marker = SELECT_MARKER_AFTER_ORDER_BY;
         self
     }
 
     pub fn order_by_asc(&mut self, col: impl Into<String>) -> &mut Self {
-        self.order_by_cols.push(format!("{} ASC", col.into()));
+        self.order_by_cols.push(OrderTerm::new(col).asc());
         self.marker = SELECT_MARKER_AFTER_ORDER_BY;
         self
     }
 
     pub fn order_by_desc(&mut self, col: impl Into<String>) -> &mut Self {
-        self.order_by_cols.push(format!("{} DESC", col.into()));
+        self.order_by_cols.push(OrderTerm::new(col).desc());
         self.marker = SELECT_MARKER_AFTER_ORDER_BY;
         self
     }
@@ -448,13 +778,17 @@ impl SelectBuilder {
     }
 
     pub fn for_update(&mut self) -> &mut Self {
-        self.for_what = Some("UPDATE");
-        self.marker = SELECT_MARKER_AFTER_FOR;
-        self
+        self.lock(RowLock::update())
     }
 
     pub fn for_share(&mut self) -> &mut Self {
-        self.for_what = Some("SHARE");
+        self.lock(RowLock::share())
+    }
+
+    /// Lock: set the row-locking clause, e.g. `RowLock::update().skip_locked()`. SQL Server
+    /// renders it as table hints after the locked tables.
+    pub fn lock(&mut self, lock: RowLock) -> &mut Self {
+        self.row_lock = Some(lock);
         self.marker = SELECT_MARKER_AFTER_FOR;
         self
     }
@@ -489,8 +823,18 @@ impl Default for SelectBuilder {
     }
 }
 
-impl Builder for SelectBuilder {
-    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+impl SelectBuilder {
+    fn rebind_where_var(&self) {
+        rebind_where_var(
+            &self.args,
+            self.where_clause.as_ref(),
+            self.where_var.as_ref(),
+            &self.where_stale,
+        );
+    }
+
+    /// Build the `$`-format; the where clause and nested builders stay placeholders until compile.
+    fn build_format(&self, flavor: Flavor) -> Result<String, BuildError> {
         let mut buf = StringBuilder::new();
         write_injection(&mut buf, &self.injection, SELECT_MARKER_INIT);
 
@@ -509,7 +853,16 @@ impl Builder for SelectBuilder {
         }
         write_injection(&mut buf, &self.injection, SELECT_MARKER_AFTER_SELECT);
 
-        let table_names = self.table_names();
+        let lock_hint = self
+            .row_lock
+            .as_ref()
+            .filter(|l| flavor == Flavor::SQLServer && l.validate(flavor).is_ok());
+        let hinted = |table: &String| match lock_hint {
+            Some(l) if l.locks_table(table) => format!("{table} {}", l.table_hint()),
+            _ => table.clone(),
+        };
+
+        let table_names: Vec<String> = self.table_names().iter().map(hinted).collect();
         if !table_names.is_empty() {
             buf.write_leading("FROM");
             buf.write_str(" ");
@@ -517,25 +870,10 @@ impl Builder for SelectBuilder {
         }
         write_injection(&mut buf, &self.injection, SELECT_MARKER_AFTER_FROM);
 
-        for i in 0..self.join_tables.len() {
-            if let Some(opt) = self.join_options[i] {
-                buf.write_leading(opt.as_str());
-            }
-            buf.write_leading("JOIN");
-            buf.write_str(" ");
-            buf.write_str(&self.join_tables[i]);
-
-            let on = self.join_exprs[i]
-                .iter()
-                .filter(|s| !s.is_empty())
-                .cloned()
-                .collect::<Vec<_>>();
-            if !on.is_empty() {
-                buf.write_str(" ON ");
-                buf.write_str(&on.join(" AND "));
-            }
+        for join in &self.joins {
+            buf.write_leading(&join.render(flavor, &hinted)?);
         }
-        if !self.join_tables.is_empty() {
+        if !self.joins.is_empty() {
             write_injection(&mut buf, &self.injection, SELECT_MARKER_AFTER_JOIN);
         }
 
@@ -545,9 +883,10 @@ impl Builder for SelectBuilder {
         }
 
         if !self.group_by_cols.is_empty() {
+            let group_by = self.group_by_list(flavor)?;
             buf.write_leading("GROUP BY");
             buf.write_str(" ");
-            buf.write_str(&self.group_by_cols.join(", "));
+            buf.write_str(&group_by);
             let having = self
                 .having_exprs
                 .iter()
@@ -561,116 +900,128 @@ impl Builder for SelectBuilder {
             write_injection(&mut buf, &self.injection, SELECT_MARKER_AFTER_GROUP_BY);
         }
 
-        if !self.order_by_cols.is_empty() {
-            buf.write_leading("ORDER BY");
+        if !self.windows.is_empty() {
+            let windows: Vec<String> = self
+                .windows
+                .iter()
+                .map(|(name, spec)| format!("{name} AS ({spec})"))
+                .collect();
+            buf.write_leading("WINDOW");
             buf.write_str(" ");
-            buf.write_str(&self.order_by_cols.join(", "));
-            if let Some(order) = self.order {
-                buf.write_str(" ");
-                buf.write_str(order);
-            }
+            buf.write_str(&windows.join(", "));
+            write_injection(&mut buf, &self.injection, SELECT_MARKER_AFTER_WINDOW);
+        }
+
+        if !self.order_by_cols.is_empty() {
+            let scope = if self.distinct {
+                OrderByScope::Distinct
+            } else {
+                OrderByScope::Statement
+            };
+            write_order_by(&mut buf, &self.order_by_cols, self.order, flavor, scope)?;
             write_injection(&mut buf, &self.injection, SELECT_MARKER_AFTER_ORDER_BY);
         }
 
         // LIMIT/OFFSET rules follow each flavor's expected behavior
-        match flavor {
-            Flavor::MySQL | Flavor::SQLite | Flavor::ClickHouse => {
-                if let Some(lim) = &self.limit_var {
-                    buf.write_leading("LIMIT");
-                    buf.write_str(" ");
-                    buf.write_str(lim);
-                    if let Some(off) = &self.offset_var {
-                        buf.write_leading("OFFSET");
-                        buf.write_str(" ");
-                        buf.write_str(off);
-                    }
-                }
-            }
-            Flavor::CQL => {
-                if let Some(lim) = &self.limit_var {
-                    buf.write_leading("LIMIT");
-                    buf.write_str(" ");
-                    buf.write_str(lim);
-                }
-            }
-            Flavor::PostgreSQL => {
-                if let Some(lim) = &self.limit_var {
-                    buf.write_leading("LIMIT");
-                    buf.write_str(" ");
-                    buf.write_str(lim);
-                }
-                if let Some(off) = &self.offset_var {
-                    buf.write_leading("OFFSET");
-                    buf.write_str(" ");
-                    buf.write_str(off);
-                }
-            }
-            Flavor::Presto => {
-                if let Some(off) = &self.offset_var {
-                    buf.write_leading("OFFSET");
-                    buf.write_str(" ");
-                    buf.write_str(off);
-                }
-                if let Some(lim) = &self.limit_var {
-                    buf.write_leading("LIMIT");
-                    buf.write_str(" ");
-                    buf.write_str(lim);
-                }
-            }
-            Flavor::SQLServer | Flavor::Oracle => {
-                if self.order_by_cols.is_empty()
-                    && (self.limit_var.is_some() || self.offset_var.is_some())
-                    && flavor == Flavor::SQLServer
-                {
-                    buf.write_leading("ORDER BY 1");
-                }
+        flavor.dialect().write_pagination(
+            buf.as_mut_string(),
+            &Pagination {
+                limit: self.limit_var.as_deref(),
+                offset: self.offset_var.as_deref(),
+                has_order_by: !self.order_by_cols.is_empty(),
+            },
+        );
 
-                if let Some(off) = &self.offset_var {
-                    buf.write_leading("OFFSET");
-                    buf.write_str(" ");
-                    buf.write_str(off);
-                    buf.write_str(" ROWS");
-                }
+        if self.limit_var.is_some() {
+            write_injection(&mut buf, &self.injection, SELECT_MARKER_AFTER_LIMIT);
+        }
 
-                if let Some(lim) = &self.limit_var {
-                    if self.offset_var.is_none() {
-                        buf.write_leading("OFFSET 0 ROWS");
-                    }
-                    buf.write_leading("FETCH NEXT");
-                    buf.write_str(" ");
-                    buf.write_str(lim);
-                    buf.write_str(" ROWS ONLY");
-                }
-            }
-            Flavor::Informix | Flavor::Doris => {
-                // Future: handle Informix/Doris specific quirks if needed
-                if let Some(lim) = &self.limit_var {
-                    buf.write_leading("LIMIT");
-                    buf.write_str(" ");
-                    buf.write_str(lim);
-                    if let Some(off) = &self.offset_var {
-                        buf.write_leading("OFFSET");
-                        buf.write_str(" ");
-                        buf.write_str(off);
-                    }
-                }
+        if let Some(lock) = &self.row_lock {
+            if let Some(clause) = lock.clause(flavor)? {
+                buf.write_leading(&clause);
             }
+            write_injection(&mut buf, &self.injection, SELECT_MARKER_AFTER_FOR);
         }
 
-        if self.limit_var.is_some() {
-            write_injection(&mut buf, &self.injection, SELECT_MARKER_AFTER_LIMIT);
+        Ok(buf.into_string())
+    }
+
+    fn group_by_standard(&self) -> String {
+        let items: Vec<String> = self.group_by_cols.iter().map(|g| g.render()).collect();
+        items.join(", ")
+    }
+
+    fn group_by_list(&self, flavor: Flavor) -> Result<String, BuildError> {
+        let unsupported = |feature| BuildError::Unsupported { flavor, feature };
+        let grouped = self
+            .group_by_cols
+            .iter()
+            .any(|g| !matches!(g, GroupingElement::Column(_)));
+        if !grouped {
+            return Ok(self.group_by_standard());
         }
 
-        if let Some(what) = self.for_what {
-            buf.write_leading("FOR");
-            buf.write_str(" ");
-            buf.write_str(what);
-            write_injection(&mut buf, &self.injection, SELECT_MARKER_AFTER_FOR);
+        match flavor {
+            Flavor::SQLite | Flavor::CQL => Err(unsupported("ROLLUP/CUBE/GROUPING SETS")),
+            Flavor::MySQL => match self.group_by_cols.as_slice() {
+                [GroupingElement::Rollup(cols)] => Ok(format!("{} WITH ROLLUP", cols.join(", "))),
+                _ if self
+                    .group_by_cols
+                    .iter()
+                    .any(|g| matches!(g, GroupingElement::Cube(_))) =>
+                {
+                    Err(unsupported("CUBE"))
+                }
+                _ if self
+                    .group_by_cols
+                    .iter()
+                    .any(|g| matches!(g, GroupingElement::GroupingSets(_))) =>
+                {
+                    Err(unsupported("GROUPING SETS"))
+                }
+                _ => Err(unsupported("ROLLUP combined with other grouping elements")),
+            },
+            _ => Ok(self.group_by_standard()),
         }
+    }
+}
+
+impl Builder for SelectBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        self.rebind_where_var();
+        let format = unwrap_or_panic(self.build_format(flavor));
+        self.args
+            .snapshot()
+            .compile_with_flavor(&format, flavor, initial_arg)
+    }
 
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        self.rebind_where_var();
+        let format = self.build_format(flavor)?;
         self.args
-            .borrow()
-            .compile_with_flavor(&buf.into_string(), flavor, initial_arg)
+            .snapshot()
+            .try_compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        self.rebind_where_var();
+        let format = self.build_format(flavor)?;
+        self.args
+            .snapshot()
+            .compile_into(&format, flavor, sql, args)
+    }
+
+    fn needs_subquery_in_compound(&self) -> bool {
+        !self.order_by_cols.is_empty() || self.limit_var.is_some() || self.offset_var.is_some()
     }
 
     fn flavor(&self) -> Flavor {
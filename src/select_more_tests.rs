@@ -1,8 +1,11 @@
 #[cfg(test)]
 mod tests {
+    use crate::args::BuildError;
     use crate::flavor::Flavor;
+    use crate::modifiers::raw;
     use crate::modifiers::{Arg, Builder, flatten};
-    use crate::select::SelectBuilder;
+    use crate::select::{JoinOption, SelectBuilder};
+    use crate::window::{FrameBound, FrameUnit, WindowSpec};
     use crate::{from_tables, join_on, order_by_cols, select_cols, where_exprs};
 
     type SelectCase = Box<dyn Fn(&mut SelectBuilder)>;
@@ -208,4 +211,373 @@ mod tests {
 
         assert_eq!(results, expected);
     }
+
+    #[test]
+    fn select_builder_named_window_with_frame_args() {
+        let mut sb = SelectBuilder::new();
+        let spec = WindowSpec::new()
+            .partition_by(["dept"])
+            .order_by_desc("salary")
+            .rows_between(FrameBound::preceding(3_i64), FrameBound::CurrentRow);
+        sb.select(vec!["dept", "SUM(salary) OVER w AS running"]);
+        sb.from(vec!["emp"]);
+        let expr = sb.greater_than("salary", 100_i64);
+        sb.where_(vec![expr]);
+        sb.group_by(vec!["dept", "salary"]);
+        sb.window("w", &spec);
+        sb.order_by(vec!["dept"]);
+        sb.limit(10);
+
+        let (sql, args) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT dept, SUM(salary) OVER w AS running FROM emp WHERE salary > $1 \
+             GROUP BY dept, salary \
+             WINDOW w AS (PARTITION BY dept ORDER BY salary DESC ROWS BETWEEN $2 PRECEDING AND CURRENT ROW) \
+             ORDER BY dept LIMIT $3"
+        );
+        assert_eq!(
+            args,
+            vec![Arg::from(100_i64), Arg::from(3_i64), Arg::from(10_i64)]
+        );
+
+        let (sql, _) = sb.build_with_flavor(Flavor::MySQL, &[]);
+        assert!(sql.contains("ROWS BETWEEN ? PRECEDING AND CURRENT ROW) ORDER BY dept"));
+    }
+
+    #[test]
+    fn select_builder_inline_over_and_multiple_windows() {
+        let mut sb = SelectBuilder::new();
+        let w1 = WindowSpec::new().partition_by(["dept"]);
+        let w2 = WindowSpec::from_window("w1")
+            .order_by_asc("hired_at")
+            .frame(FrameUnit::Range, FrameBound::UnboundedPreceding);
+        let rank = sb.over(
+            "RANK()",
+            &WindowSpec::new().order_by(["score DESC"]).frame_between(
+                FrameUnit::Groups,
+                FrameBound::CurrentRow,
+                FrameBound::following(1_i64),
+            ),
+        );
+        sb.select(vec!["id".to_string(), rank]);
+        sb.from(vec!["emp"]);
+        sb.window("w1", &w1).window("w2", &w2);
+        sb.sql("/* windows */");
+
+        let (sql, args) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, RANK() OVER (ORDER BY score DESC GROUPS BETWEEN CURRENT ROW AND $1 FOLLOWING) \
+             FROM emp WINDOW w1 AS (PARTITION BY dept), \
+             w2 AS (w1 ORDER BY hired_at ASC RANGE UNBOUNDED PRECEDING) /* windows */"
+        );
+        assert_eq!(args, vec![Arg::from(1_i64)]);
+
+        let cloned = sb.clone_builder();
+        assert_eq!(cloned.build_with_flavor(Flavor::PostgreSQL, &[]).0, sql);
+    }
+
+    #[test]
+    fn select_builder_rollup_cube_grouping_sets() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["region", "product", "SUM(amount)"]);
+        sb.from(vec!["sales"]);
+        sb.group_by_rollup(vec!["region", "product"]);
+        sb.having(vec!["SUM(amount) > 0"]);
+
+        let (sql, _) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT region, product, SUM(amount) FROM sales \
+             GROUP BY ROLLUP (region, product) HAVING SUM(amount) > 0"
+        );
+        let (sql, _) = sb.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT region, product, SUM(amount) FROM sales \
+             GROUP BY region, product WITH ROLLUP HAVING SUM(amount) > 0"
+        );
+
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["year", "region", "product", "SUM(amount)"]);
+        sb.from(vec!["sales"]);
+        sb.group_by(vec!["year"]);
+        sb.group_by_cube(vec!["region", "product"]);
+        let (sql, _) = sb.build_with_flavor(Flavor::SQLServer, &[]);
+        assert_eq!(
+            sql,
+            "SELECT year, region, product, SUM(amount) FROM sales GROUP BY year, CUBE (region, product)"
+        );
+
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["region", "product", "SUM(amount)"]);
+        sb.from(vec!["sales"]);
+        sb.group_by_grouping_sets([vec!["region", "product"], vec!["region"], vec![]]);
+        let (sql, _) = sb.build_with_flavor(Flavor::Oracle, &[]);
+        assert_eq!(
+            sql,
+            "SELECT region, product, SUM(amount) FROM sales \
+             GROUP BY GROUPING SETS ((region, product), (region), ())"
+        );
+    }
+
+    #[test]
+    fn select_builder_grouping_unsupported_flavors() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["region", "SUM(amount)"]);
+        sb.from(vec!["sales"]);
+        sb.group_by_cube(vec!["region"]);
+
+        for (flavor, feature) in [
+            (Flavor::SQLite, "ROLLUP/CUBE/GROUPING SETS"),
+            (Flavor::CQL, "ROLLUP/CUBE/GROUPING SETS"),
+            (Flavor::MySQL, "CUBE"),
+        ] {
+            assert_eq!(
+                sb.try_build_with_flavor(flavor, &[]).unwrap_err(),
+                BuildError::Unsupported { flavor, feature }
+            );
+        }
+
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["year", "region"]);
+        sb.from(vec!["sales"]);
+        sb.group_by(vec!["year"]);
+        sb.group_by_rollup(vec!["region"]);
+        assert_eq!(
+            sb.try_build_with_flavor(Flavor::MySQL, &[]).unwrap_err(),
+            BuildError::Unsupported {
+                flavor: Flavor::MySQL,
+                feature: "ROLLUP combined with other grouping elements",
+            }
+        );
+        assert!(sb.try_build_with_flavor(Flavor::PostgreSQL, &[]).is_ok());
+    }
+
+    #[test]
+    fn select_builder_cross_natural_using_joins() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["*"]);
+        sb.from(vec!["sizes"]);
+        sb.cross_join("colors");
+        sb.natural_join(Some(JoinOption::LeftJoin), "stock");
+        sb.join_using(None, "prices", vec!["size_id", "color_id"]);
+
+        let (sql, _) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT * FROM sizes CROSS JOIN colors NATURAL LEFT JOIN stock \
+             JOIN prices USING (size_id, color_id)"
+        );
+
+        assert_eq!(
+            sb.try_build_with_flavor(Flavor::SQLServer, &[])
+                .unwrap_err(),
+            BuildError::Unsupported {
+                flavor: Flavor::SQLServer,
+                feature: "NATURAL JOIN",
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "NATURAL JOIN is not supported by SQLServer")]
+    fn select_builder_unsupported_join_panics_on_build() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["u.*"]);
+        sb.from(vec!["users u"]);
+        sb.natural_join(None, "banned b");
+        sb.build_with_flavor(Flavor::SQLServer, &[]);
+    }
+
+    #[test]
+    fn select_builder_join_on_with_bound_args() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["u.id", "o.total"]);
+        sb.from(vec!["users u"]);
+        let on = vec![
+            sb.equal("o.user_id", raw("u.id")),
+            sb.equal("o.status", "paid"),
+        ];
+        sb.join_with_option(Some(JoinOption::LeftJoin), "orders o", on);
+        let cond = sb.greater_than("u.id", 10_i64);
+        sb.where_(vec![cond]);
+
+        let (sql, args) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT u.id, o.total FROM users u \
+             LEFT JOIN orders o ON o.user_id = u.id AND o.status = $1 WHERE u.id > $2"
+        );
+        assert_eq!(args, vec![Arg::from("paid"), Arg::from(10_i64)]);
+    }
+
+    #[test]
+    fn select_builder_join_subquery_and_lateral() {
+        let mut totals = SelectBuilder::new();
+        totals.select(vec!["user_id", "SUM(total) AS total"]);
+        totals.from(vec!["orders"]);
+        let cond = totals.equal("status", "paid");
+        totals.where_(vec![cond]);
+        totals.group_by(vec!["user_id"]);
+
+        let mut latest = SelectBuilder::new();
+        latest.select(vec!["id", "created_at"]);
+        latest.from(vec!["orders"]);
+        latest.where_(vec!["orders.user_id = u.id"]);
+        latest.order_by_desc("created_at");
+        latest.limit(3);
+
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["u.id", "t.total", "l.id"]);
+        sb.from(vec!["users u"]);
+        sb.join_subquery(None, totals, "t", vec!["t.user_id = u.id"]);
+        sb.left_join_lateral(latest, "l");
+
+        let (sql, args) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT u.id, t.total, l.id FROM users u \
+             JOIN (SELECT user_id, SUM(total) AS total FROM orders WHERE status = $1 GROUP BY user_id) AS t \
+             ON t.user_id = u.id \
+             LEFT JOIN LATERAL (SELECT id, created_at FROM orders WHERE orders.user_id = u.id \
+             ORDER BY created_at DESC LIMIT $2) AS l ON TRUE"
+        );
+        assert_eq!(args, vec![Arg::from("paid"), Arg::from(3_i64)]);
+
+        let (sql, _) = sb.build_with_flavor(Flavor::SQLServer, &[]);
+        assert!(sql.contains(") AS t ON t.user_id = u.id OUTER APPLY (SELECT"));
+        let (sql, _) = sb.build_with_flavor(Flavor::Oracle, &[]);
+        assert!(sql.contains(") t ON t.user_id = u.id OUTER APPLY (SELECT"));
+        assert!(sql.ends_with(") l"));
+
+        assert_eq!(
+            sb.try_build_with_flavor(Flavor::SQLite, &[]).unwrap_err(),
+            BuildError::Unsupported {
+                flavor: Flavor::SQLite,
+                feature: "LATERAL join",
+            }
+        );
+    }
+
+    #[test]
+    fn select_builder_count_builder_and_paginate() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id", "name"]);
+        sb.from(vec!["users"]);
+        let cond = sb.equal("status", "active");
+        sb.where_(vec![cond]);
+        sb.order_by_desc("created_at");
+        sb.limit(5);
+        sb.for_update();
+
+        let (sql, args) = sb
+            .count_builder()
+            .build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(sql, "SELECT COUNT(*) FROM users WHERE status = $1");
+        assert_eq!(args, vec![Arg::from("active")]);
+
+        let (page, count) = sb.paginate(3, 20);
+        let (sql, args) = page.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, name FROM users WHERE status = ? ORDER BY created_at DESC \
+             LIMIT ? OFFSET ? FOR UPDATE"
+        );
+        assert_eq!(
+            args,
+            vec![Arg::from("active"), Arg::from(20_i64), Arg::from(40_i64)]
+        );
+        let (sql, _) = count.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(sql, "SELECT COUNT(*) FROM users WHERE status = ?");
+
+        // The source builder is left untouched.
+        let (sql, _) = sb.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id, name FROM users WHERE status = ? ORDER BY created_at DESC LIMIT ? FOR UPDATE"
+        );
+    }
+
+    #[test]
+    fn select_builder_count_builder_wraps_grouped_queries() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["dept", "COUNT(*) AS n"]);
+        sb.from(vec!["emp"]);
+        let cond = sb.greater_than("salary", 100_i64);
+        sb.where_(vec![cond]);
+        sb.group_by(vec!["dept"]);
+        sb.having(vec!["COUNT(*) > 1"]);
+        sb.order_by(vec!["n"]);
+        sb.limit(10);
+
+        let (sql, args) = sb
+            .count_builder()
+            .build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT COUNT(*) FROM (SELECT dept, COUNT(*) AS n FROM emp WHERE salary > $1 \
+             GROUP BY dept HAVING COUNT(*) > 1) t"
+        );
+        assert_eq!(args, vec![Arg::from(100_i64)]);
+
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["city"]);
+        sb.distinct();
+        sb.from(vec!["users"]);
+        let (sql, _) = sb.count_builder().build_with_flavor(Flavor::Oracle, &[]);
+        assert_eq!(
+            sql,
+            "SELECT COUNT(*) FROM (SELECT DISTINCT city FROM users) t"
+        );
+    }
+
+    #[test]
+    fn clone_builder_is_copy_on_write() {
+        let mut base = SelectBuilder::new();
+        base.select(vec!["id"]).from(vec!["users"]);
+        base.where_(vec![base.equal("status", "active")]);
+
+        let mut by_age = base.clone_builder();
+        by_age.where_(vec![by_age.greater_than("age", 18_i64)]);
+
+        // The original keeps changing after the clone was taken.
+        base.where_(vec![base.is_not_null("email")]);
+
+        // A clone of a clone, filtered through its where clause handle.
+        let by_name = by_age.clone_builder();
+        let cond = by_name.like("name", "a%");
+        by_name
+            .where_clause()
+            .unwrap()
+            .borrow_mut()
+            .add_where_expr(by_name.args.clone(), vec![cond]);
+
+        let (sql, args) = base.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id FROM users WHERE status = $1 AND email IS NOT NULL"
+        );
+        assert_eq!(args, vec![Arg::from("active")]);
+
+        let (sql, args) = by_age.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(sql, "SELECT id FROM users WHERE status = $1 AND age > $2");
+        assert_eq!(args, vec![Arg::from("active"), Arg::from(18_i64)]);
+
+        let (sql, args) = by_name
+            .try_build_with_flavor(Flavor::PostgreSQL, &[])
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT id FROM users WHERE status = $1 AND age > $2 AND name LIKE $3"
+        );
+        assert_eq!(args.len(), 3);
+
+        // Building the original again is unaffected by its clones.
+        assert_eq!(
+            base.build_with_flavor(Flavor::PostgreSQL, &[]).0,
+            "SELECT id FROM users WHERE status = $1 AND email IS NOT NULL"
+        );
+    }
 }
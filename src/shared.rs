@@ -0,0 +1,128 @@
+//! Shared: thread-safe shared ownership for builder state (args, where clauses, late-bound
+//! builders) and the copy-on-write storage that keeps `clone_builder` cheap.
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Shared: `Arc<RwLock<T>>` with `RefCell`-style `borrow`/`borrow_mut`, so builders holding it
+/// are `Send + Sync` and can live across `.await` points or move into spawned tasks.
+///
+/// Cloning shares the value. Guards must not be held across a build of the same value; a
+/// poisoned lock is recovered since builder state stays consistent between calls.
+///
+/// Unlike `RefCell`, a second `borrow_mut` (or a `borrow_mut` while a `borrow` guard is held)
+/// on the same thread does not panic: it deadlocks. Drop the guard before borrowing again.
+pub struct Shared<T>(Arc<RwLock<T>>);
+
+impl<T> Shared<T> {
+    pub fn new(value: T) -> Self {
+        Self(Arc::new(RwLock::new(value)))
+    }
+
+    pub fn borrow(&self) -> RwLockReadGuard<'_, T> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn borrow_mut(&self) -> RwLockWriteGuard<'_, T> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Snapshot: a clone of the value, taken under a read lock that is released on return.
+    ///
+    /// Builds compile from a snapshot of their args: nested builders (such as the where clause)
+    /// lock the same args again, and `RwLock` does not allow a recursive read once a writer is
+    /// waiting.
+    pub fn snapshot(&self) -> T
+    where
+        T: Clone,
+    {
+        self.borrow().clone()
+    }
+
+    /// Whether both handles point at the same value.
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(&a.0, &b.0)
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Default> Default for Shared<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.try_read() {
+            Ok(v) => f.debug_tuple("Shared").field(&*v).finish(),
+            Err(_) => f.write_str("Shared(<locked>)"),
+        }
+    }
+}
+
+pub fn shared<T>(value: T) -> Shared<T> {
+    Shared::new(value)
+}
+
+/// CowArc: copy-on-write storage. Cloning bumps a reference count; the first mutation through a
+/// shared handle copies the value, so clones of a builder only pay for what they change.
+#[derive(Default, PartialEq, Eq)]
+pub(crate) struct CowArc<T>(Arc<T>);
+
+impl<T> CowArc<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+}
+
+impl<T> Clone for CowArc<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Deref for CowArc<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Clone> DerefMut for CowArc<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        Arc::make_mut(&mut self.0)
+    }
+}
+
+impl<T> From<T> for CowArc<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CowArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<A> FromIterator<A> for CowArc<Vec<A>> {
+    fn from_iter<I: IntoIterator<Item = A>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<'a, A> IntoIterator for &'a CowArc<Vec<A>> {
+    type Item = &'a A;
+    type IntoIter = std::slice::Iter<'a, A>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
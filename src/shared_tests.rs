@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use crate::modifiers::{Arg, Builder, rc_builder};
+    use crate::shared::Shared;
+    use crate::{
+        AlterTableBuilder, CTEBuilder, CTEQueryBuilder, CreateIndexBuilder, CreateTableBuilder,
+        DeleteBuilder, DropIndexBuilder, DropTableBuilder, Flavor, InsertBuilder, MergeBuilder,
+        RcBuilder, SelectBuilder, TruncateTableBuilder, UnionBuilder, UpdateBuilder, WhereClause,
+        WhereClauseRef,
+    };
+    use pretty_assertions::assert_eq;
+    use std::sync::Barrier;
+    use std::thread;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn builders_are_send_and_sync() {
+        assert_send_sync::<SelectBuilder>();
+        assert_send_sync::<InsertBuilder>();
+        assert_send_sync::<UpdateBuilder>();
+        assert_send_sync::<DeleteBuilder>();
+        assert_send_sync::<UnionBuilder>();
+        assert_send_sync::<CTEBuilder>();
+        assert_send_sync::<CTEQueryBuilder>();
+        assert_send_sync::<MergeBuilder>();
+        assert_send_sync::<CreateTableBuilder>();
+        assert_send_sync::<AlterTableBuilder>();
+        assert_send_sync::<CreateIndexBuilder>();
+        assert_send_sync::<DropIndexBuilder>();
+        assert_send_sync::<DropTableBuilder>();
+        assert_send_sync::<TruncateTableBuilder>();
+        assert_send_sync::<WhereClauseRef>();
+        assert_send_sync::<RcBuilder<SelectBuilder>>();
+        assert_send_sync::<Box<dyn Builder>>();
+        assert_send_sync::<Arg>();
+    }
+
+    #[test]
+    fn builder_moves_into_thread_with_shared_where_clause() {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id"]).from(vec!["users"]);
+        sb.where_(vec![sb.equal("status", "active")]);
+        let wc = sb.where_clause().unwrap();
+
+        let mut db = DeleteBuilder::new();
+        db.delete_from(vec!["users"]);
+        db.set_where_clause(Some(wc.clone()));
+
+        // Mutating the shared clause is visible to both builders, on any thread.
+        let mut extra = SelectBuilder::new();
+        let cond = extra.less_than("age", 18_i64);
+        extra.where_(vec![cond]);
+        wc.borrow_mut()
+            .add_where_clause(&extra.where_clause().unwrap().borrow());
+
+        let handle = thread::spawn(move || {
+            let (select, _) = sb.build_with_flavor(Flavor::PostgreSQL, &[]);
+            let (delete, args) = db.build_with_flavor(Flavor::PostgreSQL, &[]);
+            (select, delete, args.len())
+        });
+        let (select, delete, n) = handle.join().unwrap();
+        assert_eq!(
+            select,
+            "SELECT id FROM users WHERE status = $1 AND age < $2"
+        );
+        assert_eq!(delete, "DELETE FROM users WHERE status = $1 AND age < $2");
+        assert_eq!(n, 2);
+    }
+
+    #[test]
+    fn rc_builder_late_binding_across_threads() {
+        let mut inner = SelectBuilder::new();
+        inner.select(vec!["user_id"]).from(vec!["bans"]);
+        let inner = Shared::new(inner);
+
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["*"]).from(vec!["users"]);
+        let sub = sb.var(Arg::Builder(Box::new(rc_builder(inner.clone()))));
+        sb.where_(vec![format!("id IN ({sub})")]);
+
+        let worker = {
+            let inner = inner.clone();
+            thread::spawn(move || {
+                let cond = inner.borrow().greater_than("until", 100_i64);
+                inner.borrow_mut().where_(vec![cond]);
+            })
+        };
+        worker.join().unwrap();
+
+        let (sql, args) = thread::spawn(move || sb.build_with_flavor(Flavor::MySQL, &[]))
+            .join()
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM users WHERE id IN (SELECT user_id FROM bans WHERE until > ?)"
+        );
+        assert_eq!(args.len(), 1);
+    }
+
+    #[test]
+    fn fresh_clone_builds_concurrently() {
+        let mut base = SelectBuilder::new();
+        base.select(vec!["id"]).from(vec!["users"]);
+        base.where_(vec![base.equal("status", "active")]);
+
+        for _ in 0..200 {
+            let mut clone = base.clone_builder();
+            clone.where_(vec![clone.greater_than("age", 18_i64)]);
+
+            // Every thread sees the clone's own where clause, whichever one rebinds it.
+            let start = Barrier::new(4);
+            thread::scope(|s| {
+                let handles: Vec<_> = (0..4)
+                    .map(|_| {
+                        s.spawn(|| {
+                            start.wait();
+                            clone.build_with_flavor(Flavor::PostgreSQL, &[]).0
+                        })
+                    })
+                    .collect();
+                for h in handles {
+                    assert_eq!(
+                        h.join().unwrap(),
+                        "SELECT id FROM users WHERE status = $1 AND age > $2"
+                    );
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn shared_where_clause_identity() {
+        let wc = WhereClause::new();
+        let same = wc.clone();
+        assert!(Shared::ptr_eq(&wc, &same));
+        assert!(!Shared::ptr_eq(&wc, &WhereClause::new()));
+    }
+}
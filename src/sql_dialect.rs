@@ -0,0 +1,457 @@
+//! SqlDialect: pluggable dialect behavior behind every [`Flavor`].
+//!
+//! The ten built-in flavors are implemented here; additional dialects (CockroachDB, MariaDB,
+//! in-house proxies, ...) can be registered with [`register_dialect`] and then used like any
+//! other `Flavor`.
+
+use crate::column_def::ColumnType;
+use crate::dialect::Dialect;
+use crate::flavor::{Flavor, InterpolateError};
+use crate::interpolate::{encode_ansi_value, encode_sql_value, interpolate_with};
+use crate::modifiers::Arg;
+use crate::value::SqlValue;
+use std::sync::RwLock;
+
+/// How a dialect exposes rows affected by INSERT/UPDATE/DELETE.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReturningStyle {
+    /// The dialect has no RETURNING equivalent; `returning(...)` is ignored.
+    Unsupported,
+    /// Trailing `RETURNING col, ...` (PostgreSQL, SQLite).
+    Returning,
+    /// `OUTPUT INSERTED.col, ...` / `OUTPUT DELETED.col, ...` (SQL Server).
+    Output,
+}
+
+/// How a dialect resolves a conflicting INSERT (upsert).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UpsertStyle {
+    /// No upsert syntax; `InsertBuilder::do_update`/`do_nothing` fail to build.
+    Unsupported,
+    /// `ON CONFLICT (...) DO UPDATE SET ... / DO NOTHING`, incoming row as `EXCLUDED` (PostgreSQL, SQLite).
+    OnConflict,
+    /// `ON DUPLICATE KEY UPDATE ...`, incoming row as `VALUES(col)` or a row alias (MySQL, Doris).
+    OnDuplicateKey,
+}
+
+/// How a dialect expresses `UpdateBuilder::join`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UpdateJoinStyle {
+    /// No joined UPDATE; `UpdateBuilder::join` fails to build.
+    Unsupported,
+    /// `UPDATE t JOIN s ON ... SET ...` (MySQL, Doris).
+    Inline,
+    /// `UPDATE t SET ... FROM s WHERE <on> AND ...`; the first join must be inner (PostgreSQL, SQLite).
+    From,
+    /// `UPDATE alias SET ... FROM t JOIN s ON ...` (SQL Server).
+    FromSelf,
+}
+
+/// How a dialect expresses `DeleteBuilder::join`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeleteJoinStyle {
+    /// No joined DELETE; `DeleteBuilder::join` fails to build.
+    Unsupported,
+    /// `DELETE FROM t USING s WHERE <on> AND ...`; the first join must be inner (PostgreSQL).
+    Using,
+    /// `DELETE t FROM t JOIN s ON ...`, without ORDER BY/LIMIT (MySQL, SQL Server).
+    FromJoin,
+    /// `DELETE FROM t WHERE EXISTS (SELECT 1 FROM s WHERE ... AND <on>)`; the first join must be
+    /// inner (SQLite, Oracle).
+    Exists,
+}
+
+/// LIMIT/OFFSET placeholders handed to [`SqlDialect::write_pagination`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Pagination<'a> {
+    pub limit: Option<&'a str>,
+    pub offset: Option<&'a str>,
+    /// Whether the statement already has an ORDER BY clause.
+    pub has_order_by: bool,
+}
+
+/// Dialect behavior consulted by the builders, `Args` and interpolation.
+///
+/// Every method except [`SqlDialect::name`] has a default that follows ANSI SQL with `?`
+/// placeholders, so an implementation only overrides what differs.
+pub trait SqlDialect: Send + Sync {
+    /// Human readable name, used by `Display for Flavor`.
+    fn name(&self) -> &str;
+
+    /// Placeholder style used by the default `write_placeholder` and `interpolate`.
+    fn placeholder_style(&self) -> Dialect {
+        Dialect::QuestionMark
+    }
+
+    /// Write the placeholder for the `index`-th (1-based) argument of the statement.
+    fn write_placeholder(&self, out: &mut String, index: usize) {
+        self.placeholder_style().write_placeholder(index, out);
+    }
+
+    /// Quote an identifier.
+    fn quote(&self, ident: &str) -> String {
+        format!("\"{ident}\"")
+    }
+
+    /// Write the LIMIT/OFFSET clause; `limit`/`offset` are already placeholders.
+    ///
+    /// A leading space is written when `out` is not empty.
+    fn write_pagination(&self, out: &mut String, p: &Pagination<'_>) {
+        if let Some(lim) = p.limit {
+            write_leading(out, "LIMIT ");
+            out.push_str(lim);
+        }
+        if let Some(off) = p.offset {
+            write_leading(out, "OFFSET ");
+            out.push_str(off);
+        }
+    }
+
+    /// RETURNING/OUTPUT support.
+    fn returning_style(&self) -> ReturningStyle {
+        ReturningStyle::Unsupported
+    }
+
+    /// Upsert syntax used by `InsertBuilder::do_update`/`do_nothing`.
+    fn upsert_style(&self) -> UpsertStyle {
+        UpsertStyle::Unsupported
+    }
+
+    /// Joined UPDATE syntax used by `UpdateBuilder::join`.
+    fn update_join_style(&self) -> UpdateJoinStyle {
+        UpdateJoinStyle::Unsupported
+    }
+
+    /// Joined DELETE syntax used by `DeleteBuilder::join`.
+    fn delete_join_style(&self) -> DeleteJoinStyle {
+        DeleteJoinStyle::Unsupported
+    }
+
+    /// Concrete SQL type for a `ColumnDef`.
+    fn column_type(&self, ty: &ColumnType) -> String {
+        ty.ansi_name()
+    }
+
+    /// Column keyword for `ColumnDef::auto_increment`; `None` if the dialect has none.
+    fn auto_increment_keyword(&self) -> Option<&str> {
+        Some("GENERATED BY DEFAULT AS IDENTITY")
+    }
+
+    /// Maximum bind parameters per statement, used by `InsertBuilder::build_batches_with_flavor`.
+    fn max_params(&self) -> Option<usize> {
+        None
+    }
+
+    /// Verb used by `InsertBuilder::insert_ignore_into`.
+    fn insert_ignore_keyword(&self) -> &str {
+        "INSERT"
+    }
+
+    /// Clause appended after VALUES by `InsertBuilder::insert_ignore_into`, if any.
+    fn insert_ignore_suffix(&self) -> Option<&str> {
+        None
+    }
+
+    /// Encode a value as a SQL literal (used by interpolation).
+    fn encode_literal(&self, out: &mut String, value: &SqlValue) -> Result<(), InterpolateError> {
+        encode_ansi_value(out, value);
+        Ok(())
+    }
+
+    /// Replace the placeholders in `sql` with literals encoded by `encode_literal`.
+    fn interpolate(&self, sql: &str, args: &[Arg]) -> Result<String, InterpolateError> {
+        interpolate_with(self.placeholder_style(), sql, args, &|out, v| {
+            self.encode_literal(out, v)
+        })
+    }
+}
+
+fn write_leading(out: &mut String, s: &str) {
+    if !out.is_empty() {
+        out.push(' ');
+    }
+    out.push_str(s);
+}
+
+/// Opaque handle of a dialect registered with [`register_dialect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DialectId(pub(crate) u16);
+
+static CUSTOM_DIALECTS: RwLock<Vec<&'static dyn SqlDialect>> = RwLock::new(Vec::new());
+
+/// Register a user-defined dialect and return the `Flavor` that selects it.
+///
+/// The dialect lives for the rest of the program; register it once (e.g. in a `LazyLock`)
+/// and reuse the returned flavor.
+pub fn register_dialect(dialect: impl SqlDialect + 'static) -> Flavor {
+    let leaked: &'static dyn SqlDialect = Box::leak(Box::new(dialect));
+    let mut dialects = CUSTOM_DIALECTS.write().unwrap_or_else(|e| e.into_inner());
+    let id = u16::try_from(dialects.len()).expect("too many registered dialects");
+    dialects.push(leaked);
+    Flavor::Custom(DialectId(id))
+}
+
+pub(crate) fn custom_dialect(id: DialectId) -> Option<&'static dyn SqlDialect> {
+    let dialects = CUSTOM_DIALECTS.read().unwrap_or_else(|e| e.into_inner());
+    dialects.get(id.0 as usize).copied()
+}
+
+/// Built-in implementation shared by the ten predefined flavors.
+#[derive(Debug)]
+pub(crate) struct BuiltinDialect(Flavor);
+
+pub(crate) static BUILTIN_DIALECTS: [BuiltinDialect; 10] = [
+    BuiltinDialect(Flavor::MySQL),
+    BuiltinDialect(Flavor::PostgreSQL),
+    BuiltinDialect(Flavor::SQLite),
+    BuiltinDialect(Flavor::SQLServer),
+    BuiltinDialect(Flavor::CQL),
+    BuiltinDialect(Flavor::ClickHouse),
+    BuiltinDialect(Flavor::Presto),
+    BuiltinDialect(Flavor::Oracle),
+    BuiltinDialect(Flavor::Informix),
+    BuiltinDialect(Flavor::Doris),
+];
+
+impl SqlDialect for BuiltinDialect {
+    fn name(&self) -> &str {
+        match self.0 {
+            Flavor::MySQL => "MySQL",
+            Flavor::PostgreSQL => "PostgreSQL",
+            Flavor::SQLite => "SQLite",
+            Flavor::SQLServer => "SQLServer",
+            Flavor::CQL => "CQL",
+            Flavor::ClickHouse => "ClickHouse",
+            Flavor::Presto => "Presto",
+            Flavor::Oracle => "Oracle",
+            Flavor::Informix => "Informix",
+            Flavor::Doris => "Doris",
+            Flavor::Custom(_) => "Custom",
+        }
+    }
+
+    fn placeholder_style(&self) -> Dialect {
+        match self.0 {
+            Flavor::PostgreSQL => Dialect::DollarNumbered,
+            Flavor::SQLServer => Dialect::AtPNumbered,
+            Flavor::Oracle => Dialect::ColonNumbered,
+            _ => Dialect::QuestionMark,
+        }
+    }
+
+    fn quote(&self, ident: &str) -> String {
+        match self.0 {
+            Flavor::MySQL | Flavor::ClickHouse | Flavor::Doris => format!("`{ident}`"),
+            Flavor::CQL => format!("'{ident}'"),
+            _ => format!("\"{ident}\""),
+        }
+    }
+
+    fn write_pagination(&self, out: &mut String, p: &Pagination<'_>) {
+        match self.0 {
+            Flavor::CQL => {
+                if let Some(lim) = p.limit {
+                    write_leading(out, "LIMIT ");
+                    out.push_str(lim);
+                }
+            }
+            Flavor::PostgreSQL => {
+                if let Some(lim) = p.limit {
+                    write_leading(out, "LIMIT ");
+                    out.push_str(lim);
+                }
+                if let Some(off) = p.offset {
+                    write_leading(out, "OFFSET ");
+                    out.push_str(off);
+                }
+            }
+            Flavor::Presto => {
+                if let Some(off) = p.offset {
+                    write_leading(out, "OFFSET ");
+                    out.push_str(off);
+                }
+                if let Some(lim) = p.limit {
+                    write_leading(out, "LIMIT ");
+                    out.push_str(lim);
+                }
+            }
+            Flavor::SQLServer | Flavor::Oracle => {
+                if !p.has_order_by
+                    && (p.limit.is_some() || p.offset.is_some())
+                    && self.0 == Flavor::SQLServer
+                {
+                    write_leading(out, "ORDER BY 1");
+                }
+
+                if let Some(off) = p.offset {
+                    write_leading(out, "OFFSET ");
+                    out.push_str(off);
+                    out.push_str(" ROWS");
+                }
+
+                if let Some(lim) = p.limit {
+                    if p.offset.is_none() {
+                        write_leading(out, "OFFSET 0 ROWS");
+                    }
+                    write_leading(out, "FETCH NEXT ");
+                    out.push_str(lim);
+                    out.push_str(" ROWS ONLY");
+                }
+            }
+            // MySQL, SQLite, ClickHouse, Informix, Doris: OFFSET is only valid after LIMIT.
+            _ => {
+                if let Some(lim) = p.limit {
+                    write_leading(out, "LIMIT ");
+                    out.push_str(lim);
+                    if let Some(off) = p.offset {
+                        write_leading(out, "OFFSET ");
+                        out.push_str(off);
+                    }
+                }
+            }
+        }
+    }
+
+    fn returning_style(&self) -> ReturningStyle {
+        match self.0 {
+            Flavor::PostgreSQL | Flavor::SQLite => ReturningStyle::Returning,
+            Flavor::SQLServer => ReturningStyle::Output,
+            _ => ReturningStyle::Unsupported,
+        }
+    }
+
+    fn upsert_style(&self) -> UpsertStyle {
+        match self.0 {
+            Flavor::PostgreSQL | Flavor::SQLite => UpsertStyle::OnConflict,
+            Flavor::MySQL | Flavor::Doris => UpsertStyle::OnDuplicateKey,
+            _ => UpsertStyle::Unsupported,
+        }
+    }
+
+    fn update_join_style(&self) -> UpdateJoinStyle {
+        match self.0 {
+            Flavor::MySQL | Flavor::Doris => UpdateJoinStyle::Inline,
+            Flavor::PostgreSQL | Flavor::SQLite => UpdateJoinStyle::From,
+            Flavor::SQLServer => UpdateJoinStyle::FromSelf,
+            _ => UpdateJoinStyle::Unsupported,
+        }
+    }
+
+    fn delete_join_style(&self) -> DeleteJoinStyle {
+        match self.0 {
+            Flavor::PostgreSQL => DeleteJoinStyle::Using,
+            Flavor::MySQL | Flavor::SQLServer => DeleteJoinStyle::FromJoin,
+            Flavor::SQLite | Flavor::Oracle => DeleteJoinStyle::Exists,
+            _ => DeleteJoinStyle::Unsupported,
+        }
+    }
+
+    fn column_type(&self, ty: &ColumnType) -> String {
+        use ColumnType::*;
+        let s = match (self.0, ty) {
+            (_, Custom(t)) => return t.to_string(),
+            (Flavor::ClickHouse, _) => match ty {
+                Int => "Int32",
+                BigInt => "Int64",
+                Text | Varchar(_) | Bytes | Json => "String",
+                Bool => "Bool",
+                Timestamp => "DateTime",
+                Decimal(p, s) => return format!("Decimal({p}, {s})"),
+                Uuid => "UUID",
+                Custom(_) => unreachable!(),
+            },
+            (Flavor::CQL, _) => match ty {
+                Int => "int",
+                BigInt => "bigint",
+                Text | Json => "text",
+                Varchar(_) => "varchar",
+                Bool => "boolean",
+                Timestamp => "timestamp",
+                Bytes => "blob",
+                Decimal(..) => "decimal",
+                Uuid => "uuid",
+                Custom(_) => unreachable!(),
+            },
+            (Flavor::Oracle, _) => match ty {
+                Int => "NUMBER(10)",
+                BigInt => "NUMBER(19)",
+                Text | Json => "CLOB",
+                Varchar(n) => return format!("VARCHAR2({n})"),
+                Bool => "NUMBER(1)",
+                Timestamp => "TIMESTAMP",
+                Bytes => "BLOB",
+                Decimal(p, s) => return format!("NUMBER({p}, {s})"),
+                Uuid => "CHAR(36)",
+                Custom(_) => unreachable!(),
+            },
+            (Flavor::SQLServer, _) => match ty {
+                Int => "INT",
+                Text | Json => "NVARCHAR(MAX)",
+                Varchar(n) => return format!("NVARCHAR({n})"),
+                Bool => "BIT",
+                Timestamp => "DATETIME2",
+                Bytes => "VARBINARY(MAX)",
+                Uuid => "UNIQUEIDENTIFIER",
+                _ => return ty.ansi_name(),
+            },
+            (Flavor::PostgreSQL, Bytes) => "BYTEA",
+            (Flavor::PostgreSQL, Json) => "JSONB",
+            // SQLite: AUTOINCREMENT only works on INTEGER PRIMARY KEY.
+            (Flavor::SQLite, BigInt) => "INTEGER",
+            (Flavor::SQLite, Bool) => "INTEGER",
+            (Flavor::SQLite, Json | Uuid) => "TEXT",
+            (Flavor::MySQL | Flavor::Doris, Int) => "INT",
+            (Flavor::MySQL | Flavor::Doris, Timestamp) => "DATETIME",
+            (Flavor::MySQL, Uuid) => "CHAR(36)",
+            (Flavor::Doris, Uuid) => "VARCHAR(36)",
+            (Flavor::Doris, Text | Bytes) => "STRING",
+            (Flavor::Informix, Timestamp) => "DATETIME YEAR TO FRACTION(5)",
+            (Flavor::Informix, Bytes) => "BYTE",
+            (Flavor::Informix, Uuid) => "CHAR(36)",
+            (Flavor::Presto, Text) => "VARCHAR",
+            (Flavor::Presto, Bytes) => "VARBINARY",
+            _ => return ty.ansi_name(),
+        };
+        s.to_string()
+    }
+
+    fn auto_increment_keyword(&self) -> Option<&str> {
+        match self.0 {
+            Flavor::MySQL | Flavor::Doris => Some("AUTO_INCREMENT"),
+            Flavor::PostgreSQL | Flavor::Oracle => Some("GENERATED BY DEFAULT AS IDENTITY"),
+            Flavor::SQLServer => Some("IDENTITY(1,1)"),
+            Flavor::SQLite => Some("AUTOINCREMENT"),
+            _ => None,
+        }
+    }
+
+    fn max_params(&self) -> Option<usize> {
+        match self.0 {
+            // SQLite before 3.32 only allows 999.
+            Flavor::SQLite => Some(32766),
+            Flavor::SQLServer => Some(2100),
+            Flavor::PostgreSQL | Flavor::MySQL | Flavor::Oracle => Some(65535),
+            _ => None,
+        }
+    }
+
+    fn insert_ignore_keyword(&self) -> &str {
+        match self.0 {
+            Flavor::MySQL | Flavor::Oracle => "INSERT IGNORE",
+            Flavor::SQLite => "INSERT OR IGNORE",
+            _ => "INSERT",
+        }
+    }
+
+    fn insert_ignore_suffix(&self) -> Option<&str> {
+        match self.0 {
+            // PostgreSQL: ON CONFLICT DO NOTHING must appear after VALUES
+            Flavor::PostgreSQL => Some("ON CONFLICT DO NOTHING"),
+            _ => None,
+        }
+    }
+
+    fn encode_literal(&self, out: &mut String, value: &SqlValue) -> Result<(), InterpolateError> {
+        encode_sql_value(out, value, self.0)
+    }
+}
@@ -0,0 +1,202 @@
+#[cfg(test)]
+mod tests {
+    use crate::delete::DeleteBuilder;
+    use crate::dialect::Dialect;
+    use crate::flavor::{Flavor, InterpolateError};
+    use crate::insert::InsertBuilder;
+    use crate::modifiers::{Arg, Builder};
+    use crate::select::SelectBuilder;
+    use crate::sql_dialect::{
+        DeleteJoinStyle, Pagination, ReturningStyle, SqlDialect, UpdateJoinStyle, register_dialect,
+    };
+    use crate::update::UpdateBuilder;
+    use crate::value::SqlValue;
+    use crate::{default_flavor, set_default_flavor_scoped};
+    use pretty_assertions::assert_eq;
+    use std::sync::LazyLock;
+
+    struct Cockroach;
+
+    impl SqlDialect for Cockroach {
+        fn name(&self) -> &str {
+            "CockroachDB"
+        }
+
+        fn placeholder_style(&self) -> Dialect {
+            Dialect::DollarNumbered
+        }
+
+        fn returning_style(&self) -> ReturningStyle {
+            ReturningStyle::Returning
+        }
+
+        fn insert_ignore_suffix(&self) -> Option<&str> {
+            Some("ON CONFLICT DO NOTHING")
+        }
+
+        fn update_join_style(&self) -> UpdateJoinStyle {
+            UpdateJoinStyle::From
+        }
+
+        fn delete_join_style(&self) -> DeleteJoinStyle {
+            DeleteJoinStyle::Using
+        }
+    }
+
+    struct Proxy;
+
+    impl SqlDialect for Proxy {
+        fn name(&self) -> &str {
+            "Proxy"
+        }
+
+        fn write_placeholder(&self, out: &mut String, index: usize) {
+            out.push_str(&format!("{{{index}}}"));
+        }
+
+        fn quote(&self, ident: &str) -> String {
+            format!("[{ident}]")
+        }
+
+        fn write_pagination(&self, out: &mut String, p: &Pagination<'_>) {
+            if let Some(lim) = p.limit {
+                out.push_str(" TOP ");
+                out.push_str(lim);
+            }
+        }
+
+        fn encode_literal(
+            &self,
+            _out: &mut String,
+            _value: &SqlValue,
+        ) -> Result<(), InterpolateError> {
+            Err(InterpolateError::UnsupportedArgs)
+        }
+
+        fn interpolate(&self, _sql: &str, _args: &[Arg]) -> Result<String, InterpolateError> {
+            Err(InterpolateError::NotImplemented)
+        }
+    }
+
+    static COCKROACH: LazyLock<Flavor> = LazyLock::new(|| register_dialect(Cockroach));
+    static PROXY: LazyLock<Flavor> = LazyLock::new(|| register_dialect(Proxy));
+
+    #[test]
+    fn builtin_flavors_are_dialects() {
+        assert_eq!(
+            Flavor::PostgreSQL.dialect().placeholder_style(),
+            Dialect::DollarNumbered
+        );
+        assert_eq!(
+            Flavor::SQLServer.dialect().returning_style(),
+            ReturningStyle::Output
+        );
+        assert_eq!(
+            Flavor::SQLite.dialect().insert_ignore_keyword(),
+            "INSERT OR IGNORE"
+        );
+        assert_eq!(Flavor::Doris.dialect().quote("t"), "`t`");
+        assert_eq!(Flavor::Oracle.dialect().name(), "Oracle");
+    }
+
+    #[test]
+    fn custom_dialect_builds_select() {
+        let flavor = *COCKROACH;
+        assert_eq!(flavor.to_string(), "CockroachDB");
+        assert_eq!(flavor.quote("users"), "\"users\"");
+
+        let mut sb = SelectBuilder::new();
+        sb.select(["id"]).from(["users"]);
+        sb.where_([sb.equal("name", "x"), sb.gt("age", 18_i64)]);
+        sb.limit(10).offset(20);
+        let (sql, args) = sb.build_with_flavor(flavor, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id FROM users WHERE name = $1 AND age > $2 LIMIT $3 OFFSET $4"
+        );
+        assert_eq!(args.len(), 4);
+
+        let (sql, _) = sb.build_with_flavor(*PROXY, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id FROM users WHERE name = {1} AND age > {2} TOP {3}"
+        );
+    }
+
+    #[test]
+    fn custom_dialect_insert_returning_and_ignore() {
+        let flavor = *COCKROACH;
+        let _g = set_default_flavor_scoped(flavor);
+        assert_eq!(default_flavor(), flavor);
+
+        let mut ib = InsertBuilder::new();
+        ib.insert_ignore_into("users")
+            .cols(["id", "name"])
+            .values([Arg::from(1_i64), Arg::from("a")])
+            .returning(["id"]);
+        let (sql, _) = ib.build();
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, name) VALUES ($1, $2) ON CONFLICT DO NOTHING RETURNING id"
+        );
+
+        let (sql, _) = ib.build_with_flavor(*PROXY, &[]);
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, name) VALUES ({1}, {2}) ON CONFLICT DO NOTHING"
+        );
+    }
+
+    #[test]
+    fn custom_dialect_update_join() {
+        let mut ub = UpdateBuilder::new();
+        ub.update(["orders o"]);
+        ub.join("customers c", ["c.id = o.customer_id"]);
+        ub.set([ub.assign("o.discount", 10_i64)]);
+        let (sql, _) = ub.build_with_flavor(*COCKROACH, &[]);
+        assert_eq!(
+            sql,
+            "UPDATE orders o SET o.discount = $1 FROM customers c WHERE c.id = o.customer_id"
+        );
+
+        assert!(ub.try_build_with_flavor(*PROXY, &[]).is_err());
+    }
+
+    #[test]
+    fn custom_dialect_delete_join() {
+        let mut db = DeleteBuilder::new();
+        db.delete_from(["sessions s"]);
+        db.join("users u", ["u.id = s.user_id"]);
+        db.where_([db.equal("u.banned", 1_i64)]);
+        let (sql, _) = db.build_with_flavor(*COCKROACH, &[]);
+        assert_eq!(
+            sql,
+            "DELETE FROM sessions s USING users u WHERE u.banned = $1 AND u.id = s.user_id"
+        );
+
+        assert!(db.try_build_with_flavor(*PROXY, &[]).is_err());
+    }
+
+    #[test]
+    fn custom_dialect_interpolate() {
+        let sql = COCKROACH
+            .interpolate(
+                "SELECT * FROM t WHERE a = $1 AND b = $2 AND c = $3",
+                &[
+                    "it's".into(),
+                    1_i64.into(),
+                    SqlValue::Bytes(vec![0xAB, 0x01]).into(),
+                ],
+            )
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM t WHERE a = 'it''s' AND b = 1 AND c = X'AB01'"
+        );
+
+        assert_eq!(
+            PROXY.interpolate("SELECT {1}", &[1_i64.into()]),
+            Err(InterpolateError::NotImplemented)
+        );
+    }
+}
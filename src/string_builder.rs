@@ -23,6 +23,7 @@ impl StringBuilder {
         self.buf.push_str(s);
     }
 
+    #[allow(dead_code)]
     pub(crate) fn write_char(&mut self, c: char) {
         self.buf.push(c);
     }
@@ -67,6 +68,10 @@ impl StringBuilder {
         self.buf.clear();
     }
 
+    pub(crate) fn as_mut_string(&mut self) -> &mut String {
+        &mut self.buf
+    }
+
     pub(crate) fn into_string(self) -> String {
         self.buf
     }
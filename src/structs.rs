@@ -3,6 +3,8 @@
 //! Without runtime reflection (and avoiding extra proc-macro crates), this uses `macro_rules!`
 //! to generate field metadata and getters, providing an experience close to reflective builders.
 
+use crate::column_def::{ColumnDef, ColumnType, TableConstraint};
+use crate::create_table::CreateTableBuilder;
 use crate::delete::DeleteBuilder;
 use crate::escape_all;
 use crate::field_mapper::{FieldMapperFunc, default_field_mapper};
@@ -19,7 +21,10 @@ pub enum FieldOpt {
     WithQuote,
 }
 
+/// Field metadata generated by `sql_struct!`; build it with `FieldMeta::new` since fields may be
+/// added.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct FieldMeta {
     /// Rust field name (used to generate value accessors).
     pub rust: &'static str,
@@ -34,9 +39,87 @@ pub struct FieldMeta {
     /// Omitempty tags (include "" for default).
     pub omitempty_tags: &'static [&'static str],
     pub with_quote: bool,
+    /// Column type for `Struct::create_table`; fields without one are left out of the DDL.
+    pub sql_type: Option<ColumnType>,
+    pub not_null: bool,
+    /// Default value as a SQL expression.
+    pub default: Option<&'static str>,
+    pub primary_key: bool,
 }
 
 impl FieldMeta {
+    pub const fn new(
+        rust: &'static str,
+        orig: &'static str,
+        db: &'static str,
+        as_: Option<&'static str>,
+        tags: &'static [&'static str],
+        omitempty_tags: &'static [&'static str],
+        with_quote: bool,
+    ) -> Self {
+        Self::new_with_column(
+            rust,
+            orig,
+            db,
+            as_,
+            tags,
+            omitempty_tags,
+            with_quote,
+            None,
+            false,
+            None,
+            false,
+        )
+    }
+
+    /// NewWithColumn: `new` plus the column metadata for `Struct::create_table`, in one const fn
+    /// (used by `sql_struct!`).
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new_with_column(
+        rust: &'static str,
+        orig: &'static str,
+        db: &'static str,
+        as_: Option<&'static str>,
+        tags: &'static [&'static str],
+        omitempty_tags: &'static [&'static str],
+        with_quote: bool,
+        sql_type: Option<ColumnType>,
+        not_null: bool,
+        default: Option<&'static str>,
+        primary_key: bool,
+    ) -> Self {
+        Self {
+            rust,
+            orig,
+            db,
+            as_,
+            tags,
+            omitempty_tags,
+            with_quote,
+            sql_type,
+            not_null,
+            default,
+            primary_key,
+        }
+    }
+
+    /// WithColumn: replace the column metadata for `Struct::create_table`.
+    pub fn with_column(
+        self,
+        sql_type: Option<ColumnType>,
+        not_null: bool,
+        default: Option<&'static str>,
+        primary_key: bool,
+    ) -> Self {
+        Self {
+            sql_type,
+            not_null,
+            default,
+            primary_key,
+            ..self
+        }
+    }
+
     pub fn name_for_select(&self, flavor: Flavor, alias: &str) -> String {
         let base = if self.with_quote {
             flavor.quote(alias)
@@ -545,6 +628,56 @@ impl<T: SqlStruct> Struct<T> {
         db
     }
 
+    /// CreateTable: build CREATE TABLE for the writable fields that declare a `sql_type`.
+    ///
+    /// Several `primary_key` fields become a table-level composite primary key.
+    pub fn create_table(&self, table: &str) -> CreateTableBuilder {
+        let mut ctb = CreateTableBuilder::new();
+        ctb.set_flavor(self.flavor);
+        ctb.create_table(table);
+
+        let fields: Vec<&'static FieldMeta> = self
+            .fields_for_write()
+            .into_iter()
+            .filter(|fm| fm.sql_type.is_some())
+            .collect();
+        let pk_count = fields.iter().filter(|fm| fm.primary_key).count();
+
+        let mut pk_cols = Vec::new();
+        for fm in fields {
+            let field_alias = self.alias_of(fm);
+            let col = if fm.with_quote {
+                self.flavor.quote(&field_alias)
+            } else {
+                field_alias
+            };
+            let Some(ty) = fm.sql_type.clone() else {
+                continue;
+            };
+
+            let mut def = ColumnDef::new(&col, ty);
+            if fm.not_null {
+                def = def.not_null();
+            }
+            if let Some(d) = fm.default {
+                def = def.default(d);
+            }
+            if fm.primary_key {
+                if pk_count == 1 {
+                    def = def.primary_key();
+                } else {
+                    pk_cols.push(col);
+                }
+            }
+            ctb.column(def);
+        }
+
+        if !pk_cols.is_empty() {
+            ctb.constraint(TableConstraint::primary_key(pk_cols));
+        }
+        ctb
+    }
+
     pub fn insert_into<'a>(
         &self,
         table: &str,
@@ -817,27 +950,38 @@ enum InsertVerb {
 ///   }
 /// }
 /// ```
+///
+/// Column metadata for `Struct::create_table` can follow `as:` (each key optional, in this order):
+///
+/// ```ignore
+/// id: { db: "id", tags: [], omitempty: [], quote: false, as: None,
+///       sql_type: ColumnType::BigInt, not_null: true, default: "0", primary_key: true },
+/// ```
 #[macro_export]
 macro_rules! sql_struct {
     (
         impl $ty:ty {
             $(
-                $field:ident : { db: $db:literal, $(orig: $orig:literal,)? tags: [ $($tag:literal),* $(,)? ], omitempty: [ $($omit:literal),* $(,)? ], quote: $quote:literal, as: $as:expr }
+                $field:ident : { db: $db:literal, $(orig: $orig:literal,)? tags: [ $($tag:literal),* $(,)? ], omitempty: [ $($omit:literal),* $(,)? ], quote: $quote:literal, as: $as:expr $(, sql_type: $sql_type:expr)? $(, not_null: $not_null:literal)? $(, default: $default:literal)? $(, primary_key: $pk:literal)? $(,)? }
             ),* $(,)?
         }
     ) => {
         impl $crate::structs::SqlStruct for $ty {
             const FIELDS: &'static [$crate::structs::FieldMeta] = &[
                 $(
-                    $crate::structs::FieldMeta{
-                        rust: stringify!($field),
-                        orig: $crate::__sql_struct_orig!(stringify!($field) $(, $orig)?),
-                        db: $db,
-                        as_: $as,
-                        tags: &[ $($tag),* ],
-                        omitempty_tags: &[ $($omit),* ],
-                        with_quote: $quote,
-                    }
+                    $crate::structs::FieldMeta::new_with_column(
+                        stringify!($field),
+                        $crate::__sql_struct_orig!(stringify!($field) $(, $orig)?),
+                        $db,
+                        $as,
+                        &[ $($tag),* ],
+                        &[ $($omit),* ],
+                        $quote,
+                        $crate::__sql_struct_opt!($($sql_type)?),
+                        $crate::__sql_struct_flag!($($not_null)?),
+                        $crate::__sql_struct_opt!($($default)?),
+                        $crate::__sql_struct_flag!($($pk)?),
+                    )
                 ),*
             ];
 
@@ -890,3 +1034,27 @@ macro_rules! __sql_struct_orig {
         $custom
     };
 }
+
+/// Macro helper: optional value keys (`None` when omitted).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sql_struct_opt {
+    () => {
+        None
+    };
+    ($v:expr) => {
+        Some($v)
+    };
+}
+
+/// Macro helper: optional boolean keys (`false` when omitted).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sql_struct_flag {
+    () => {
+        false
+    };
+    ($v:expr) => {
+        $v
+    };
+}
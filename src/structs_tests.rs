@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::Struct;
+    use crate::column_def::ColumnType;
     use crate::field_mapper::{
         identity_mapper, kebab_case_mapper, prefix_mapper, set_default_field_mapper,
         set_default_field_mapper_scoped, snake_case_mapper, suffix_mapper, upper_case_mapper,
@@ -8,6 +9,7 @@ mod tests {
     use crate::flavor::{Flavor, set_default_flavor_scoped};
     use crate::modifiers::Builder;
     use crate::scan_tokens;
+    use crate::structs::FieldMeta;
     use pretty_assertions::assert_eq;
 
     #[derive(Clone, Default)]
@@ -45,15 +47,15 @@ mod tests {
             c: 0.0,
         };
         let st = Struct::<StructWithQuote>::new().for_flavor(Flavor::MySQL);
-        let (sql, _) = st.update("foo", &v).build();
+        let (sql, _) = st.update("foo", &v).allow_full_table().build();
         assert_eq!(sql, "UPDATE foo SET `aa` = ?, ccc = ?");
 
         let st = Struct::<StructWithQuote>::new().for_flavor(Flavor::PostgreSQL);
-        let (sql, _) = st.update("foo", &v).build();
+        let (sql, _) = st.update("foo", &v).allow_full_table().build();
         assert_eq!(sql, r#"UPDATE foo SET "aa" = $1, ccc = $2"#);
 
         let st = Struct::<StructWithQuote>::new().for_flavor(Flavor::CQL);
-        let (sql, _) = st.update("foo", &v).build();
+        let (sql, _) = st.update("foo", &v).allow_full_table().build();
         assert_eq!(sql, "UPDATE foo SET 'aa' = ?, ccc = ?");
 
         // INSERT
@@ -93,7 +95,10 @@ mod tests {
     fn struct_omit_empty_default_tag() {
         let _g = set_default_flavor_scoped(Flavor::MySQL);
         let st = Struct::<StructOmitEmpty>::new().for_flavor(Flavor::MySQL);
-        let (sql, _) = st.update("foo", &StructOmitEmpty::default()).build();
+        let (sql, _) = st
+            .update("foo", &StructOmitEmpty::default())
+            .allow_full_table()
+            .build();
         assert_eq!(sql, "UPDATE foo SET ee = ?");
     }
 
@@ -285,7 +290,10 @@ mod tests {
         let st = Struct::<OmitEmptyForTag>::new();
 
         // Without WithTag: only default-tag fields (A/B/C) are omitted; D stays.
-        let (sql1, _) = st.update("foo", &OmitEmptyForTag::default()).build();
+        let (sql1, _) = st
+            .update("foo", &OmitEmptyForTag::default())
+            .allow_full_table()
+            .build();
         assert_eq!(sql1, "UPDATE foo SET D = ?, ee = ?");
 
         // WithTag("patch"): A/B/C/D all omit empty values.
@@ -296,7 +304,11 @@ mod tests {
             d: None,
             e: true,
         };
-        let (sql2, args2) = st.with_tag(["patch"]).update("foo", &v).build();
+        let (sql2, args2) = st
+            .with_tag(["patch"])
+            .update("foo", &v)
+            .allow_full_table()
+            .build();
         assert_eq!(sql2, "UPDATE foo SET `aa` = ?, bb = ?, cc = ?, ee = ?");
         assert_eq!(args2.len(), 4);
     }
@@ -329,6 +341,7 @@ mod tests {
 
         let (sql1, _) = st
             .update("foo", &OmitEmptyForMultipleTags::default())
+            .allow_full_table()
             .build();
         assert_eq!(sql1, "UPDATE foo SET D = ?, ee = ?");
 
@@ -339,7 +352,11 @@ mod tests {
             d: None,
             e: true,
         };
-        let (sql2, args2) = st.with_tag(["patch2"]).update("foo", &v).build();
+        let (sql2, args2) = st
+            .with_tag(["patch2"])
+            .update("foo", &v)
+            .allow_full_table()
+            .build();
         assert_eq!(sql2, "UPDATE foo SET `aa` = ?");
         assert_eq!(args2.len(), 1);
 
@@ -388,7 +405,10 @@ mod tests {
         let _g = set_default_flavor_scoped(Flavor::MySQL);
         let st = Struct::<WithPointers>::new();
 
-        let (sql1, _) = st.update("foo", &WithPointers::default()).build();
+        let (sql1, _) = st
+            .update("foo", &WithPointers::default())
+            .allow_full_table()
+            .build();
         assert_eq!(sql1, "UPDATE foo SET bb = ?");
 
         let v = WithPointers {
@@ -396,7 +416,7 @@ mod tests {
             b: None,
             c: Some(123.45),
         };
-        let (sql2, args2) = st.update("foo", &v).build();
+        let (sql2, args2) = st.update("foo", &v).allow_full_table().build();
         assert_eq!(sql2, "UPDATE foo SET aa = ?, bb = ?, cc = ?");
         assert_eq!(args2.len(), 3);
     }
@@ -614,7 +634,7 @@ mod tests {
             t3: "t3".to_string(),
             t4: "t4".to_string(),
         };
-        let (sql3, _) = s.update("t", &v).build();
+        let (sql3, _) = s.update("t", &v).allow_full_table().build();
         assert_eq!(sql3, "UPDATE t SET t1 = ?, t2 = ?, t4 = ?");
     }
 
@@ -649,7 +669,7 @@ mod tests {
         let f2: Box<dyn crate::valuer::SqlValuer> = Box::new(ImplValuer(100));
 
         let v = ContainsValuer { f1: f1.clone(), f2 };
-        let (sql, args) = st.update("t", &v).build();
+        let (sql, args) = st.update("t", &v).allow_full_table().build();
         assert_eq!(sql, "UPDATE t SET F1 = ?, F2 = ?");
 
         let result = Flavor::MySQL.interpolate(&sql, &args).unwrap();
@@ -800,14 +820,17 @@ mod tests {
             ignored: 0,
         };
 
-        let (sql, args) = s.update("user", &u).build();
+        let (sql, args) = s.update("user", &u).allow_full_table().build();
         assert_eq!(
             sql,
             "UPDATE user SET id = ?, Name = ?, status = ?, created_at = ?"
         );
         assert_eq!(args.len(), 4);
 
-        let (sql2, args2) = s.update_for_tag("user", "important", &u).build();
+        let (sql2, args2) = s
+            .update_for_tag("user", "important", &u)
+            .allow_full_table()
+            .build();
         assert_eq!(sql2, "UPDATE user SET id = ?, Name = ?, status = ?");
         assert_eq!(args2.len(), 3);
     }
@@ -914,7 +937,7 @@ mod tests {
     fn struct_delete_from_like_go() {
         let _g = set_default_flavor_scoped(Flavor::MySQL);
         let s = Struct::<StructUserForTest>::new();
-        let (sql, args) = s.delete_from("user").build();
+        let (sql, args) = s.delete_from("user").allow_full_table().build();
         assert_eq!(sql, "DELETE FROM user");
         assert!(args.is_empty());
     }
@@ -1293,4 +1316,83 @@ mod tests {
         );
         assert_eq!(args, vec![1234_i64.into()]);
     }
+
+    #[derive(Clone, Default)]
+    struct Account {
+        id: i64,
+        email: String,
+        balance: f64,
+        note: String,
+    }
+
+    crate::sql_struct! {
+        impl Account {
+            id: { db: "id", tags: [], omitempty: [], quote: false, as: None,
+                  sql_type: crate::ColumnType::BigInt, primary_key: true },
+            email: { db: "email", tags: [], omitempty: [], quote: true, as: None,
+                     sql_type: crate::ColumnType::Varchar(255), not_null: true },
+            balance: { db: "balance", tags: [], omitempty: [], quote: false, as: None,
+                       sql_type: crate::ColumnType::Decimal(12, 2), not_null: true, default: "0" },
+            note: { db: "note", tags: [], omitempty: [], quote: false, as: None },
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Membership {
+        user_id: i64,
+        group_id: i64,
+    }
+
+    crate::sql_struct! {
+        impl Membership {
+            user_id: { db: "user_id", tags: [], omitempty: [], quote: false, as: None,
+                       sql_type: crate::ColumnType::BigInt, not_null: true, primary_key: true },
+            group_id: { db: "group_id", tags: [], omitempty: [], quote: false, as: None,
+                        sql_type: crate::ColumnType::BigInt, not_null: true, primary_key: true },
+        }
+    }
+
+    #[test]
+    fn struct_create_table_from_metadata() {
+        let _g = set_default_flavor_scoped(Flavor::MySQL);
+        let (sql, _) = Struct::<Account>::new().create_table("accounts").build();
+        assert_eq!(
+            sql,
+            "CREATE TABLE accounts (id BIGINT PRIMARY KEY, `email` VARCHAR(255) NOT NULL, balance DECIMAL(12, 2) NOT NULL DEFAULT 0)"
+        );
+
+        let (sql, _) = Struct::<Account>::new()
+            .for_flavor(Flavor::PostgreSQL)
+            .create_table("accounts")
+            .build();
+        assert_eq!(
+            sql,
+            r#"CREATE TABLE accounts (id BIGINT PRIMARY KEY, "email" VARCHAR(255) NOT NULL, balance DECIMAL(12, 2) NOT NULL DEFAULT 0)"#
+        );
+
+        let (sql, _) = Struct::<Membership>::new()
+            .for_flavor(Flavor::SQLite)
+            .create_table("memberships")
+            .build();
+        assert_eq!(
+            sql,
+            "CREATE TABLE memberships (user_id INTEGER NOT NULL, group_id INTEGER NOT NULL, PRIMARY KEY (user_id, group_id))"
+        );
+    }
+
+    #[test]
+    fn field_meta_with_column_replaces_metadata() {
+        let meta = FieldMeta::new("geo", "geo", "geo", None, &[], &[], false).with_column(
+            Some(ColumnType::Custom(format!("GEOMETRY({})", 4326).into())),
+            true,
+            None,
+            false,
+        );
+        let meta = meta.with_column(Some(ColumnType::BigInt), false, Some("0"), true);
+        assert_eq!(meta.sql_type, Some(ColumnType::BigInt));
+        assert!(!meta.not_null);
+        assert_eq!(meta.default, Some("0"));
+        assert!(meta.primary_key);
+        assert_eq!(meta.db, "geo");
+    }
 }
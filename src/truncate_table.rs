@@ -0,0 +1,119 @@
+//! TruncateTableBuilder: build TRUNCATE TABLE statements.
+
+use crate::args::{Args, BuildError};
+use crate::create_index::quote_ident;
+use crate::flavor::Flavor;
+use crate::injection::{Injection, InjectionMarker};
+use crate::modifiers::{Arg, Builder};
+use crate::shared::Shared;
+use crate::string_builder::StringBuilder;
+
+const TT_MARKER_INIT: InjectionMarker = 0;
+const TT_MARKER_AFTER_TRUNCATE: InjectionMarker = 1;
+
+#[derive(Debug, Clone)]
+pub struct TruncateTableBuilder {
+    table: Option<String>,
+
+    args: Shared<Args>,
+    injection: Injection,
+    marker: InjectionMarker,
+}
+
+impl Default for TruncateTableBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TruncateTableBuilder {
+    pub fn new() -> Self {
+        Self {
+            table: None,
+            args: Shared::new(Args::default()),
+            injection: Injection::new(),
+            marker: TT_MARKER_INIT,
+        }
+    }
+
+    pub fn set_flavor(&mut self, flavor: Flavor) -> Flavor {
+        let mut a = self.args.borrow_mut();
+        let old = a.flavor;
+        a.flavor = flavor;
+        old
+    }
+
+    pub fn flavor(&self) -> Flavor {
+        self.args.borrow().flavor
+    }
+
+    pub fn truncate_table(&mut self, table: &str) -> &mut Self {
+        self.table = Some(table.to_string());
+        self.marker = TT_MARKER_AFTER_TRUNCATE;
+        self
+    }
+
+    pub fn sql(&mut self, sql: impl Into<String>) -> &mut Self {
+        self.injection.sql(self.marker, sql);
+        self
+    }
+}
+
+impl TruncateTableBuilder {
+    fn build_format(&self, flavor: Flavor) -> String {
+        let mut buf = StringBuilder::new();
+        write_injection(&mut buf, &self.injection, TT_MARKER_INIT);
+
+        // SQLite has no TRUNCATE; an unqualified DELETE uses its truncate optimization.
+        buf.write_leading(if flavor == Flavor::SQLite {
+            "DELETE FROM"
+        } else {
+            "TRUNCATE TABLE"
+        });
+        if let Some(t) = &self.table {
+            buf.write_leading(&quote_ident(flavor, t));
+        }
+        write_injection(&mut buf, &self.injection, TT_MARKER_AFTER_TRUNCATE);
+
+        buf.into_string()
+    }
+}
+
+impl Builder for TruncateTableBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        self.args
+            .snapshot()
+            .compile_with_flavor(&self.build_format(flavor), flavor, initial_arg)
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        self.args.snapshot().try_compile_with_flavor(
+            &self.build_format(flavor),
+            flavor,
+            initial_arg,
+        )
+    }
+
+    fn flavor(&self) -> Flavor {
+        self.flavor()
+    }
+}
+
+pub fn truncate_table(table: impl Into<String>) -> TruncateTableBuilder {
+    let mut builder = TruncateTableBuilder::new();
+    builder.truncate_table(&table.into());
+    builder
+}
+
+fn write_injection(buf: &mut StringBuilder, inj: &Injection, marker: InjectionMarker) {
+    let sqls = inj.at(marker);
+    if sqls.is_empty() {
+        return;
+    }
+    buf.write_leading("");
+    buf.write_str(&sqls.join(" "));
+}
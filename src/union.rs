@@ -1,32 +1,84 @@
-//! UnionBuilder: build UNION / UNION ALL statements.
+//! UnionBuilder: build UNION / INTERSECT / EXCEPT compound statements.
 
-use crate::args::Args;
+use crate::args::{Args, BuildError, unwrap_or_panic};
 use crate::flavor::Flavor;
 use crate::injection::{Injection, InjectionMarker};
 use crate::macros::{IntoStrings, collect_into_strings};
 use crate::modifiers::{Arg, Builder};
+use crate::order_by::{OrderByScope, OrderTerm, write_order_by};
+use crate::shared::{CowArc, Shared};
+use crate::sql_dialect::Pagination;
 use crate::string_builder::StringBuilder;
-use std::cell::RefCell;
-use std::rc::Rc;
-
-const UNION_DISTINCT: &str = " UNION ";
-const UNION_ALL: &str = " UNION ALL ";
 
 const UNION_MARKER_INIT: InjectionMarker = 0;
 const UNION_MARKER_AFTER_UNION: InjectionMarker = 1;
 const UNION_MARKER_AFTER_ORDER_BY: InjectionMarker = 2;
 const UNION_MARKER_AFTER_LIMIT: InjectionMarker = 3;
 
+/// Set operator joining two compound members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOp {
+    Union,
+    UnionAll,
+    Intersect,
+    IntersectAll,
+    /// `EXCEPT`; `MINUS` on Oracle.
+    Except,
+    ExceptAll,
+}
+
+impl SetOp {
+    fn standard(self) -> &'static str {
+        match self {
+            Self::Union => "UNION",
+            Self::UnionAll => "UNION ALL",
+            Self::Intersect => "INTERSECT",
+            Self::IntersectAll => "INTERSECT ALL",
+            Self::Except => "EXCEPT",
+            Self::ExceptAll => "EXCEPT ALL",
+        }
+    }
+
+    /// INTERSECT binds tighter than UNION and EXCEPT.
+    fn precedence(self) -> u8 {
+        match self {
+            Self::Intersect | Self::IntersectAll => 1,
+            _ => 0,
+        }
+    }
+
+    fn keyword(self, flavor: Flavor) -> Result<&'static str, BuildError> {
+        let unsupported = |feature| BuildError::Unsupported { flavor, feature };
+        match (self, flavor) {
+            (Self::Union | Self::UnionAll, _) => Ok(self.standard()),
+            (_, Flavor::CQL) => Err(unsupported("INTERSECT/EXCEPT")),
+            (
+                Self::IntersectAll | Self::ExceptAll,
+                Flavor::SQLite | Flavor::SQLServer | Flavor::Informix,
+            ) => Err(unsupported("INTERSECT ALL/EXCEPT ALL")),
+            (Self::Except, Flavor::Oracle) => Ok("MINUS"),
+            (Self::ExceptAll, Flavor::Oracle) => Ok("MINUS ALL"),
+            _ => Ok(self.standard()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Member {
+    /// Operator joining this member to the previous one; ignored for the first member.
+    op: SetOp,
+    var: String,
+}
+
 #[derive(Debug)]
 pub struct UnionBuilder {
-    opt: &'static str,
-    order_by_cols: Vec<String>,
+    order_by_cols: CowArc<Vec<OrderTerm>>,
     order: Option<&'static str>,
     limit_var: Option<String>,
     offset_var: Option<String>,
 
-    builder_vars: Vec<String>,
-    args: Rc<RefCell<Args>>,
+    members: CowArc<Vec<Member>>,
+    args: Shared<Args>,
 
     injection: Injection,
     marker: InjectionMarker,
@@ -47,13 +99,12 @@ impl Clone for UnionBuilder {
 impl UnionBuilder {
     pub fn new() -> Self {
         Self {
-            opt: UNION_DISTINCT,
-            order_by_cols: Vec::new(),
+            order_by_cols: CowArc::default(),
             order: None,
             limit_var: None,
             offset_var: None,
-            builder_vars: Vec::new(),
-            args: Rc::new(RefCell::new(Args::default())),
+            members: CowArc::default(),
+            args: Shared::new(Args::default()),
             injection: Injection::new(),
             marker: UNION_MARKER_INIT,
         }
@@ -72,13 +123,12 @@ impl UnionBuilder {
 
     pub fn clone_builder(&self) -> Self {
         Self {
-            opt: self.opt,
             order_by_cols: self.order_by_cols.clone(),
             order: self.order,
             limit_var: self.limit_var.clone(),
             offset_var: self.offset_var.clone(),
-            builder_vars: self.builder_vars.clone(),
-            args: Rc::new(RefCell::new(self.args.borrow().clone())),
+            members: self.members.clone(),
+            args: Shared::new(self.args.snapshot()),
             injection: self.injection.clone(),
             marker: self.marker,
         }
@@ -92,26 +142,64 @@ impl UnionBuilder {
         &mut self,
         builders: impl IntoIterator<Item = impl Builder + 'static>,
     ) -> &mut Self {
-        self.union_impl(UNION_DISTINCT, builders)
+        self.set_operation(SetOp::Union, builders)
     }
 
     pub fn union_all(
         &mut self,
         builders: impl IntoIterator<Item = impl Builder + 'static>,
     ) -> &mut Self {
-        self.union_impl(UNION_ALL, builders)
+        self.set_operation(SetOp::UnionAll, builders)
     }
 
-    fn union_impl(
+    pub fn intersect(
         &mut self,
-        opt: &'static str,
         builders: impl IntoIterator<Item = impl Builder + 'static>,
     ) -> &mut Self {
-        self.opt = opt;
-        self.builder_vars = builders
-            .into_iter()
-            .map(|b| self.var(Arg::Builder(Box::new(b))))
-            .collect();
+        self.set_operation(SetOp::Intersect, builders)
+    }
+
+    pub fn intersect_all(
+        &mut self,
+        builders: impl IntoIterator<Item = impl Builder + 'static>,
+    ) -> &mut Self {
+        self.set_operation(SetOp::IntersectAll, builders)
+    }
+
+    /// Except: `EXCEPT`, rendered as `MINUS` on Oracle.
+    pub fn except(
+        &mut self,
+        builders: impl IntoIterator<Item = impl Builder + 'static>,
+    ) -> &mut Self {
+        self.set_operation(SetOp::Except, builders)
+    }
+
+    pub fn except_all(
+        &mut self,
+        builders: impl IntoIterator<Item = impl Builder + 'static>,
+    ) -> &mut Self {
+        self.set_operation(SetOp::ExceptAll, builders)
+    }
+
+    /// SetOperation: replace the members with `builders` joined by `op`.
+    pub fn set_operation(
+        &mut self,
+        op: SetOp,
+        builders: impl IntoIterator<Item = impl Builder + 'static>,
+    ) -> &mut Self {
+        self.members.clear();
+        for b in builders {
+            self.then(op, b);
+        }
+        self
+    }
+
+    /// Then: append one member joined by `op`. Members combine left to right: a nested
+    /// `UnionBuilder` member groups its own operators, e.g. `(a UNION b) EXCEPT c`, and the
+    /// members so far are grouped when `op` binds tighter than an operator among them.
+    pub fn then(&mut self, op: SetOp, builder: impl Builder + 'static) -> &mut Self {
+        let var = self.var(Arg::Builder(Box::new(builder)));
+        self.members.push(Member { op, var });
         self.marker = UNION_MARKER_AFTER_UNION;
         self
     }
@@ -120,19 +208,30 @@ impl UnionBuilder {
     where
         T: IntoStrings,
     {
-        self.order_by_cols = collect_into_strings(cols);
+        self.order_by_cols = collect_into_strings(cols)
+            .into_iter()
+            .map(OrderTerm::new)
+            .collect();
+        self.marker = UNION_MARKER_AFTER_ORDER_BY;
+        self
+    }
+
+    /// OrderByTerm: append a structured term with its own direction and NULLS placement.
+    pub fn order_by_term(&mut self, term: impl Into<OrderTerm>) -> &mut Self {
+        let term = term.into().bind(|a| self.var(a));
+        self.order_by_cols.push(term);
         self.marker = UNION_MARKER_AFTER_ORDER_BY;
         self
     }
 
     pub fn order_by_asc(&mut self, col: impl Into<String>) -> &mut Self {
-        self.order_by_cols.push(format!("{} ASC", col.into()));
+        self.order_by_cols.push(OrderTerm::new(col).asc());
         self.marker = UNION_MARKER_AFTER_ORDER_BY;
         self
     }
 
     pub fn order_by_desc(&mut self, col: impl Into<String>) -> &mut Self {
-        self.order_by_cols.push(format!("{} DESC", col.into()));
+        self.order_by_cols.push(OrderTerm::new(col).desc());
         self.marker = UNION_MARKER_AFTER_ORDER_BY;
         self
     }
@@ -175,8 +274,8 @@ impl UnionBuilder {
     }
 }
 
-impl Builder for UnionBuilder {
-    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+impl UnionBuilder {
+    fn build_format(&self, flavor: Flavor) -> Result<String, BuildError> {
         let mut buf = StringBuilder::new();
         write_injection(&mut buf, &self.injection, UNION_MARKER_INIT);
 
@@ -184,32 +283,45 @@ impl Builder for UnionBuilder {
             && (self.limit_var.is_some() || self.offset_var.is_some()))
             || (flavor == Flavor::Informix && self.limit_var.is_some());
 
-        if !self.builder_vars.is_empty() {
+        if !self.members.is_empty() {
+            // SQLite rejects parenthesized compound members and evaluates operators strictly left
+            // to right; members that are not plain SELECTs become subqueries there.
             let need_paren = flavor != Flavor::SQLite;
 
             if nested_select {
                 buf.write_leading("SELECT * FROM (");
             }
 
-            // first
-            if need_paren {
-                buf.write_leading("(");
-                buf.write_str(&self.builder_vars[0]);
-                buf.write_str(")");
-            } else {
-                buf.write_leading(&self.builder_vars[0]);
-            }
-
-            for b in self.builder_vars.iter().skip(1) {
-                buf.write_str(self.opt);
-                if need_paren {
-                    buf.write_str("(");
+            let args = self.args.snapshot();
+            let mut body = String::new();
+            // Loosest operator in `body` outside parentheses.
+            let mut loosest: Option<u8> = None;
+            for (i, m) in self.members.iter().enumerate() {
+                if i > 0 {
+                    let keyword = m.op.keyword(flavor)?;
+                    let prec = m.op.precedence();
+                    if need_paren && loosest.is_some_and(|l| l < prec) {
+                        body = format!("({body})");
+                        loosest = None;
+                    }
+                    loosest = Some(loosest.map_or(prec, |l| l.min(prec)));
+                    body.push(' ');
+                    body.push_str(keyword);
+                    body.push(' ');
                 }
-                buf.write_str(b);
+                let needs_subquery = matches!(
+                    args.value(&m.var),
+                    Some(Arg::Builder(b)) if b.needs_subquery_in_compound()
+                );
                 if need_paren {
-                    buf.write_str(")");
+                    body.push_str(&format!("({})", m.var));
+                } else if needs_subquery {
+                    body.push_str(&format!("SELECT * FROM ({})", m.var));
+                } else {
+                    body.push_str(&m.var);
                 }
             }
+            buf.write_leading(&body);
 
             if nested_select {
                 buf.write_leading(")");
@@ -219,99 +331,17 @@ impl Builder for UnionBuilder {
         write_injection(&mut buf, &self.injection, UNION_MARKER_AFTER_UNION);
 
         if !self.order_by_cols.is_empty() {
-            buf.write_leading("ORDER BY");
-            buf.write_str(" ");
-            buf.write_str(&self.order_by_cols.join(", "));
-            if let Some(order) = self.order {
-                buf.write_str(" ");
-                buf.write_str(order);
-            }
+            write_order_by(
+                &mut buf,
+                &self.order_by_cols,
+                self.order,
+                flavor,
+                OrderByScope::Union,
+            )?;
             write_injection(&mut buf, &self.injection, UNION_MARKER_AFTER_ORDER_BY);
         }
 
         match flavor {
-            Flavor::MySQL | Flavor::SQLite | Flavor::ClickHouse => {
-                if let Some(lim) = &self.limit_var {
-                    buf.write_leading("LIMIT");
-                    buf.write_str(" ");
-                    buf.write_str(lim);
-                    if let Some(off) = &self.offset_var {
-                        buf.write_leading("OFFSET");
-                        buf.write_str(" ");
-                        buf.write_str(off);
-                    }
-                }
-            }
-            Flavor::CQL => {
-                if let Some(lim) = &self.limit_var {
-                    buf.write_leading("LIMIT");
-                    buf.write_str(" ");
-                    buf.write_str(lim);
-                }
-            }
-            Flavor::PostgreSQL => {
-                if let Some(lim) = &self.limit_var {
-                    buf.write_leading("LIMIT");
-                    buf.write_str(" ");
-                    buf.write_str(lim);
-                }
-                if let Some(off) = &self.offset_var {
-                    buf.write_leading("OFFSET");
-                    buf.write_str(" ");
-                    buf.write_str(off);
-                }
-            }
-            Flavor::Presto => {
-                if let Some(off) = &self.offset_var {
-                    buf.write_leading("OFFSET");
-                    buf.write_str(" ");
-                    buf.write_str(off);
-                }
-                if let Some(lim) = &self.limit_var {
-                    buf.write_leading("LIMIT");
-                    buf.write_str(" ");
-                    buf.write_str(lim);
-                }
-            }
-            Flavor::SQLServer => {
-                if self.order_by_cols.is_empty()
-                    && (self.limit_var.is_some() || self.offset_var.is_some())
-                {
-                    buf.write_leading("ORDER BY 1");
-                }
-                if let Some(off) = &self.offset_var {
-                    buf.write_leading("OFFSET");
-                    buf.write_str(" ");
-                    buf.write_str(off);
-                    buf.write_str(" ROWS");
-                }
-                if let Some(lim) = &self.limit_var {
-                    if self.offset_var.is_none() {
-                        buf.write_leading("OFFSET 0 ROWS");
-                    }
-                    buf.write_leading("FETCH NEXT");
-                    buf.write_str(" ");
-                    buf.write_str(lim);
-                    buf.write_str(" ROWS ONLY");
-                }
-            }
-            Flavor::Oracle => {
-                if let Some(off) = &self.offset_var {
-                    buf.write_leading("OFFSET");
-                    buf.write_str(" ");
-                    buf.write_str(off);
-                    buf.write_str(" ROWS");
-                }
-                if let Some(lim) = &self.limit_var {
-                    if self.offset_var.is_none() {
-                        buf.write_leading("OFFSET 0 ROWS");
-                    }
-                    buf.write_leading("FETCH NEXT");
-                    buf.write_str(" ");
-                    buf.write_str(lim);
-                    buf.write_str(" ROWS ONLY");
-                }
-            }
             Flavor::Informix => {
                 // Informix:
                 // - offset 无 limit 时忽略
@@ -355,15 +385,60 @@ impl Builder for UnionBuilder {
                     }
                 }
             }
+            _ => flavor.dialect().write_pagination(
+                buf.as_mut_string(),
+                &Pagination {
+                    limit: self.limit_var.as_deref(),
+                    offset: self.offset_var.as_deref(),
+                    has_order_by: !self.order_by_cols.is_empty(),
+                },
+            ),
         }
 
         if self.limit_var.is_some() {
             write_injection(&mut buf, &self.injection, UNION_MARKER_AFTER_LIMIT);
         }
 
+        Ok(buf.into_string())
+    }
+}
+
+impl Builder for UnionBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        let format = unwrap_or_panic(self.build_format(flavor));
+        self.args
+            .snapshot()
+            .compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        let format = self.build_format(flavor)?;
+        self.args
+            .snapshot()
+            .try_compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        let format = self.build_format(flavor)?;
         self.args
-            .borrow()
-            .compile_with_flavor(&buf.into_string(), flavor, initial_arg)
+            .snapshot()
+            .compile_into(&format, flavor, sql, args)
+    }
+
+    fn needs_subquery_in_compound(&self) -> bool {
+        self.members.len() > 1
+            || !self.order_by_cols.is_empty()
+            || self.limit_var.is_some()
+            || self.offset_var.is_some()
     }
 
     fn flavor(&self) -> Flavor {
@@ -1,8 +1,10 @@
 #[cfg(test)]
 mod tests {
+    use crate::args::BuildError;
     use crate::builder;
-    use crate::modifiers::{Arg, Builder};
-    use crate::{Flavor, SelectBuilder, UnionBuilder, set_default_flavor_scoped};
+    use crate::modifiers::{Arg, Builder, rc_builder};
+    use crate::shared::Shared;
+    use crate::{Flavor, SelectBuilder, SetOp, UnionBuilder, set_default_flavor_scoped};
     use pretty_assertions::{assert_eq, assert_ne};
 
     #[test]
@@ -299,4 +301,134 @@ mod tests {
         let (sql_original, _) = ub.build();
         assert_ne!(sql_original, sql_after);
     }
+
+    fn ids(table: &str) -> SelectBuilder {
+        let mut sb = SelectBuilder::new();
+        sb.select(vec!["id"]);
+        sb.from(vec![table]);
+        sb
+    }
+
+    #[test]
+    fn intersect_and_except_per_flavor() {
+        let mut ub = UnionBuilder::new();
+        ub.intersect([ids("a"), ids("b")]);
+        let (sql, _) = ub.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(sql, "(SELECT id FROM a) INTERSECT (SELECT id FROM b)");
+
+        let mut ub = UnionBuilder::new();
+        ub.except([ids("a"), ids("b")]);
+        let (sql, _) = ub.build_with_flavor(Flavor::Oracle, &[]);
+        assert_eq!(sql, "(SELECT id FROM a) MINUS (SELECT id FROM b)");
+        let (sql, _) = ub.build_with_flavor(Flavor::SQLite, &[]);
+        assert_eq!(sql, "SELECT id FROM a EXCEPT SELECT id FROM b");
+
+        let mut ub = UnionBuilder::new();
+        ub.except_all([ids("a"), ids("b")]);
+        let (sql, _) = ub.build_with_flavor(Flavor::Oracle, &[]);
+        assert_eq!(sql, "(SELECT id FROM a) MINUS ALL (SELECT id FROM b)");
+        assert_eq!(
+            ub.try_build_with_flavor(Flavor::SQLServer, &[])
+                .unwrap_err(),
+            BuildError::Unsupported {
+                flavor: Flavor::SQLServer,
+                feature: "INTERSECT ALL/EXCEPT ALL",
+            }
+        );
+    }
+
+    #[test]
+    fn mixed_set_operators_with_grouping() {
+        let mut inner = UnionBuilder::new();
+        inner.union([ids("a"), ids("b")]);
+
+        let mut c = ids("c");
+        let cond = c.greater_than("id", 10_i64);
+        c.where_(vec![cond]);
+
+        let mut ub = UnionBuilder::new();
+        ub.then(SetOp::Union, inner)
+            .then(SetOp::Except, c)
+            .then(SetOp::IntersectAll, ids("d"))
+            .order_by(["id"]);
+
+        let (sql, args) = ub.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "(((SELECT id FROM a) UNION (SELECT id FROM b)) \
+             EXCEPT (SELECT id FROM c WHERE id > $1)) \
+             INTERSECT ALL (SELECT id FROM d) ORDER BY id"
+        );
+        assert_eq!(args, vec![Arg::from(10_i64)]);
+
+        let mut inner = UnionBuilder::new();
+        inner.union([ids("a"), ids("b")]);
+        let mut ub = UnionBuilder::new();
+        ub.then(SetOp::Union, inner).then(SetOp::Except, ids("c"));
+        let (sql, _) = ub.build_with_flavor(Flavor::SQLite, &[]);
+        assert_eq!(
+            sql,
+            "SELECT * FROM (SELECT id FROM a UNION SELECT id FROM b) EXCEPT SELECT id FROM c"
+        );
+    }
+
+    #[test]
+    fn intersect_groups_looser_operators_on_its_left() {
+        let mut ub = UnionBuilder::new();
+        ub.then(SetOp::Union, ids("a"))
+            .then(SetOp::Union, ids("b"))
+            .then(SetOp::Intersect, ids("c"))
+            .then(SetOp::Except, ids("d"));
+        let (sql, _) = ub.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "((SELECT id FROM a) UNION (SELECT id FROM b)) INTERSECT (SELECT id FROM c) \
+             EXCEPT (SELECT id FROM d)"
+        );
+
+        let mut ub = UnionBuilder::new();
+        ub.then(SetOp::Intersect, ids("a"))
+            .then(SetOp::Intersect, ids("b"))
+            .then(SetOp::Union, ids("c"));
+        let (sql, _) = ub.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "(SELECT id FROM a) INTERSECT (SELECT id FROM b) UNION (SELECT id FROM c)"
+        );
+
+        // SQLite evaluates left to right and has no parenthesized members.
+        let mut ub = UnionBuilder::new();
+        ub.then(SetOp::Union, ids("a"))
+            .then(SetOp::Union, ids("b"))
+            .then(SetOp::Intersect, ids("c"));
+        let (sql, _) = ub.build_with_flavor(Flavor::SQLite, &[]);
+        assert_eq!(
+            sql,
+            "SELECT id FROM a UNION SELECT id FROM b INTERSECT SELECT id FROM c"
+        );
+    }
+
+    #[test]
+    fn sqlite_wraps_members_that_are_not_plain_selects() {
+        let mut top = ids("scores");
+        top.order_by_desc("id").limit(3);
+
+        let mut inner = UnionBuilder::new();
+        inner.union([ids("a"), ids("b")]);
+        let shared_inner = rc_builder(Shared::new(inner));
+        let boxed_top: Box<dyn Builder> = Box::new(top);
+
+        let mut ub = UnionBuilder::new();
+        ub.then(SetOp::Union, boxed_top)
+            .then(SetOp::Union, shared_inner)
+            .then(SetOp::Union, ids("c"));
+        let (sql, args) = ub.build_with_flavor(Flavor::SQLite, &[]);
+        assert_eq!(
+            sql,
+            "SELECT * FROM (SELECT id FROM scores ORDER BY id DESC LIMIT ?) \
+             UNION SELECT * FROM (SELECT id FROM a UNION SELECT id FROM b) \
+             UNION SELECT id FROM c"
+        );
+        assert_eq!(args, vec![Arg::from(3_i64)]);
+    }
 }
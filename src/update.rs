@@ -1,17 +1,23 @@
 //! UpdateBuilder: build UPDATE statements.
 
-use crate::args::Args;
+use crate::args::{Args, BuildError, unwrap_or_panic};
 use crate::cond::{ArgsRef, Cond};
 use crate::cte::CTEBuilder;
 use crate::flavor::Flavor;
 use crate::injection::{Injection, InjectionMarker};
 use crate::macros::{IntoStrings, collect_into_strings};
-use crate::modifiers::{Arg, Builder, escape};
+use crate::modifiers::{Arg, Builder, arith_assignment, escape};
+use crate::order_by::{OrderByScope, OrderTerm, write_order_by};
+use crate::select::JoinOption;
+use crate::shared::{CowArc, Shared};
+use crate::sql_dialect::{ReturningStyle, UpdateJoinStyle};
 use crate::string_builder::StringBuilder;
-use crate::where_clause::{WhereClause, WhereClauseBuilder, WhereClauseRef};
-use std::cell::RefCell;
+use crate::where_clause::{
+    WhereClause, WhereClauseBuilder, WhereClauseRef, copy_where_clause, rebind_where_var,
+    where_with,
+};
 use std::ops::Deref;
-use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
 
 const UPDATE_MARKER_INIT: InjectionMarker = 0;
 const UPDATE_MARKER_AFTER_WITH: InjectionMarker = 1;
@@ -21,24 +27,70 @@ const UPDATE_MARKER_AFTER_WHERE: InjectionMarker = 4;
 const UPDATE_MARKER_AFTER_ORDER_BY: InjectionMarker = 5;
 const UPDATE_MARKER_AFTER_LIMIT: InjectionMarker = 6;
 const UPDATE_MARKER_AFTER_RETURNING: InjectionMarker = 7;
+const UPDATE_MARKER_AFTER_JOIN: InjectionMarker = 8;
+
+/// One `[option] JOIN table ON ...` of a multi-table UPDATE or DELETE.
+#[derive(Debug, Clone)]
+pub(crate) struct TableJoin {
+    pub(crate) option: Option<JoinOption>,
+    pub(crate) table: String,
+    pub(crate) on: Vec<String>,
+}
+
+impl TableJoin {
+    pub(crate) fn new(option: Option<JoinOption>, table: String, on: Vec<String>) -> Self {
+        let on = on.into_iter().filter(|s| !s.is_empty()).collect();
+        Self { option, table, on }
+    }
+
+    pub(crate) fn is_inner(&self) -> bool {
+        matches!(self.option, None | Some(JoinOption::InnerJoin))
+    }
+
+    /// `a AND b`; empty without ON expressions.
+    pub(crate) fn on_expr(&self) -> String {
+        self.on.join(" AND ")
+    }
+
+    pub(crate) fn render(&self) -> String {
+        let mut s = match self.option {
+            Some(opt) => format!("{} JOIN {}", opt.as_str(), self.table),
+            None => format!("JOIN {}", self.table),
+        };
+        if !self.on.is_empty() {
+            s.push_str(" ON ");
+            s.push_str(&self.on_expr());
+        }
+        s
+    }
+}
+
+/// Alias of a table expression: `t` for `users t` / `users AS t`, else the table itself.
+pub(crate) fn table_alias(table: &str) -> &str {
+    table.split_whitespace().last().unwrap_or(table)
+}
 
 #[derive(Debug)]
 pub struct UpdateBuilder {
     args: ArgsRef,
     cond: Cond,
 
-    tables: Vec<String>,
-    assignments: Vec<String>,
+    tables: CowArc<Vec<String>>,
+    assignments: CowArc<Vec<String>>,
 
     where_clause: Option<WhereClauseRef>,
     where_var: Option<String>,
+    // See `rebind_where_var`.
+    where_stale: AtomicBool,
     cte_var: Option<String>,
     cte: Option<CTEBuilder>,
 
-    order_by_cols: Vec<String>,
+    order_by_cols: CowArc<Vec<OrderTerm>>,
     order: Option<&'static str>,
     limit_var: Option<String>,
-    returning: Vec<String>,
+    returning: CowArc<Vec<String>>,
+    joins: CowArc<Vec<TableJoin>>,
+    allow_full_table: bool,
 
     injection: Injection,
     marker: InjectionMarker,
@@ -65,21 +117,24 @@ impl Clone for UpdateBuilder {
 
 impl UpdateBuilder {
     pub fn new() -> Self {
-        let args = Rc::new(RefCell::new(Args::default()));
+        let args = Shared::new(Args::default());
         let cond = Cond::with_args(args.clone());
         Self {
             args,
             cond,
-            tables: Vec::new(),
-            assignments: Vec::new(),
+            tables: CowArc::default(),
+            assignments: CowArc::default(),
             where_clause: None,
             where_var: None,
+            where_stale: AtomicBool::new(false),
             cte_var: None,
             cte: None,
-            order_by_cols: Vec::new(),
+            order_by_cols: CowArc::default(),
             order: None,
             limit_var: None,
-            returning: Vec::new(),
+            returning: CowArc::default(),
+            joins: CowArc::default(),
+            allow_full_table: false,
             injection: Injection::new(),
             marker: UPDATE_MARKER_INIT,
         }
@@ -108,7 +163,7 @@ impl UpdateBuilder {
     fn table_names(&self) -> Vec<String> {
         let mut table_names = Vec::new();
         if !self.tables.is_empty() {
-            table_names.extend(self.tables.clone());
+            table_names.extend(self.tables.iter().cloned());
         }
         if let Some(cte) = &self.cte {
             table_names.extend(cte.table_names_for_from());
@@ -147,47 +202,28 @@ impl UpdateBuilder {
     }
 
     pub fn clone_builder(&self) -> Self {
-        let old_args = self.args.borrow().clone();
-        let args = Rc::new(RefCell::new(old_args));
+        let args = Shared::new(self.args.snapshot());
         let cond = Cond::with_args(args.clone());
 
-        let mut cloned = Self {
+        Self {
             args,
             cond,
             tables: self.tables.clone(),
             assignments: self.assignments.clone(),
-            where_clause: self.where_clause.clone(),
+            where_clause: self.where_clause.as_ref().map(copy_where_clause),
             where_var: self.where_var.clone(),
+            where_stale: AtomicBool::new(self.where_clause.is_some()),
             cte_var: self.cte_var.clone(),
             cte: self.cte.clone(),
             order_by_cols: self.order_by_cols.clone(),
             order: self.order,
             limit_var: self.limit_var.clone(),
             returning: self.returning.clone(),
+            joins: self.joins.clone(),
+            allow_full_table: self.allow_full_table,
             injection: self.injection.clone(),
             marker: self.marker,
-        };
-
-        if let (Some(wc), Some(ph)) = (&self.where_clause, &self.where_var) {
-            let new_wc = Rc::new(RefCell::new(wc.borrow().clone()));
-            cloned.where_clause = Some(new_wc.clone());
-            cloned
-                .args
-                .borrow_mut()
-                .replace(ph, Arg::Builder(Box::new(WhereClauseBuilder::new(new_wc))));
-        }
-
-        if let (Some(cte), Some(ph)) = (&self.cte, &self.cte_var) {
-            let cte_for_arg = cte.clone();
-            let cte_for_field = cte_for_arg.clone();
-            cloned.cte = Some(cte_for_field);
-            cloned
-                .args
-                .borrow_mut()
-                .replace(ph, Arg::Builder(Box::new(cte_for_arg)));
         }
-
-        cloned
     }
 
     pub fn build(&self) -> (String, Vec<Arg>) {
@@ -202,16 +238,38 @@ impl UpdateBuilder {
     where
         T: IntoStrings,
     {
-        self.tables = collect_into_strings(tables);
+        self.tables = collect_into_strings(tables).into();
         self.marker = UPDATE_MARKER_AFTER_UPDATE;
         self
     }
 
+    /// Join: update using rows of another table. Renders `UPDATE t JOIN s ON ... SET` on
+    /// MySQL/Doris, `UPDATE t SET ... FROM s WHERE ...` on PostgreSQL/SQLite and
+    /// `UPDATE t SET ... FROM t JOIN s ON ...` on SQL Server.
+    pub fn join(&mut self, table: impl Into<String>, on_expr: impl IntoStrings) -> &mut Self {
+        self.join_with_option(None, table, on_expr)
+    }
+
+    pub fn join_with_option(
+        &mut self,
+        option: Option<JoinOption>,
+        table: impl Into<String>,
+        on_expr: impl IntoStrings,
+    ) -> &mut Self {
+        self.joins.push(TableJoin::new(
+            option,
+            table.into(),
+            collect_into_strings(on_expr),
+        ));
+        self.marker = UPDATE_MARKER_AFTER_JOIN;
+        self
+    }
+
     pub fn set<T>(&mut self, assignments: T) -> &mut Self
     where
         T: IntoStrings,
     {
-        self.assignments = collect_into_strings(assignments);
+        self.assignments = collect_into_strings(assignments).into();
         self.marker = UPDATE_MARKER_AFTER_SET;
         self
     }
@@ -305,18 +363,15 @@ impl UpdateBuilder {
     }
 
     pub fn incr(&self, field: &str) -> String {
-        let f = escape(field);
-        format!("{f} = {f} + 1")
+        self.arith(field, '+', "1")
     }
 
     pub fn decr(&self, field: &str) -> String {
-        let f = escape(field);
-        format!("{f} = {f} - 1")
+        self.arith(field, '-', "1")
     }
 
     pub fn add_(&self, field: &str, value: impl Into<Arg>) -> String {
-        let f = escape(field);
-        format!("{f} = {f} + {}", self.var(value))
+        self.arith(field, '+', &self.var(value))
     }
 
     /// Add an assignment expression.
@@ -325,37 +380,50 @@ impl UpdateBuilder {
     }
 
     pub fn sub(&self, field: &str, value: impl Into<Arg>) -> String {
-        let f = escape(field);
-        format!("{f} = {f} - {}", self.var(value))
+        self.arith(field, '-', &self.var(value))
     }
 
     pub fn mul(&self, field: &str, value: impl Into<Arg>) -> String {
-        let f = escape(field);
-        format!("{f} = {f} * {}", self.var(value))
+        self.arith(field, '*', &self.var(value))
     }
 
     pub fn div(&self, field: &str, value: impl Into<Arg>) -> String {
+        self.arith(field, '/', &self.var(value))
+    }
+
+    fn arith(&self, field: &str, op: char, rhs: &str) -> String {
         let f = escape(field);
-        format!("{f} = {f} / {}", self.var(value))
+        arith_assignment(&f, &f, op, rhs)
     }
 
     pub fn order_by<T>(&mut self, cols: T) -> &mut Self
     where
         T: IntoStrings,
     {
-        self.order_by_cols = collect_into_strings(cols);
+        self.order_by_cols = collect_into_strings(cols)
+            .into_iter()
+            .map(OrderTerm::new)
+            .collect();
+        self.marker = UPDATE_MARKER_AFTER_ORDER_BY;
+        self
+    }
+
+    /// OrderByTerm: append a structured term with its own direction and NULLS placement.
+    pub fn order_by_term(&mut self, term: impl Into<OrderTerm>) -> &mut Self {
+        let term = term.into().bind(|a| self.var(a));
+        self.order_by_cols.push(term);
         self.marker = UPDATE_MARKER_AFTER_ORDER_BY;
         self
     }
 
     pub fn order_by_asc(&mut self, col: impl Into<String>) -> &mut Self {
-        self.order_by_cols.push(format!("{} ASC", col.into()));
+        self.order_by_cols.push(OrderTerm::new(col).asc());
         self.marker = UPDATE_MARKER_AFTER_ORDER_BY;
         self
     }
 
     pub fn order_by_desc(&mut self, col: impl Into<String>) -> &mut Self {
-        self.order_by_cols.push(format!("{} DESC", col.into()));
+        self.order_by_cols.push(OrderTerm::new(col).desc());
         self.marker = UPDATE_MARKER_AFTER_ORDER_BY;
         self
     }
@@ -386,7 +454,7 @@ impl UpdateBuilder {
     where
         T: IntoStrings,
     {
-        self.returning = collect_into_strings(cols);
+        self.returning = collect_into_strings(cols).into();
         self.marker = UPDATE_MARKER_AFTER_RETURNING;
         self
     }
@@ -396,14 +464,49 @@ impl UpdateBuilder {
         self.assignments.iter().filter(|s| !s.is_empty()).count()
     }
 
+    /// Permit building without a WHERE predicate. Without it, `build` panics and `try_build`
+    /// returns `BuildError::MissingWhere` so a statement never touches every row by accident.
+    pub fn allow_full_table(&mut self) -> &mut Self {
+        self.allow_full_table = true;
+        self
+    }
+
     pub fn sql(&mut self, sql: impl Into<String>) -> &mut Self {
         self.injection.sql(self.marker, sql);
         self
     }
 }
 
-impl Builder for UpdateBuilder {
-    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+impl UpdateBuilder {
+    /// Passes when a WHERE predicate or an inner join's ON restricts the affected rows. A
+    /// builder without a target table renders nothing and is not guarded.
+    fn check_where(&self) -> Result<(), BuildError> {
+        let has_where = self
+            .where_clause
+            .as_ref()
+            .is_some_and(|wc| !wc.borrow().is_empty());
+        let has_join_on = self
+            .joins
+            .iter()
+            .any(|j| j.is_inner() && !j.on_expr().is_empty());
+        if has_where || has_join_on || self.allow_full_table || self.table_names().is_empty() {
+            return Ok(());
+        }
+        Err(BuildError::MissingWhere {
+            statement: "UPDATE",
+        })
+    }
+
+    fn rebind_where_var(&self) {
+        rebind_where_var(
+            &self.args,
+            self.where_clause.as_ref(),
+            self.where_var.as_ref(),
+            &self.where_stale,
+        );
+    }
+
+    fn build_format(&self, flavor: Flavor) -> Result<String, BuildError> {
         let mut buf = StringBuilder::new();
         write_injection(&mut buf, &self.injection, UPDATE_MARKER_INIT);
 
@@ -412,7 +515,19 @@ impl Builder for UpdateBuilder {
             write_injection(&mut buf, &self.injection, UPDATE_MARKER_AFTER_WITH);
         }
 
+        let join_style = if self.joins.is_empty() {
+            None
+        } else {
+            Some(self.join_style(flavor)?)
+        };
+
         match flavor {
+            _ if join_style == Some(UpdateJoinStyle::FromSelf) => {
+                let aliases: Vec<&str> = self.tables.iter().map(|t| table_alias(t)).collect();
+                buf.write_leading("UPDATE");
+                buf.write_str(" ");
+                buf.write_str(&aliases.join(", "));
+            }
             Flavor::MySQL => {
                 let table_names = self.table_names();
                 if !table_names.is_empty() {
@@ -431,6 +546,13 @@ impl Builder for UpdateBuilder {
         }
         write_injection(&mut buf, &self.injection, UPDATE_MARKER_AFTER_UPDATE);
 
+        if join_style == Some(UpdateJoinStyle::Inline) {
+            for join in &self.joins {
+                buf.write_leading(&join.render());
+            }
+            write_injection(&mut buf, &self.injection, UPDATE_MARKER_AFTER_JOIN);
+        }
+
         let assigns: Vec<String> = self
             .assignments
             .iter()
@@ -444,18 +566,9 @@ impl Builder for UpdateBuilder {
         }
         write_injection(&mut buf, &self.injection, UPDATE_MARKER_AFTER_SET);
 
-        if flavor != Flavor::MySQL
-            && let Some(cte) = &self.cte
-        {
-            let cte_table_names = cte.table_names_for_from();
-            if !cte_table_names.is_empty() {
-                buf.write_leading("FROM");
-                buf.write_str(" ");
-                buf.write_str(&cte_table_names.join(", "));
-            }
-        }
-
-        if flavor == Flavor::SQLServer && !self.returning.is_empty() {
+        // SQL Server requires OUTPUT before FROM.
+        let returning_style = flavor.dialect().returning_style();
+        if returning_style == ReturningStyle::Output && !self.returning.is_empty() {
             buf.write_leading("OUTPUT");
             buf.write_str(" ");
             let prefixed: Vec<String> = self
@@ -467,19 +580,62 @@ impl Builder for UpdateBuilder {
             write_injection(&mut buf, &self.injection, UPDATE_MARKER_AFTER_RETURNING);
         }
 
-        if let Some(ph) = &self.where_var {
-            buf.write_leading(ph);
+        let mut from_items = Vec::new();
+        let mut join_where = None;
+        match join_style {
+            Some(UpdateJoinStyle::From) => {
+                let (first, rest) = self.joins.split_first().expect("joins are not empty");
+                let mut item = first.table.clone();
+                for join in rest {
+                    item.push(' ');
+                    item.push_str(&join.render());
+                }
+                from_items.push(item);
+                join_where = Some(first.on_expr()).filter(|s| !s.is_empty());
+            }
+            Some(UpdateJoinStyle::FromSelf) => {
+                let mut item = self.tables.join(", ");
+                for join in &self.joins {
+                    item.push(' ');
+                    item.push_str(&join.render());
+                }
+                from_items.push(item);
+            }
+            _ => {}
+        }
+        if flavor != Flavor::MySQL
+            && let Some(cte) = &self.cte
+        {
+            from_items.extend(cte.table_names_for_from());
+        }
+        if !from_items.is_empty() {
+            buf.write_leading("FROM");
+            buf.write_str(" ");
+            buf.write_str(&from_items.join(", "));
+            if join_style.is_some() {
+                write_injection(&mut buf, &self.injection, UPDATE_MARKER_AFTER_JOIN);
+            }
+        }
+
+        if let Some(w) = where_with(
+            self.where_var.as_ref(),
+            self.where_clause.as_ref(),
+            join_where,
+        ) {
+            buf.write_leading(&w);
+        }
+        if self.where_var.is_some() || join_style == Some(UpdateJoinStyle::From) {
             write_injection(&mut buf, &self.injection, UPDATE_MARKER_AFTER_WHERE);
         }
 
         if !self.order_by_cols.is_empty() {
-            buf.write_leading("ORDER BY");
-            buf.write_str(" ");
-            buf.write_str(&self.order_by_cols.join(", "));
-            if let Some(order) = self.order {
-                buf.write_str(" ");
-                buf.write_str(order);
-            }
+            write_order_by(
+                &mut buf,
+                &self.order_by_cols,
+                self.order,
+                flavor,
+                OrderByScope::Statement,
+            )?;
             write_injection(&mut buf, &self.injection, UPDATE_MARKER_AFTER_ORDER_BY);
         }
 
@@ -490,17 +646,67 @@ impl Builder for UpdateBuilder {
             write_injection(&mut buf, &self.injection, UPDATE_MARKER_AFTER_LIMIT);
         }
 
-        if (flavor == Flavor::PostgreSQL || flavor == Flavor::SQLite) && !self.returning.is_empty()
-        {
+        if returning_style == ReturningStyle::Returning && !self.returning.is_empty() {
             buf.write_leading("RETURNING");
             buf.write_str(" ");
             buf.write_str(&self.returning.join(", "));
             write_injection(&mut buf, &self.injection, UPDATE_MARKER_AFTER_RETURNING);
         }
 
+        Ok(buf.into_string())
+    }
+}
+
+impl UpdateBuilder {
+    fn join_style(&self, flavor: Flavor) -> Result<UpdateJoinStyle, BuildError> {
+        let unsupported = |feature| BuildError::Unsupported { flavor, feature };
+        match flavor.dialect().update_join_style() {
+            UpdateJoinStyle::Unsupported => Err(unsupported("UPDATE with JOIN")),
+            // The first joined table becomes a FROM item and its ON moves into WHERE, which only
+            // preserves inner-join semantics.
+            UpdateJoinStyle::From if !self.joins[0].is_inner() => {
+                Err(unsupported("outer join as the first UPDATE ... FROM item"))
+            }
+            style => Ok(style),
+        }
+    }
+}
+
+impl Builder for UpdateBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        self.rebind_where_var();
+        unwrap_or_panic(self.check_where());
+        let format = unwrap_or_panic(self.build_format(flavor));
+        self.args
+            .snapshot()
+            .compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        self.rebind_where_var();
+        self.check_where()?;
+        let format = self.build_format(flavor)?;
+        self.args
+            .snapshot()
+            .try_compile_with_flavor(&format, flavor, initial_arg)
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        self.rebind_where_var();
+        self.check_where()?;
+        let format = self.build_format(flavor)?;
         self.args
-            .borrow()
-            .compile_with_flavor(&buf.into_string(), flavor, initial_arg)
+            .snapshot()
+            .compile_into(&format, flavor, sql, args)
     }
 
     fn flavor(&self) -> Flavor {
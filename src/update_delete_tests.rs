@@ -1,7 +1,11 @@
 #[cfg(test)]
 mod tests {
-    use crate::modifiers::Builder;
-    use crate::{DeleteBuilder, Flavor, UpdateBuilder, set_default_flavor_scoped};
+    use crate::args::BuildError;
+    use crate::modifiers::{Arg, Builder};
+    use crate::{
+        Condition, DeleteBuilder, Flavor, JoinOption, Operator, UpdateBuilder, WhereClause,
+        build_delete_with_flavor, build_update_with_flavor, set_default_flavor_scoped,
+    };
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -122,4 +126,350 @@ mod tests {
             "DELETE FROM user OUTPUT DELETED.id, DELETED.deleted_at WHERE id = @p1"
         );
     }
+
+    fn update_with_join() -> UpdateBuilder {
+        let mut ub = UpdateBuilder::new();
+        ub.update(["orders o"]);
+        let on = ub.equal("c.tier", "gold");
+        ub.join("customers c", ["c.id = o.customer_id", on.as_str()]);
+        ub.set([ub.assign("o.discount", 10_i64)]);
+        let cond = ub.greater_than("o.total", 100_i64);
+        ub.where_([cond]);
+        ub
+    }
+
+    #[test]
+    fn update_join_per_flavor() {
+        let ub = update_with_join();
+
+        let (sql, args) = ub.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "UPDATE orders o JOIN customers c ON c.id = o.customer_id AND c.tier = ? \
+             SET o.discount = ? WHERE o.total > ?"
+        );
+        assert_eq!(
+            args,
+            vec![Arg::from("gold"), Arg::from(10_i64), Arg::from(100_i64)]
+        );
+
+        let (sql, args) = ub.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "UPDATE orders o SET o.discount = $1 FROM customers c \
+             WHERE o.total > $2 AND c.id = o.customer_id AND c.tier = $3"
+        );
+        assert_eq!(
+            args,
+            vec![Arg::from(10_i64), Arg::from(100_i64), Arg::from("gold")]
+        );
+
+        let (sql, _) = ub.build_with_flavor(Flavor::SQLite, &[]);
+        assert_eq!(
+            sql,
+            "UPDATE orders o SET o.discount = ? FROM customers c \
+             WHERE o.total > ? AND c.id = o.customer_id AND c.tier = ?"
+        );
+
+        let (sql, args) = ub.build_with_flavor(Flavor::SQLServer, &[]);
+        assert_eq!(
+            sql,
+            "UPDATE o SET o.discount = @p1 \
+             FROM orders o JOIN customers c ON c.id = o.customer_id AND c.tier = @p2 \
+             WHERE o.total > @p3"
+        );
+        assert_eq!(
+            args,
+            vec![Arg::from(10_i64), Arg::from("gold"), Arg::from(100_i64)]
+        );
+    }
+
+    #[test]
+    fn update_join_chain_and_returning() {
+        let mut ub = UpdateBuilder::new();
+        ub.update(["accounts"]);
+        ub.join("users u", ["u.id = accounts.user_id"]);
+        ub.join_with_option(Some(JoinOption::LeftJoin), "teams t", ["t.id = u.team_id"]);
+        ub.set(["accounts.team = t.name"]);
+        ub.returning(["id"]);
+
+        let (sql, _) = ub.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "UPDATE accounts SET accounts.team = t.name \
+             FROM users u LEFT JOIN teams t ON t.id = u.team_id \
+             WHERE u.id = accounts.user_id RETURNING id"
+        );
+        let (sql, _) = ub.build_with_flavor(Flavor::SQLServer, &[]);
+        assert_eq!(
+            sql,
+            "UPDATE accounts SET accounts.team = t.name OUTPUT INSERTED.id \
+             FROM accounts JOIN users u ON u.id = accounts.user_id \
+             LEFT JOIN teams t ON t.id = u.team_id"
+        );
+
+        assert_eq!(
+            ub.try_build_with_flavor(Flavor::Oracle, &[]).unwrap_err(),
+            BuildError::Unsupported {
+                flavor: Flavor::Oracle,
+                feature: "UPDATE with JOIN",
+            }
+        );
+
+        let mut ub = UpdateBuilder::new();
+        ub.update(["a"]);
+        ub.join_with_option(Some(JoinOption::LeftJoin), "b", ["b.id = a.id"]);
+        ub.set(["x = b.x"]);
+        ub.allow_full_table();
+        assert!(ub.try_build_with_flavor(Flavor::PostgreSQL, &[]).is_err());
+        assert!(ub.try_build_with_flavor(Flavor::MySQL, &[]).is_ok());
+    }
+
+    #[test]
+    fn update_join_with_empty_where_clause() {
+        let mut ub = UpdateBuilder::new();
+        ub.update(["orders o"]);
+        ub.join("customers c", ["c.id = o.customer_id"]);
+        ub.set(["o.tier = c.tier"]);
+        ub.add_where_clause_ref(&WhereClause::new());
+
+        let (sql, _) = ub.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "UPDATE orders o SET o.tier = c.tier FROM customers c WHERE c.id = o.customer_id"
+        );
+        let (sql, _) = ub.build_with_flavor(Flavor::SQLite, &[]);
+        assert_eq!(
+            sql,
+            "UPDATE orders o SET o.tier = c.tier FROM customers c WHERE c.id = o.customer_id"
+        );
+    }
+
+    fn delete_with_join() -> DeleteBuilder {
+        let mut db = DeleteBuilder::new();
+        db.delete_from(["orders o"]);
+        let on = db.equal("c.status", "banned");
+        db.join("customers c", ["c.id = o.customer_id", on.as_str()]);
+        let cond = db.less_than("o.created_at", "2020-01-01");
+        db.where_([cond]);
+        db.returning(["id"]);
+        db
+    }
+
+    #[test]
+    fn delete_join_per_flavor() {
+        let db = delete_with_join();
+
+        let (sql, args) = db.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "DELETE FROM orders o USING customers c \
+             WHERE o.created_at < $1 AND c.id = o.customer_id AND c.status = $2 RETURNING id"
+        );
+        assert_eq!(args, vec![Arg::from("2020-01-01"), Arg::from("banned")]);
+
+        let (sql, args) = db.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "DELETE o FROM orders o JOIN customers c ON c.id = o.customer_id AND c.status = ? \
+             WHERE o.created_at < ?"
+        );
+        assert_eq!(args, vec![Arg::from("banned"), Arg::from("2020-01-01")]);
+
+        let (sql, _) = db.build_with_flavor(Flavor::SQLServer, &[]);
+        assert_eq!(
+            sql,
+            "DELETE o OUTPUT DELETED.id \
+             FROM orders o JOIN customers c ON c.id = o.customer_id AND c.status = @p1 \
+             WHERE o.created_at < @p2"
+        );
+
+        let (sql, args) = db.build_with_flavor(Flavor::SQLite, &[]);
+        assert_eq!(
+            sql,
+            "DELETE FROM orders o WHERE EXISTS (SELECT 1 FROM customers c \
+             WHERE o.created_at < ? AND c.id = o.customer_id AND c.status = ?) RETURNING id"
+        );
+        assert_eq!(args, vec![Arg::from("2020-01-01"), Arg::from("banned")]);
+
+        let (sql, _) = db.build_with_flavor(Flavor::Oracle, &[]);
+        assert_eq!(
+            sql,
+            "DELETE FROM orders o WHERE EXISTS (SELECT 1 FROM customers c \
+             WHERE o.created_at < :1 AND c.id = o.customer_id AND c.status = :2)"
+        );
+    }
+
+    #[test]
+    fn delete_join_without_where_and_unsupported() {
+        let mut db = DeleteBuilder::new();
+        db.delete_from(["sessions"]);
+        db.join("users u", ["u.id = sessions.user_id"]);
+        db.join_with_option(Some(JoinOption::InnerJoin), "bans b", ["b.user_id = u.id"]);
+
+        let (sql, _) = db.build_with_flavor(Flavor::SQLite, &[]);
+        assert_eq!(
+            sql,
+            "DELETE FROM sessions WHERE EXISTS (SELECT 1 FROM users u \
+             INNER JOIN bans b ON b.user_id = u.id WHERE u.id = sessions.user_id)"
+        );
+        let (sql, _) = db.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "DELETE FROM sessions USING users u INNER JOIN bans b ON b.user_id = u.id \
+             WHERE u.id = sessions.user_id"
+        );
+
+        assert_eq!(
+            db.try_build_with_flavor(Flavor::ClickHouse, &[])
+                .unwrap_err(),
+            BuildError::Unsupported {
+                flavor: Flavor::ClickHouse,
+                feature: "DELETE with JOIN",
+            }
+        );
+
+        let mut db = DeleteBuilder::new();
+        db.delete_from(["a"]);
+        db.join_with_option(Some(JoinOption::LeftJoin), "b", ["b.id = a.id"]);
+        db.allow_full_table();
+        assert!(db.try_build_with_flavor(Flavor::PostgreSQL, &[]).is_err());
+        assert!(db.try_build_with_flavor(Flavor::MySQL, &[]).is_ok());
+    }
+
+    #[test]
+    fn delete_join_where_and_order_limit() {
+        let mut db = DeleteBuilder::new();
+        db.delete_from(["sessions s"]);
+        db.join("users u", ["u.id = s.user_id"]);
+        let cond = db.equal("u.banned", 1_i64);
+        db.where_([cond]);
+
+        // The WHERE may name the joined table, so it lives inside the EXISTS.
+        let (sql, _) = db.build_with_flavor(Flavor::SQLite, &[]);
+        assert_eq!(
+            sql,
+            "DELETE FROM sessions s WHERE EXISTS \
+             (SELECT 1 FROM users u WHERE u.banned = ? AND u.id = s.user_id)"
+        );
+
+        db.order_by(["s.id"]).limit(10);
+        assert_eq!(
+            db.try_build_with_flavor(Flavor::MySQL, &[]).unwrap_err(),
+            BuildError::Unsupported {
+                flavor: Flavor::MySQL,
+                feature: "ORDER BY/LIMIT on multi-table DELETE",
+            }
+        );
+
+        let mut db = DeleteBuilder::new();
+        db.delete_from(["sessions s"]);
+        db.join("users u", ["u.id = s.user_id"]);
+        db.add_where_clause_ref(&WhereClause::new());
+        let (sql, _) = db.build_with_flavor(Flavor::SQLite, &[]);
+        assert_eq!(
+            sql,
+            "DELETE FROM sessions s WHERE EXISTS (SELECT 1 FROM users u WHERE u.id = s.user_id)"
+        );
+        let (sql, _) = db.build_with_flavor(Flavor::PostgreSQL, &[]);
+        assert_eq!(
+            sql,
+            "DELETE FROM sessions s USING users u WHERE u.id = s.user_id"
+        );
+    }
+
+    #[test]
+    fn update_delete_without_where_are_rejected() {
+        let mut ub = UpdateBuilder::new();
+        ub.update(["users"]);
+        ub.set(["active = 0"]);
+        assert_eq!(
+            ub.try_build_with_flavor(Flavor::MySQL, &[]).unwrap_err(),
+            BuildError::MissingWhere {
+                statement: "UPDATE"
+            }
+        );
+
+        // An emptied shared where clause still counts as missing.
+        ub.add_where_clause_ref(&WhereClause::new());
+        assert!(ub.try_build_with_flavor(Flavor::MySQL, &[]).is_err());
+
+        ub.allow_full_table();
+        let (sql, _) = ub.try_build_with_flavor(Flavor::MySQL, &[]).unwrap();
+        assert_eq!(sql, "UPDATE users SET active = 0 ");
+
+        let mut db = DeleteBuilder::new();
+        db.delete_from(["users"]);
+        assert_eq!(
+            db.try_build_with_flavor(Flavor::PostgreSQL, &[])
+                .unwrap_err(),
+            BuildError::MissingWhere {
+                statement: "DELETE"
+            }
+        );
+        assert!(db.clone_builder().allow_full_table().try_build().is_ok());
+
+        // An inner join's ON restricts the affected rows.
+        db.join("bans b", ["b.user_id = users.id"]);
+        assert!(db.try_build_with_flavor(Flavor::PostgreSQL, &[]).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "DELETE without WHERE would affect every row")]
+    fn delete_without_where_panics_on_build() {
+        let mut db = DeleteBuilder::new();
+        db.delete_from(["users"]);
+        db.build();
+    }
+
+    #[test]
+    #[should_panic(expected = "UPDATE without WHERE would affect every row")]
+    fn build_update_with_every_condition_skipped_panics() {
+        let mut skipped = Condition::new("id", Operator::Equal, 1_i64);
+        skipped.skip = true;
+        let mut ub = UpdateBuilder::new();
+        ub.update(["users"]);
+        build_update_with_flavor(Flavor::MySQL, ub, [("name", "bob")], [skipped]);
+    }
+
+    #[test]
+    fn build_delete_respects_allow_full_table() {
+        let mut skipped = Condition::new("id", Operator::Equal, 1_i64);
+        skipped.skip = true;
+        let mut db = DeleteBuilder::new();
+        db.delete_from(["users"]).allow_full_table();
+        let (sql, _) = build_delete_with_flavor(Flavor::MySQL, db, [skipped]);
+        assert_eq!(sql, "DELETE FROM users");
+    }
+
+    #[test]
+    fn update_clone_builder_is_copy_on_write() {
+        let mut base = UpdateBuilder::new();
+        base.update(["users"]);
+        base.set([base.assign("active", 0_i64)]);
+        base.where_([base.equal("tenant", 7_i64)]);
+
+        let mut one = base.clone_builder();
+        one.where_([one.equal("id", 1_i64)]);
+        one.set_more([one.assign("note", "x")]);
+
+        let (sql, args) = base.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(sql, "UPDATE users SET active = ? WHERE tenant = ?");
+        assert_eq!(args.len(), 2);
+
+        let (sql, args) = one.build_with_flavor(Flavor::MySQL, &[]);
+        assert_eq!(
+            sql,
+            "UPDATE users SET active = ?, note = ? WHERE tenant = ? AND id = ?"
+        );
+        assert_eq!(
+            args,
+            vec![
+                Arg::from(0_i64),
+                Arg::from("x"),
+                Arg::from(7_i64),
+                Arg::from(1_i64)
+            ]
+        );
+    }
 }
@@ -11,7 +11,7 @@ use crate::value::SqlValue;
 pub struct ValuerError(pub String);
 
 /// Trait for computing SQL values at interpolation time.
-pub trait SqlValuer: dyn_clone::DynClone + std::fmt::Debug {
+pub trait SqlValuer: dyn_clone::DynClone + std::fmt::Debug + Send + Sync {
     fn value(&self) -> Result<SqlValue, ValuerError>;
 }
 
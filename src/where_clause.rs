@@ -1,19 +1,65 @@
 //! WhereClause: reusable WHERE clause builder.
 
-use crate::args::Args;
+use crate::args::{Args, BuildError, rollback_on_err};
 use crate::flavor::Flavor;
 use crate::macros::{IntoStrings, collect_into_strings};
 use crate::modifiers::{Arg, Builder};
+use crate::shared::{CowArc, Shared};
 use crate::string_builder::{StringBuilder, filter_empty_strings};
-use std::cell::RefCell;
-use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub type ArgsRef = Shared<Args>;
+pub type WhereClauseRef = Shared<WhereClause>;
+
+/// Point the where-clause placeholder `ph` at `wc` if `stale` is set.
+///
+/// `clone_builder` keeps the args shared with the original, so the clone's placeholder still
+/// renders the original's clause; rebinding it on the first build keeps cloning O(1).
+///
+/// `stale` is re-checked and cleared under the args write lock, so a concurrent build of the
+/// same clone either performs the rebind or waits for it before compiling.
+pub(crate) fn rebind_where_var(
+    args: &ArgsRef,
+    wc: Option<&WhereClauseRef>,
+    ph: Option<&String>,
+    stale: &AtomicBool,
+) {
+    if !stale.load(Ordering::Acquire) {
+        return;
+    }
+    let mut args = args.borrow_mut();
+    if !stale.load(Ordering::Acquire) {
+        return;
+    }
+    if let (Some(wc), Some(ph)) = (wc, ph) {
+        args.replace(
+            ph,
+            Arg::Builder(Box::new(WhereClauseBuilder::new(wc.clone()))),
+        );
+    }
+    stale.store(false, Ordering::Release);
+}
 
-pub type ArgsRef = Rc<RefCell<Args>>;
-pub type WhereClauseRef = Rc<RefCell<WhereClause>>;
+/// WhereWith: the WHERE of a builder whose where clause `wc` renders through placeholder `ph`,
+/// plus `extra`, a predicate the builder adds itself (e.g. a join's ON moved out of FROM).
+/// `extra` is only joined with `AND` when the clause has predicates of its own.
+pub(crate) fn where_with(
+    ph: Option<&String>,
+    wc: Option<&WhereClauseRef>,
+    extra: Option<String>,
+) -> Option<String> {
+    let Some(extra) = extra else {
+        return ph.cloned();
+    };
+    match ph.filter(|_| wc.is_some_and(|wc| !wc.borrow().is_empty())) {
+        Some(ph) => Some(format!("{ph} AND {extra}")),
+        None => Some(format!("WHERE {extra}")),
+    }
+}
 
 /// CopyWhereClause: deep clone a WhereClause.
 pub fn copy_where_clause(wc: &WhereClauseRef) -> WhereClauseRef {
-    Rc::new(RefCell::new(wc.borrow().clone()))
+    Shared::new(wc.borrow().clone())
 }
 
 #[derive(Debug, Clone)]
@@ -23,29 +69,53 @@ struct Clause {
 }
 
 impl Clause {
-    fn build(&self, flavor: Flavor, initial: &[Arg]) -> (String, Vec<Arg>) {
+    fn build(
+        &self,
+        flavor: Flavor,
+        initial: &[Arg],
+        strict: bool,
+    ) -> Result<(String, Vec<Arg>), BuildError> {
         let exprs = filter_empty_strings(self.and_exprs.clone());
         if exprs.is_empty() {
-            return (String::new(), initial.to_vec());
+            return Ok((String::new(), initial.to_vec()));
         }
         let mut buf = StringBuilder::new();
         buf.write_strings(&exprs, " AND ");
+        let args = self.args.snapshot();
+        if strict {
+            args.try_compile_with_flavor(&buf.into_string(), flavor, initial)
+        } else {
+            Ok(args.compile_with_flavor(&buf.into_string(), flavor, initial))
+        }
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        let exprs = filter_empty_strings(self.and_exprs.clone());
+        if exprs.is_empty() {
+            return Ok(());
+        }
         self.args
-            .borrow()
-            .compile_with_flavor(&buf.into_string(), flavor, initial)
+            .snapshot()
+            .compile_into(&exprs.join(" AND "), flavor, sql, args)
     }
 }
 
-/// WhereClause: shareable but not thread-safe.
+/// WhereClause: shared between builders through `WhereClauseRef`; `Send + Sync`, clauses are
+/// stored copy-on-write.
 #[derive(Debug, Default, Clone)]
 pub struct WhereClause {
     flavor: Flavor,
-    clauses: Vec<Clause>,
+    clauses: CowArc<Vec<Clause>>,
 }
 
 impl WhereClause {
     pub fn new() -> WhereClauseRef {
-        Rc::new(RefCell::new(Self::default()))
+        Shared::new(Self::default())
     }
 
     pub fn set_flavor(&mut self, flavor: Flavor) -> Flavor {
@@ -69,7 +139,7 @@ impl WhereClause {
         }
 
         if let Some(last) = self.clauses.last_mut()
-            && Rc::ptr_eq(&last.args, &args)
+            && Shared::ptr_eq(&last.args, &args)
         {
             last.and_exprs.extend(exprs);
             return;
@@ -82,7 +152,12 @@ impl WhereClause {
     }
 
     pub fn add_where_clause(&mut self, other: &WhereClause) {
-        self.clauses.extend(other.clauses.clone());
+        self.clauses.extend(other.clauses.iter().cloned());
+    }
+
+    /// Whether the clause holds no predicates; an empty clause renders as nothing.
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
     }
 }
 
@@ -98,28 +173,71 @@ impl WhereClauseBuilder {
     }
 }
 
-impl Builder for WhereClauseBuilder {
-    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
-        let wc = self.wc.borrow();
+impl WhereClauseBuilder {
+    fn build_clauses(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+        strict: bool,
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        let wc = self.wc.snapshot();
         if wc.clauses.is_empty() {
-            return (String::new(), initial_arg.to_vec());
+            return Ok((String::new(), initial_arg.to_vec()));
         }
 
         let mut buf = StringBuilder::new();
         buf.write_str("WHERE ");
 
-        let (sql0, args0) = wc.clauses[0].build(flavor, initial_arg);
+        let (sql0, args0) = wc.clauses[0].build(flavor, initial_arg, strict)?;
         buf.write_str(&sql0);
         let mut args = args0;
 
         for clause in &wc.clauses[1..] {
             buf.write_str(" AND ");
-            let (s, a) = clause.build(flavor, &args);
+            let (s, a) = clause.build(flavor, &args, strict)?;
             buf.write_str(&s);
             args = a;
         }
 
-        (buf.into_string(), args)
+        Ok((buf.into_string(), args))
+    }
+}
+
+impl Builder for WhereClauseBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: &[Arg]) -> (String, Vec<Arg>) {
+        self.build_clauses(flavor, initial_arg, false)
+            .expect("lenient compile never fails")
+    }
+
+    fn try_build_with_flavor(
+        &self,
+        flavor: Flavor,
+        initial_arg: &[Arg],
+    ) -> Result<(String, Vec<Arg>), BuildError> {
+        self.build_clauses(flavor, initial_arg, true)
+    }
+
+    fn build_into(
+        &self,
+        flavor: Flavor,
+        sql: &mut String,
+        args: &mut Vec<Arg>,
+    ) -> Result<(), BuildError> {
+        let wc = self.wc.snapshot();
+        if wc.clauses.is_empty() {
+            return Ok(());
+        }
+
+        rollback_on_err(sql, args, |sql, args| {
+            sql.push_str("WHERE ");
+            for (i, clause) in wc.clauses.iter().enumerate() {
+                if i > 0 {
+                    sql.push_str(" AND ");
+                }
+                clause.build_into(flavor, sql, args)?;
+            }
+            Ok(())
+        })
     }
 
     fn flavor(&self) -> Flavor {
@@ -4,12 +4,11 @@ mod tests {
     use crate::delete::DeleteBuilder;
     use crate::modifiers::{Arg, Builder, rc_builder};
     use crate::select::SelectBuilder;
+    use crate::shared::Shared;
     use crate::update::UpdateBuilder;
     use crate::where_clause::{WhereClause, copy_where_clause};
     use crate::{delete_from_tables, from_tables, select_cols, update_tables, where_exprs};
     use pretty_assertions::assert_eq;
-    use std::cell::RefCell;
-    use std::rc::Rc;
 
     #[test]
     fn where_clause_shared_instances_like_go() {
@@ -17,9 +16,9 @@ mod tests {
         select_cols!(sb, "*");
         from_tables!(sb, "t");
         let mut ub = UpdateBuilder::new();
-        update_tables!(ub, "t").set(["foo = 1"]);
+        update_tables!(ub, "t").set(["foo = 1"]).allow_full_table();
         let mut db = DeleteBuilder::new();
-        delete_from_tables!(db, "t");
+        delete_from_tables!(db, "t").allow_full_table();
 
         let where_clause = WhereClause::new();
         sb.set_where_clause(Some(where_clause.clone()));
@@ -81,7 +80,7 @@ mod tests {
         );
 
         // Nested WhereClause + late-binding builder: pass sb as subquery first, then keep mutating sb.
-        let sb_shared = Rc::new(RefCell::new(sb));
+        let sb_shared = Shared::new(sb);
         let sb_arg: Arg = (Box::new(rc_builder(sb_shared.clone())) as Box<dyn Builder>).into();
         ub.where_([ub.not_in("id", [sb_arg])]);
 
@@ -107,11 +106,11 @@ mod tests {
         where_exprs!(sb, blank);
 
         let mut ub = UpdateBuilder::new();
-        update_tables!(ub, "t").set(["foo = 1"]);
+        update_tables!(ub, "t").set(["foo = 1"]).allow_full_table();
         where_exprs!(ub, blank);
 
         let mut db = DeleteBuilder::new();
-        delete_from_tables!(db, "t");
+        delete_from_tables!(db, "t").allow_full_table();
         where_exprs!(db, blank);
 
         assert_eq!(sb.build().0, "SELECT * FROM t");
@@ -129,11 +128,11 @@ mod tests {
         where_exprs!(sb, empty);
 
         let mut ub = UpdateBuilder::new();
-        update_tables!(ub, "t").set(["foo = 1"]);
+        update_tables!(ub, "t").set(["foo = 1"]).allow_full_table();
         where_exprs!(ub, empty);
 
         let mut db = DeleteBuilder::new();
-        delete_from_tables!(db, "t");
+        delete_from_tables!(db, "t").allow_full_table();
         where_exprs!(db, empty);
 
         assert_eq!(sb.build().0, "SELECT * FROM t");
@@ -148,10 +147,10 @@ mod tests {
         from_tables!(sb, "t");
 
         let mut ub = UpdateBuilder::new();
-        update_tables!(ub, "t").set(["foo = 1"]);
+        update_tables!(ub, "t").set(["foo = 1"]).allow_full_table();
 
         let mut db = DeleteBuilder::new();
-        delete_from_tables!(db, "t");
+        delete_from_tables!(db, "t").allow_full_table();
 
         let cond = Cond::new();
         let wc = WhereClause::new();
@@ -174,10 +173,10 @@ mod tests {
         from_tables!(sb, "t");
 
         let mut ub = UpdateBuilder::new();
-        update_tables!(ub, "t").set(["foo = 1"]);
+        update_tables!(ub, "t").set(["foo = 1"]).allow_full_table();
 
         let mut db = DeleteBuilder::new();
-        delete_from_tables!(db, "t");
+        delete_from_tables!(db, "t").allow_full_table();
 
         let cond = Cond::new();
         let wc = WhereClause::new();
@@ -0,0 +1,162 @@
+//! WindowSpec: window definitions for `OVER (...)` and `WINDOW name AS (...)`.
+
+use crate::macros::{IntoStrings, collect_into_strings};
+use crate::modifiers::Arg;
+
+/// Frame unit of a window frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameUnit {
+    Rows,
+    Range,
+    Groups,
+}
+
+impl FrameUnit {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Rows => "ROWS",
+            Self::Range => "RANGE",
+            Self::Groups => "GROUPS",
+        }
+    }
+}
+
+/// Frame bound; `Preceding`/`Following` offsets are bound as args.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(Arg),
+    CurrentRow,
+    Following(Arg),
+    UnboundedFollowing,
+}
+
+impl FrameBound {
+    pub fn preceding(offset: impl Into<Arg>) -> Self {
+        Self::Preceding(offset.into())
+    }
+
+    pub fn following(offset: impl Into<Arg>) -> Self {
+        Self::Following(offset.into())
+    }
+
+    fn render(&self, var: &mut dyn FnMut(Arg) -> String) -> String {
+        match self {
+            Self::UnboundedPreceding => "UNBOUNDED PRECEDING".to_string(),
+            Self::Preceding(n) => format!("{} PRECEDING", var(n.clone())),
+            Self::CurrentRow => "CURRENT ROW".to_string(),
+            Self::Following(n) => format!("{} FOLLOWING", var(n.clone())),
+            Self::UnboundedFollowing => "UNBOUNDED FOLLOWING".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Frame {
+    unit: FrameUnit,
+    start: FrameBound,
+    end: Option<FrameBound>,
+}
+
+/// WindowSpec: `PARTITION BY ... ORDER BY ... <frame>`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowSpec {
+    base: Option<String>,
+    partition_by: Vec<String>,
+    order_by: Vec<String>,
+    frame: Option<Frame>,
+}
+
+impl WindowSpec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Extend a named window: `(base ORDER BY ...)`.
+    pub fn from_window(base: impl Into<String>) -> Self {
+        Self {
+            base: Some(base.into()),
+            ..Self::default()
+        }
+    }
+
+    pub fn partition_by<T>(mut self, cols: T) -> Self
+    where
+        T: IntoStrings,
+    {
+        self.partition_by.extend(collect_into_strings(cols));
+        self
+    }
+
+    pub fn order_by<T>(mut self, cols: T) -> Self
+    where
+        T: IntoStrings,
+    {
+        self.order_by.extend(collect_into_strings(cols));
+        self
+    }
+
+    pub fn order_by_asc(mut self, col: impl Into<String>) -> Self {
+        self.order_by.push(format!("{} ASC", col.into()));
+        self
+    }
+
+    pub fn order_by_desc(mut self, col: impl Into<String>) -> Self {
+        self.order_by.push(format!("{} DESC", col.into()));
+        self
+    }
+
+    /// Frame with a start bound only, e.g. `ROWS UNBOUNDED PRECEDING`.
+    pub fn frame(mut self, unit: FrameUnit, start: FrameBound) -> Self {
+        self.frame = Some(Frame {
+            unit,
+            start,
+            end: None,
+        });
+        self
+    }
+
+    /// `<unit> BETWEEN start AND end`.
+    pub fn frame_between(mut self, unit: FrameUnit, start: FrameBound, end: FrameBound) -> Self {
+        self.frame = Some(Frame {
+            unit,
+            start,
+            end: Some(end),
+        });
+        self
+    }
+
+    pub fn rows_between(self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame_between(FrameUnit::Rows, start, end)
+    }
+
+    pub fn range_between(self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame_between(FrameUnit::Range, start, end)
+    }
+
+    /// Render the spec body (without parentheses); frame offsets go through `var`.
+    pub(crate) fn render(&self, var: &mut dyn FnMut(Arg) -> String) -> String {
+        let mut parts = Vec::new();
+        if let Some(base) = &self.base {
+            parts.push(base.clone());
+        }
+        if !self.partition_by.is_empty() {
+            parts.push(format!("PARTITION BY {}", self.partition_by.join(", ")));
+        }
+        if !self.order_by.is_empty() {
+            parts.push(format!("ORDER BY {}", self.order_by.join(", ")));
+        }
+        if let Some(frame) = &self.frame {
+            let start = frame.start.render(var);
+            parts.push(match &frame.end {
+                Some(end) => format!(
+                    "{} BETWEEN {start} AND {}",
+                    frame.unit.as_str(),
+                    end.render(var)
+                ),
+                None => format!("{} {start}", frame.unit.as_str()),
+            });
+        }
+        parts.join(" ")
+    }
+}